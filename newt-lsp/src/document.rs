@@ -0,0 +1,81 @@
+use crate::line_index::LineIndex;
+use newtc::featurez::{SyntaxTree, TextEdit, TextRange};
+use serde_json::{json, Value};
+
+pub struct Document {
+    text: String,
+    lines: LineIndex,
+    tree: SyntaxTree,
+}
+
+impl Document {
+    pub fn new(text: String) -> Document {
+        let tree = SyntaxTree::from_stmts(&text);
+
+        Document {
+            lines: LineIndex::new(&text),
+            text,
+            tree,
+        }
+    }
+
+    pub fn change(&mut self, change: &Value) {
+        let insert = change.get("text").and_then(Value::as_str).unwrap_or("");
+        let range = change.get("range").and_then(|range| self.range(range));
+
+        let range = match range {
+            Some(range) => range,
+            None => {
+                *self = Document::new(insert.to_string());
+                return;
+            }
+        };
+
+        let edit = TextEdit::new(range, insert);
+        self.text = edit.apply(&self.text, 0);
+        self.lines = LineIndex::new(&self.text);
+        self.tree = self.tree.reparse(&edit);
+    }
+
+    pub fn tree(&self) -> &SyntaxTree {
+        &self.tree
+    }
+
+    pub fn diagnostics(&self) -> Vec<Value> {
+        self.tree
+            .errors()
+            .map(|error| self.diagnostic(error.range(), &error.description()))
+            .collect()
+    }
+
+    pub fn offset(&self, position: &Value) -> Option<usize> {
+        self.lines.offset_json(&self.text, position)
+    }
+
+    pub fn range(&self, range: &Value) -> Option<TextRange> {
+        let start = self.offset(range.get("start")?)?;
+        let end = self.offset(range.get("end")?)?;
+
+        if start <= end {
+            Some(TextRange::new(start, end))
+        } else {
+            None
+        }
+    }
+
+    pub fn range_json(&self, range: TextRange) -> Value {
+        json!({
+            "start": self.lines.position_json(&self.text, range.start()),
+            "end": self.lines.position_json(&self.text, range.end()),
+        })
+    }
+
+    fn diagnostic(&self, range: TextRange, message: &str) -> Value {
+        json!({
+            "range": self.range_json(range),
+            "severity": 1,
+            "source": "newtc",
+            "message": message,
+        })
+    }
+}
@@ -0,0 +1,62 @@
+use serde_json::{json, Value};
+
+/// LSP positions are zero-based lines and UTF-16 characters.
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> LineIndex {
+        let mut line_starts = vec![0];
+        line_starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+
+        LineIndex { line_starts }
+    }
+
+    pub fn position(&self, text: &str, offset: usize) -> (usize, usize) {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let character = text[self.line_starts[line]..offset].encode_utf16().count();
+
+        (line, character)
+    }
+
+    /// Positions past the end of a line clamp to its end, and positions past the last line
+    /// clamp to the end of the text.
+    pub fn offset(&self, text: &str, line: usize, character: usize) -> usize {
+        let line_start = match self.line_starts.get(line) {
+            Some(line_start) => *line_start,
+            None => return text.len(),
+        };
+        let line_end = self
+            .line_starts
+            .get(line + 1)
+            .map_or(text.len(), |end| *end);
+        let mut units = 0;
+
+        for (i, c) in text[line_start..line_end].char_indices() {
+            if units >= character || c == '\n' {
+                return line_start + i;
+            }
+
+            units += c.len_utf16();
+        }
+
+        line_end
+    }
+
+    pub fn position_json(&self, text: &str, offset: usize) -> Value {
+        let (line, character) = self.position(text, offset);
+
+        json!({ "line": line, "character": character })
+    }
+
+    pub fn offset_json(&self, text: &str, position: &Value) -> Option<usize> {
+        let line = position.get("line")?.as_u64()? as usize;
+        let character = position.get("character")?.as_u64()? as usize;
+
+        Some(self.offset(text, line, character))
+    }
+}
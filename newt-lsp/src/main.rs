@@ -0,0 +1,30 @@
+//! A language server for Newt.  It speaks LSP over stdin and stdout, publishing syntax errors
+//! as diagnostics and answering hover, go-to-definition, completion and document outline
+//! requests from each document's syntax tree.
+
+mod document;
+mod line_index;
+mod scopes;
+mod server;
+mod transport;
+
+mod tests;
+
+use crate::server::Server;
+use std::io::{stdin, stdout};
+use std::process::exit;
+
+fn main() {
+    let stdin = stdin();
+    let stdout = stdout();
+    let mut server = Server::new();
+
+    let exit_code = server
+        .run(&mut stdin.lock(), &mut stdout.lock())
+        .unwrap_or_else(|error| {
+            eprintln!("newt-lsp: {}", error);
+            1
+        });
+
+    exit(exit_code);
+}
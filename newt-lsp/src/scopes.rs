@@ -0,0 +1,302 @@
+use newtc::featurez::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TokenKind};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BindingKind {
+    Variable,
+    Parameter,
+    Function,
+    Struct,
+    Interface,
+    Module,
+}
+
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    pub declaration: TextRange,
+    pub scope: TextRange,
+    pub detail: String,
+}
+
+impl Binding {
+    fn is_visible_at(&self, offset: usize) -> bool {
+        self.scope.contains(offset)
+    }
+}
+
+/// Collects the bindings declared in `root`.  Functions, structs and interfaces are visible
+/// throughout their block, variables and imports from the end of their statement, and
+/// parameters within their body.
+pub fn bindings(root: &SyntaxNode) -> Vec<Binding> {
+    let mut bindings = Vec::new();
+    collect(
+        root,
+        TextRange::new(root.range().start(), usize::MAX),
+        &mut bindings,
+    );
+
+    bindings
+}
+
+pub fn resolve<'a>(bindings: &'a [Binding], name: &str, offset: usize) -> Option<&'a Binding> {
+    let candidates = bindings.iter().filter(|b| b.name == name);
+
+    if let Some(declared) = candidates
+        .clone()
+        .find(|b| b.declaration.start() <= offset && offset <= b.declaration.end())
+    {
+        return Some(declared);
+    }
+
+    candidates
+        .filter(|b| b.is_visible_at(offset))
+        .min_by_key(|b| (b.scope.len(), usize::MAX - b.declaration.start()))
+}
+
+pub fn visible_at(bindings: &[Binding], offset: usize) -> Vec<&Binding> {
+    let mut visible: Vec<&Binding> = bindings
+        .iter()
+        .filter(|b| b.is_visible_at(offset))
+        .collect();
+    visible.sort_by_key(|b| (b.scope.len(), usize::MAX - b.declaration.start()));
+
+    let mut names: Vec<&str> = Vec::new();
+    visible.retain(|b| {
+        if names.contains(&b.name.as_str()) {
+            return false;
+        }
+
+        names.push(&b.name);
+        true
+    });
+
+    visible
+}
+
+pub fn identifier_at(root: &SyntaxNode, offset: usize) -> Option<&SyntaxToken> {
+    for child in root.children() {
+        let range = child.range();
+
+        if offset < range.start() || range.end() < offset {
+            continue;
+        }
+
+        let identifier = match child {
+            SyntaxElement::Node(node) => identifier_at(node, offset),
+            SyntaxElement::Token(token) if token.token_kind() == TokenKind::Identifier => {
+                Some(token)
+            }
+            SyntaxElement::Token(_) => None,
+        };
+
+        if identifier.is_some() {
+            return identifier;
+        }
+    }
+
+    None
+}
+
+pub fn text_before<P: Fn(&SyntaxElement) -> bool>(node: &SyntaxNode, until: P) -> String {
+    let text: String = node
+        .children()
+        .iter()
+        .take_while(|child| !until(child))
+        .map(SyntaxElement::text)
+        .collect();
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn signature(function: &SyntaxNode) -> String {
+    text_before(function, |child| is_node(child, SyntaxKind::StmtListStmt))
+}
+
+pub fn body(node: &SyntaxNode) -> Option<&SyntaxNode> {
+    node.nodes().find(|n| n.kind() == SyntaxKind::StmtListStmt)
+}
+
+pub fn first_identifier(node: &SyntaxNode) -> Option<&SyntaxToken> {
+    node.tokens()
+        .find(|t| t.token_kind() == TokenKind::Identifier)
+}
+
+fn collect(node: &SyntaxNode, scope: TextRange, bindings: &mut Vec<Binding>) {
+    for child in node.nodes() {
+        let after = TextRange::new(child.range().end().min(scope.end()), scope.end());
+
+        match child.kind() {
+            SyntaxKind::VariableDeclarationStmt => {
+                let detail = text_before(child, |c| is_token(c, TokenKind::Equals));
+                declare(bindings, child, BindingKind::Variable, after, detail);
+            }
+            SyntaxKind::FunctionDeclarationStmt => {
+                // Methods are reached through their receiver rather than by name.
+                if node.kind() != SyntaxKind::ImplStmt {
+                    declare(
+                        bindings,
+                        child,
+                        BindingKind::Function,
+                        scope,
+                        signature(child),
+                    );
+                }
+
+                if let Some(body) = body(child) {
+                    declare_parameters(child, body.range(), bindings);
+                }
+            }
+            SyntaxKind::FunctionExpr => {
+                if let Some(body) = body(child) {
+                    declare_parameters(child, body.range(), bindings);
+                }
+            }
+            SyntaxKind::ForStmt => {
+                // The loop's label, if it has one, comes before the variable
+                let identifier = child
+                    .tokens()
+                    .skip_while(|t| t.token_kind() != TokenKind::For)
+                    .find(|t| t.token_kind() == TokenKind::Identifier);
+
+                if let (Some(identifier), Some(body)) = (identifier, body(child)) {
+                    bindings.push(Binding {
+                        name: identifier.lexeme().to_string(),
+                        kind: BindingKind::Variable,
+                        declaration: identifier.range(),
+                        scope: body.range(),
+                        detail: format!("for {}", identifier.lexeme()),
+                    });
+                }
+            }
+            SyntaxKind::CatchClause => {
+                if let Some(body) = body(child) {
+                    let detail = text_before(child, |c| is_node(c, SyntaxKind::StmtListStmt));
+                    declare(bindings, child, BindingKind::Variable, body.range(), detail);
+                }
+            }
+            SyntaxKind::MatchArm => {
+                if let Some(pattern) = child.nodes().next() {
+                    declare_pattern(pattern, child.range(), bindings);
+                }
+            }
+            SyntaxKind::StructDeclarationStmt => {
+                let detail = text_before(child, |c| is_token(c, TokenKind::LeftBrace));
+                declare(bindings, child, BindingKind::Struct, scope, detail);
+            }
+            SyntaxKind::InterfaceDeclarationStmt => {
+                let detail = text_before(child, |c| is_token(c, TokenKind::LeftBrace));
+                declare(bindings, child, BindingKind::Interface, scope, detail);
+            }
+            SyntaxKind::ImportStmt => {
+                let identifier = child
+                    .tokens()
+                    .filter(|t| t.token_kind() == TokenKind::Identifier)
+                    .last();
+
+                if let Some(identifier) = identifier {
+                    bindings.push(Binding {
+                        name: identifier.lexeme().to_string(),
+                        kind: BindingKind::Module,
+                        declaration: identifier.range(),
+                        scope: after,
+                        detail: text_before(child, |c| is_token(c, TokenKind::SemiColon)),
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        let child_scope = match child.kind() {
+            SyntaxKind::StmtListStmt => child.range(),
+            _ => scope,
+        };
+
+        collect(child, child_scope, bindings);
+    }
+}
+
+fn declare(
+    bindings: &mut Vec<Binding>,
+    node: &SyntaxNode,
+    kind: BindingKind,
+    scope: TextRange,
+    detail: String,
+) {
+    if let Some(identifier) = first_identifier(node) {
+        bindings.push(Binding {
+            name: identifier.lexeme().to_string(),
+            kind,
+            declaration: identifier.range(),
+            scope,
+            detail,
+        });
+    }
+}
+
+fn declare_parameters(function: &SyntaxNode, scope: TextRange, bindings: &mut Vec<Binding>) {
+    let mut children = function
+        .children()
+        .iter()
+        .filter(|c| c.is_node() || c.as_token().is_some())
+        .skip_while(|c| !is_token(c, TokenKind::LeftParenthesis))
+        .skip(1)
+        .take_while(|c| !is_token(c, TokenKind::RightParenthesis))
+        .peekable();
+
+    while let Some(child) = children.next() {
+        let identifier = match child.as_token() {
+            Some(token) if token.token_kind() == TokenKind::Identifier => token,
+            _ => continue,
+        };
+        let mut detail = identifier.lexeme().to_string();
+
+        if children
+            .peek()
+            .map_or(false, |c| is_token(c, TokenKind::Colon))
+        {
+            children.next();
+
+            if let Some(SyntaxElement::Node(type_ref)) = children.peek() {
+                detail = format!("{}: {}", detail, type_ref.text().trim());
+            }
+        }
+
+        bindings.push(Binding {
+            name: identifier.lexeme().to_string(),
+            kind: BindingKind::Parameter,
+            declaration: identifier.range(),
+            scope,
+            detail,
+        });
+    }
+}
+
+fn declare_pattern(pattern: &SyntaxNode, scope: TextRange, bindings: &mut Vec<Binding>) {
+    let binds = match pattern.kind() {
+        SyntaxKind::BindingPattern => true,
+        SyntaxKind::PatternField => !has_token(pattern, TokenKind::Colon),
+        _ => false,
+    };
+
+    if binds {
+        let detail = format!("match {}", pattern.text().trim());
+        declare(bindings, pattern, BindingKind::Variable, scope, detail);
+    }
+
+    for child in pattern.nodes() {
+        declare_pattern(child, scope, bindings);
+    }
+}
+
+fn has_token(node: &SyntaxNode, kind: TokenKind) -> bool {
+    node.children().iter().any(|child| is_token(child, kind))
+}
+
+fn is_node(element: &SyntaxElement, kind: SyntaxKind) -> bool {
+    element.as_node().map_or(false, |n| n.kind() == kind)
+}
+
+fn is_token(element: &SyntaxElement, kind: TokenKind) -> bool {
+    element.as_token().map_or(false, |t| t.token_kind() == kind)
+}
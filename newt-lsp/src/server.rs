@@ -0,0 +1,321 @@
+use crate::document::Document;
+use crate::scopes::{self, BindingKind};
+use crate::transport::{read_message, write_message};
+use newtc::featurez::{SyntaxKind, SyntaxNode};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, ErrorKind, Result, Write};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+
+struct ResponseError {
+    code: i64,
+    message: String,
+}
+
+impl ResponseError {
+    fn new(code: i64, message: &str) -> ResponseError {
+        ResponseError {
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+pub struct Server {
+    documents: HashMap<String, Document>,
+    initialized: bool,
+    shutdown: bool,
+    exit_code: Option<i32>,
+}
+
+impl Server {
+    pub fn new() -> Server {
+        Server {
+            documents: HashMap::new(),
+            initialized: false,
+            shutdown: false,
+            exit_code: None,
+        }
+    }
+
+    pub fn run<R: BufRead, W: Write>(&mut self, input: &mut R, output: &mut W) -> Result<i32> {
+        loop {
+            let message = match read_message(input) {
+                Ok(Some(message)) => message,
+                Ok(None) => return Ok(1),
+                Err(ref error) if error.kind() == ErrorKind::InvalidData => {
+                    let error = ResponseError::new(PARSE_ERROR, &error.to_string());
+                    write_message(output, &response(Value::Null, Err(error)))?;
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+
+            for reply in self.handle(&message) {
+                write_message(output, &reply)?;
+            }
+
+            if let Some(exit_code) = self.exit_code {
+                return Ok(exit_code);
+            }
+        }
+    }
+
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = match message.get("method").and_then(Value::as_str) {
+            Some(method) => method,
+            // Responses to requests the server never sends.
+            None => return Vec::new(),
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        match message.get("id") {
+            Some(id) => vec![response(id.clone(), self.request(method, &params))],
+            None => self.notification(method, &params),
+        }
+    }
+
+    fn request(
+        &mut self,
+        method: &str,
+        params: &Value,
+    ) -> std::result::Result<Value, ResponseError> {
+        if self.shutdown {
+            return Err(ResponseError::new(
+                INVALID_REQUEST,
+                "the server is shutting down",
+            ));
+        }
+
+        if !self.initialized && method != "initialize" {
+            return Err(ResponseError::new(
+                SERVER_NOT_INITIALIZED,
+                "the server has not been initialized",
+            ));
+        }
+
+        match method {
+            "initialize" => {
+                self.initialized = true;
+                Ok(initialize_result())
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => self.with_position(params, hover),
+            "textDocument/definition" => self.with_position(params, definition),
+            "textDocument/completion" => self.with_position(params, completion),
+            "textDocument/documentSymbol" => {
+                let document = self.document(params)?;
+                let tree = document.tree().root().as_node();
+
+                Ok(json!(
+                    tree.map_or_else(Vec::new, |root| outline(document, root))
+                ))
+            }
+            _ => Err(ResponseError::new(
+                METHOD_NOT_FOUND,
+                &format!("unsupported method {}", method),
+            )),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or("")
+            .to_string();
+
+        match method {
+            "exit" => {
+                self.exit_code = Some(if self.shutdown { 0 } else { 1 });
+                Vec::new()
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or("");
+                self.documents
+                    .insert(uri.clone(), Document::new(text.to_string()));
+
+                vec![self.publish_diagnostics(&uri)]
+            }
+            "textDocument/didChange" => {
+                let document = match self.documents.get_mut(&uri) {
+                    Some(document) => document,
+                    None => return Vec::new(),
+                };
+
+                for change in params["contentChanges"].as_array().into_iter().flatten() {
+                    document.change(change);
+                }
+
+                vec![self.publish_diagnostics(&uri)]
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+
+                vec![self.publish_diagnostics(&uri)]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: &str) -> Value {
+        let diagnostics = self
+            .documents
+            .get(uri)
+            .map_or_else(Vec::new, Document::diagnostics);
+
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        })
+    }
+
+    fn document(&self, params: &Value) -> std::result::Result<&Document, ResponseError> {
+        params["textDocument"]["uri"]
+            .as_str()
+            .and_then(|uri| self.documents.get(uri))
+            .ok_or_else(|| ResponseError::new(INVALID_PARAMS, "the document is not open"))
+    }
+
+    fn with_position<F>(
+        &self,
+        params: &Value,
+        answer: F,
+    ) -> std::result::Result<Value, ResponseError>
+    where
+        F: Fn(&str, &Document, &SyntaxNode, usize) -> Value,
+    {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or("");
+        let document = self.document(params)?;
+        let offset = document
+            .offset(&params["position"])
+            .ok_or_else(|| ResponseError::new(INVALID_PARAMS, "expected a position"))?;
+
+        Ok(document
+            .tree()
+            .root()
+            .as_node()
+            .map_or(Value::Null, |root| answer(uri, document, root, offset)))
+    }
+}
+
+fn response(id: Value, result: std::result::Result<Value, ResponseError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code, "message": error.message },
+        }),
+    }
+}
+
+fn initialize_result() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": { "openClose": true, "change": 2 },
+            "hoverProvider": true,
+            "definitionProvider": true,
+            "completionProvider": {},
+            "documentSymbolProvider": true,
+        },
+        "serverInfo": { "name": "newt-lsp", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+fn hover(_: &str, document: &Document, root: &SyntaxNode, offset: usize) -> Value {
+    let identifier = match scopes::identifier_at(root, offset) {
+        Some(identifier) => identifier,
+        None => return Value::Null,
+    };
+    let bindings = scopes::bindings(root);
+
+    match scopes::resolve(&bindings, identifier.lexeme(), identifier.range().start()) {
+        Some(binding) => json!({
+            "contents": {
+                "kind": "markdown",
+                "value": format!("```newt\n{}\n```", binding.detail),
+            },
+            "range": document.range_json(identifier.range()),
+        }),
+        None => Value::Null,
+    }
+}
+
+fn definition(uri: &str, document: &Document, root: &SyntaxNode, offset: usize) -> Value {
+    let identifier = match scopes::identifier_at(root, offset) {
+        Some(identifier) => identifier,
+        None => return Value::Null,
+    };
+    let bindings = scopes::bindings(root);
+
+    match scopes::resolve(&bindings, identifier.lexeme(), identifier.range().start()) {
+        Some(binding) => json!({ "uri": uri, "range": document.range_json(binding.declaration) }),
+        None => Value::Null,
+    }
+}
+
+fn completion(_: &str, _: &Document, root: &SyntaxNode, offset: usize) -> Value {
+    let bindings = scopes::bindings(root);
+    let items: Vec<Value> = scopes::visible_at(&bindings, offset)
+        .into_iter()
+        .map(|binding| {
+            json!({
+                "label": binding.name,
+                "kind": completion_kind(binding.kind),
+                "detail": binding.detail,
+            })
+        })
+        .collect();
+
+    json!(items)
+}
+
+fn completion_kind(kind: BindingKind) -> u8 {
+    match kind {
+        BindingKind::Variable | BindingKind::Parameter => 6,
+        BindingKind::Function => 3,
+        BindingKind::Struct => 22,
+        BindingKind::Interface => 8,
+        BindingKind::Module => 9,
+    }
+}
+
+fn outline(document: &Document, node: &SyntaxNode) -> Vec<Value> {
+    let mut symbols = Vec::new();
+
+    for child in node.nodes() {
+        if child.kind() != SyntaxKind::FunctionDeclarationStmt {
+            symbols.extend(outline(document, child));
+            continue;
+        }
+
+        let identifier = match scopes::first_identifier(child) {
+            Some(identifier) => identifier,
+            None => continue,
+        };
+        let kind = match node.kind() {
+            SyntaxKind::ImplStmt => 6,
+            _ => 12,
+        };
+
+        symbols.push(json!({
+            "name": identifier.lexeme(),
+            "detail": scopes::signature(child),
+            "kind": kind,
+            "range": document.range_json(child.range()),
+            "selectionRange": document.range_json(identifier.range()),
+            "children": outline(document, child),
+        }));
+    }
+
+    symbols
+}
@@ -0,0 +1,68 @@
+#![cfg(test)]
+
+use crate::line_index::LineIndex;
+use crate::scopes::{bindings, resolve, visible_at, BindingKind};
+use newtc::featurez::SyntaxTree;
+
+#[test]
+fn line_index_round_trips_positions() {
+    let text = "let a = 1;\nlet b = 2;\n";
+    let lines = LineIndex::new(text);
+
+    assert_eq!((0, 0), lines.position(text, 0));
+    assert_eq!((1, 4), lines.position(text, 15));
+    assert_eq!((2, 0), lines.position(text, text.len()));
+    assert_eq!(15, lines.offset(text, 1, 4));
+    assert_eq!(10, lines.offset(text, 0, 99));
+    assert_eq!(text.len(), lines.offset(text, 7, 0));
+}
+
+#[test]
+fn line_index_counts_characters_in_utf16_code_units() {
+    let text = "// 😀é\nx";
+    let lines = LineIndex::new(text);
+
+    assert_eq!((0, 6), lines.position(text, text.find('\n').unwrap()));
+    assert_eq!(text.find('é').unwrap(), lines.offset(text, 0, 5));
+}
+
+#[test]
+fn bindings_are_scoped_to_their_blocks() {
+    let source = "fn f(a: i64) { let b = a; { let c = b; } }\nlet d = f(1);";
+    let tree = SyntaxTree::from_stmts(source);
+    let root = tree.root().as_node().unwrap();
+    let bindings = bindings(root);
+    let names_at = |offset: usize| -> Vec<String> {
+        let mut names: Vec<String> = visible_at(&bindings, offset)
+            .iter()
+            .map(|b| b.name.clone())
+            .collect();
+        names.sort();
+        names
+    };
+
+    assert_eq!(vec!["a", "b", "f"], names_at(source.find("b; }").unwrap()));
+    assert_eq!(
+        vec!["a", "b", "c", "f"],
+        names_at(source.find("} }").unwrap())
+    );
+    assert_eq!(
+        vec!["a", "b", "f"],
+        names_at(source.find("{ let c").unwrap())
+    );
+    assert_eq!(vec!["f"], names_at(source.find("let d").unwrap()));
+
+    let parameter = resolve(&bindings, "a", source.find("a;").unwrap()).unwrap();
+    assert_eq!(BindingKind::Parameter, parameter.kind);
+    assert_eq!("a: i64", parameter.detail);
+}
+
+#[test]
+fn inner_bindings_shadow_outer_ones() {
+    let source = "let x = 1; fn f() { let x = true; return x; }";
+    let tree = SyntaxTree::from_stmts(source);
+    let bindings = bindings(tree.root().as_node().unwrap());
+    let x = resolve(&bindings, "x", source.rfind('x').unwrap()).unwrap();
+
+    assert_eq!(source.find("x = true").unwrap(), x.declaration.start());
+}
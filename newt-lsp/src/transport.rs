@@ -0,0 +1,50 @@
+use serde_json::Value;
+use std::io::{BufRead, Error, ErrorKind, Result, Write};
+
+pub fn read_message<R: BufRead>(input: &mut R) -> Result<Option<Value>> {
+    let mut content_length = None;
+
+    loop {
+        let mut header = String::new();
+
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some(length) = header.strip_prefix("Content-Length:") {
+            let length = length.trim().parse::<usize>().map_err(invalid_data)?;
+            content_length = Some(length);
+        }
+    }
+
+    let content_length =
+        content_length.ok_or_else(|| invalid_data("message has no Content-Length header"))?;
+    let mut content = vec![0; content_length];
+    input.read_exact(&mut content)?;
+
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(invalid_data)
+}
+
+pub fn write_message<W: Write>(output: &mut W, message: &Value) -> Result<()> {
+    let content = message.to_string();
+
+    write!(
+        output,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    output.flush()
+}
+
+fn invalid_data<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> Error {
+    Error::new(ErrorKind::InvalidData, error)
+}
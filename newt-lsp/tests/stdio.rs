@@ -0,0 +1,328 @@
+//! Drives the `newt-lsp` binary over stdio the way an editor would.
+
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+struct Client {
+    server: Child,
+    input: ChildStdin,
+    output: BufReader<ChildStdout>,
+    next_id: u64,
+    notifications: Vec<Value>,
+}
+
+impl Client {
+    fn start() -> Client {
+        let mut client = Client::spawn();
+
+        client.request("initialize", json!({ "capabilities": {} }));
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn spawn() -> Client {
+        let mut server = Command::new(env!("CARGO_BIN_EXE_newt-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("Expected the server to start");
+        let input = server.stdin.take().unwrap();
+        let output = BufReader::new(server.stdout.take().unwrap());
+
+        Client {
+            server,
+            input,
+            output,
+            next_id: 0,
+            notifications: Vec::new(),
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        let content = message.to_string();
+
+        write!(
+            self.input,
+            "Content-Length: {}\r\n\r\n{}",
+            content.len(),
+            content
+        )
+        .unwrap();
+        self.input.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut content_length = 0;
+
+        loop {
+            let mut header = String::new();
+            self.output.read_line(&mut header).unwrap();
+
+            match header.trim_end() {
+                "" => break,
+                header => {
+                    if let Some(length) = header.strip_prefix("Content-Length: ") {
+                        content_length = length.parse().unwrap();
+                    }
+                }
+            }
+        }
+
+        let mut content = vec![0; content_length];
+        self.output.read_exact(&mut content).unwrap();
+        serde_json::from_slice(&content).unwrap()
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+        loop {
+            let message = self.receive();
+
+            if message["id"] == json!(id) {
+                return message;
+            }
+
+            self.notifications.push(message);
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    fn diagnostics(&mut self) -> Value {
+        let notification = match self.notifications.pop() {
+            Some(notification) => notification,
+            None => self.receive(),
+        };
+
+        assert_eq!("textDocument/publishDiagnostics", notification["method"]);
+        notification["params"]["diagnostics"].clone()
+    }
+
+    fn open(&mut self, uri: &str, text: &str) -> Value {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": uri, "languageId": "newt", "version": 1, "text": text }
+            }),
+        );
+
+        self.diagnostics()
+    }
+
+    fn at(&mut self, method: &str, uri: &str, line: u64, character: u64) -> Value {
+        self.request(
+            method,
+            json!({
+                "textDocument": { "uri": uri },
+                "position": { "line": line, "character": character },
+            }),
+        )["result"]
+            .clone()
+    }
+
+    fn stop(mut self) -> i32 {
+        assert_eq!(Value::Null, self.request("shutdown", Value::Null)["result"]);
+        self.notify("exit", Value::Null);
+
+        self.server.wait().unwrap().code().unwrap()
+    }
+}
+
+fn range(start: (u64, u64), end: (u64, u64)) -> Value {
+    json!({
+        "start": { "line": start.0, "character": start.1 },
+        "end": { "line": end.0, "character": end.1 },
+    })
+}
+
+const SOURCE: &str = "fn add(a: i64, b: i64) -> i64 {
+\tlet sum: i64 = a + b;
+\treturn sum;
+}
+
+impl Dog {
+\tfn bark(self) {
+\t\tfn inner() {}
+\t}
+}
+
+let total = add(1, 2);
+";
+
+#[test]
+fn initialize_advertises_capabilities_and_exit_follows_shutdown() {
+    let mut client = Client::start();
+    let response = client.request("initialize", json!({ "capabilities": {} }));
+
+    assert_eq!(
+        json!(true),
+        response["result"]["capabilities"]["hoverProvider"]
+    );
+    assert_eq!(
+        json!(2),
+        response["result"]["capabilities"]["textDocumentSync"]["change"]
+    );
+    assert_eq!(0, client.stop());
+}
+
+#[test]
+fn requests_before_initialize_and_unknown_methods_are_errors() {
+    let mut client = Client::spawn();
+
+    assert_eq!(
+        json!(-32002),
+        client.request("textDocument/hover", json!({}))["error"]["code"]
+    );
+
+    client.request("initialize", json!({ "capabilities": {} }));
+
+    assert_eq!(
+        json!(-32601),
+        client.request("textDocument/rename", json!({}))["error"]["code"]
+    );
+    assert_eq!(0, client.stop());
+}
+
+#[test]
+fn syntax_errors_are_published_and_cleared_by_incremental_changes() {
+    let mut client = Client::start();
+    let diagnostics = client.open("file:///broken.newt", "let x = 1;\nlet y = ;\n");
+
+    assert_eq!(1, diagnostics.as_array().unwrap().len());
+    assert_eq!(json!(1), diagnostics[0]["range"]["start"]["line"]);
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Expected expression"));
+
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": "file:///broken.newt", "version": 2 },
+            "contentChanges": [{ "range": range((1, 8), (1, 8)), "text": "x" }],
+        }),
+    );
+
+    assert_eq!(json!([]), client.diagnostics());
+    assert_eq!(0, client.stop());
+}
+
+#[test]
+fn hover_shows_the_declaration_of_let_and_fn_bindings() {
+    let mut client = Client::start();
+    client.open("file:///hover.newt", SOURCE);
+
+    let hover = client.at("textDocument/hover", "file:///hover.newt", 2, 9);
+    assert_eq!(
+        json!("```newt\nlet sum: i64\n```"),
+        hover["contents"]["value"]
+    );
+    assert_eq!(range((2, 8), (2, 11)), hover["range"]);
+
+    let hover = client.at("textDocument/hover", "file:///hover.newt", 11, 13);
+    assert_eq!(
+        json!("```newt\nfn add(a: i64, b: i64) -> i64\n```"),
+        hover["contents"]["value"]
+    );
+
+    assert_eq!(
+        Value::Null,
+        client.at("textDocument/hover", "file:///hover.newt", 11, 17)
+    );
+    assert_eq!(0, client.stop());
+}
+
+#[test]
+fn definition_goes_to_the_binding_in_scope() {
+    let mut client = Client::start();
+    client.open("file:///definition.newt", SOURCE);
+
+    let definition = client.at("textDocument/definition", "file:///definition.newt", 1, 16);
+    assert_eq!(json!("file:///definition.newt"), definition["uri"]);
+    assert_eq!(range((0, 7), (0, 8)), definition["range"]);
+
+    let definition = client.at("textDocument/definition", "file:///definition.newt", 11, 12);
+    assert_eq!(range((0, 3), (0, 6)), definition["range"]);
+    assert_eq!(0, client.stop());
+}
+
+#[test]
+fn completion_offers_the_identifiers_in_scope() {
+    let mut client = Client::start();
+    client.open("file:///completion.newt", SOURCE);
+
+    let labels = |items: &Value| -> Vec<String> {
+        let mut labels: Vec<String> = items
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["label"].as_str().unwrap().to_string())
+            .collect();
+        labels.sort();
+        labels
+    };
+
+    let items = client.at("textDocument/completion", "file:///completion.newt", 2, 1);
+    assert_eq!(vec!["a", "add", "b", "sum"], labels(&items));
+
+    let items = client.at("textDocument/completion", "file:///completion.newt", 12, 0);
+    assert_eq!(vec!["add", "total"], labels(&items));
+    assert_eq!(0, client.stop());
+}
+
+#[test]
+fn document_symbols_outline_function_declarations() {
+    let mut client = Client::start();
+    client.open("file:///outline.newt", SOURCE);
+
+    let symbols = client.request(
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": "file:///outline.newt" } }),
+    )["result"]
+        .clone();
+
+    assert_eq!(json!("add"), symbols[0]["name"]);
+    assert_eq!(json!(12), symbols[0]["kind"]);
+    assert_eq!(json!("fn add(a: i64, b: i64) -> i64"), symbols[0]["detail"]);
+    assert_eq!(range((0, 3), (0, 6)), symbols[0]["selectionRange"]);
+    assert_eq!(json!("bark"), symbols[1]["name"]);
+    assert_eq!(json!(6), symbols[1]["kind"]);
+    assert_eq!(json!("inner"), symbols[1]["children"][0]["name"]);
+    assert_eq!(2, symbols.as_array().unwrap().len());
+    assert_eq!(0, client.stop());
+}
+
+#[test]
+fn documents_with_several_syntax_errors_report_each_of_them() {
+    let mut client = Client::start();
+    let diagnostics = client.open(
+        "file:///broken.newt",
+        "let x = 1\nlet y = ;\nfn f(a b) { return a; }\nlet z = f(1);\n",
+    );
+    let lines: Vec<Value> = diagnostics
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diagnostic| diagnostic["range"]["start"]["line"].clone())
+        .collect();
+
+    assert_eq!(vec![json!(0), json!(1), json!(2)], lines);
+
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": "file:///broken.newt", "version": 2 },
+            "contentChanges": [{ "text": "let x = 1;" }],
+        }),
+    );
+
+    assert_eq!(json!([]), client.diagnostics());
+    assert_eq!(0, client.stop());
+}
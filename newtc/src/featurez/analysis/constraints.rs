@@ -0,0 +1,680 @@
+use crate::featurez::analysis::{Resolution, Type};
+use crate::featurez::newtypes::TransparentNewType;
+use crate::featurez::syntax::*;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone)]
+struct TypeParameter {
+    name: String,
+    bounds: Vec<String>,
+}
+
+impl TypeParameter {
+    fn from_nodes<'n>(nodes: impl Iterator<Item = &'n TypeParameterNode>) -> Vec<TypeParameter> {
+        nodes
+            .map(|node| TypeParameter {
+                name: node.identifier().lexeme().to_string(),
+                bounds: node.bounds().map(|b| b.lexeme().to_string()).collect(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Signature {
+    type_parameters: Vec<TypeParameter>,
+    parameters: Vec<Option<String>>,
+    takes_self: bool,
+}
+
+impl Signature {
+    fn new<'n>(
+        type_parameters: impl Iterator<Item = &'n TypeParameterNode>,
+        parameters: impl Iterator<Item = (&'n SyntaxToken, Option<&'n TypeRefNode>)>,
+    ) -> Signature {
+        let parameters: Vec<_> = parameters.collect();
+
+        Signature {
+            type_parameters: TypeParameter::from_nodes(type_parameters),
+            takes_self: parameters
+                .first()
+                .is_some_and(|(name, _)| name.lexeme() == "self"),
+            parameters: parameters
+                .iter()
+                .map(|(_, t)| t.map(|t| t.identifier().lexeme().to_string()))
+                .collect(),
+        }
+    }
+
+    fn bounds_of(&self, parameter: usize) -> Option<&TypeParameter> {
+        let type_name = self.parameters.get(parameter)?.as_ref()?;
+
+        self.type_parameters.iter().find(|p| &p.name == type_name)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct GenericStruct {
+    type_parameters: Vec<TypeParameter>,
+    fields: HashMap<String, String>,
+}
+
+impl GenericStruct {
+    fn new(node: &StructDeclarationStmtNode) -> GenericStruct {
+        GenericStruct {
+            type_parameters: TypeParameter::from_nodes(node.type_parameters()),
+            fields: node
+                .fields()
+                .map(|field| {
+                    (
+                        field.identifier().lexeme().to_string(),
+                        field.type_ref().identifier().lexeme().to_string(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn bounds_of(&self, field: &str) -> Option<&TypeParameter> {
+        let type_name = self.fields.get(field)?;
+
+        self.type_parameters.iter().find(|p| &p.name == type_name)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum StaticType {
+    Named(String),
+    Parameter(TypeParameter),
+    Unknown,
+}
+
+/// Generics are erased at runtime, so this is the only place bounds are enforced.
+pub struct ConstraintChecker<'a> {
+    resolution: &'a Resolution,
+    structs: HashMap<String, GenericStruct>,
+    implementations: HashMap<String, HashSet<String>>,
+    functions: HashMap<String, Signature>,
+    methods: HashMap<(String, String), Signature>,
+    type_scopes: Vec<Vec<TypeParameter>>,
+    // The globals this program declares, followed by the scopes of the locals being checked
+    value_scopes: Vec<HashMap<String, StaticType>>,
+    impl_type: Option<String>,
+    errors: Vec<NewtStaticError>,
+}
+
+impl<'a> ConstraintChecker<'a> {
+    pub fn check(node: &SyntaxNode, resolution: &'a Resolution) -> Vec<NewtStaticError> {
+        let mut checker = ConstraintChecker {
+            resolution,
+            structs: HashMap::new(),
+            implementations: HashMap::new(),
+            functions: HashMap::new(),
+            methods: HashMap::new(),
+            type_scopes: Vec::new(),
+            value_scopes: vec![HashMap::new()],
+            impl_type: None,
+            errors: Vec::new(),
+        };
+
+        if let Some(expr) = ExprNode::cast(node) {
+            checker.visit_expr(expr);
+        } else if let Some(stmt) = StmtNode::cast(node) {
+            if node.kind() == SyntaxKind::StmtListStmt {
+                checker.declare(StmtListStmtNode::from_inner(node));
+            }
+
+            checker.visit_stmt(stmt);
+        }
+
+        checker.errors
+    }
+
+    fn declare(&mut self, node: &StmtListStmtNode) {
+        for stmt in node.stmts() {
+            match stmt.kind() {
+                StmtKind::StructDeclarationStmt(node) => {
+                    self.structs.insert(
+                        node.identifier().lexeme().to_string(),
+                        GenericStruct::new(node),
+                    );
+                }
+                StmtKind::FunctionDeclarationStmt(node) => {
+                    self.functions.insert(
+                        node.identifier().lexeme().to_string(),
+                        Signature::new(node.type_parameters(), node.parameters()),
+                    );
+                }
+                StmtKind::ImplStmt(node) => {
+                    let type_name = node.type_identifier().lexeme();
+
+                    if let Some(interface) = node.interface_identifier() {
+                        self.implementations
+                            .entry(type_name.to_string())
+                            .or_default()
+                            .insert(interface.lexeme().to_string());
+                    }
+
+                    for method in node.methods() {
+                        self.methods.insert(
+                            (
+                                type_name.to_string(),
+                                method.identifier().lexeme().to_string(),
+                            ),
+                            Signature::new(method.type_parameters(), method.parameters()),
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn begin_type_scope<'n>(&mut self, nodes: impl Iterator<Item = &'n TypeParameterNode>) {
+        let nodes: Vec<_> = nodes.collect();
+
+        for node in &nodes {
+            for bound in node.bounds() {
+                if self.structs.contains_key(bound.lexeme()) {
+                    self.report(
+                        &format!(
+                            "{} bounds {} but is not an interface",
+                            bound.lexeme(),
+                            node.identifier().lexeme()
+                        ),
+                        bound.range(),
+                    );
+                }
+            }
+        }
+
+        self.type_scopes
+            .push(TypeParameter::from_nodes(nodes.into_iter()));
+    }
+
+    fn end_type_scope(&mut self) {
+        self.type_scopes.pop();
+    }
+
+    fn type_parameter(&self, name: &str) -> Option<&TypeParameter> {
+        self.type_scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter())
+            .find(|parameter| parameter.name == name)
+    }
+
+    fn check_type_ref(&mut self, node: &TypeRefNode) {
+        let name = node.identifier().lexeme();
+
+        if let Some(generic) = self.structs.get(name).cloned() {
+            for (argument, parameter) in node.arguments().zip(generic.type_parameters.iter()) {
+                let argument_type = self.type_of_ref(argument);
+                self.check_bounds(&argument_type, parameter, argument.range());
+            }
+        }
+
+        for argument in node.arguments() {
+            self.check_type_ref(argument);
+        }
+    }
+
+    fn check_function<'n>(
+        &mut self,
+        type_parameters: impl Iterator<Item = &'n TypeParameterNode>,
+        parameters: impl Iterator<Item = (&'n SyntaxToken, Option<&'n TypeRefNode>)>,
+        return_type: Option<&TypeRefNode>,
+        body: &StmtListStmtNode,
+    ) {
+        self.begin_type_scope(type_parameters);
+
+        if let Some(return_type) = return_type {
+            self.check_type_ref(return_type);
+        }
+
+        let mut values = HashMap::new();
+        for (index, (parameter, annotation)) in parameters.enumerate() {
+            let parameter_type = match (annotation, &self.impl_type) {
+                (Some(annotation), _) => {
+                    self.check_type_ref(annotation);
+                    self.type_of_ref(annotation)
+                }
+                (None, Some(impl_type)) if index == 0 && parameter.lexeme() == "self" => {
+                    StaticType::Named(impl_type.clone())
+                }
+                (None, _) => StaticType::Unknown,
+            };
+
+            values.insert(parameter.lexeme().to_string(), parameter_type);
+        }
+
+        self.value_scopes.push(values);
+        let impl_type = self.impl_type.take();
+
+        self.visit_stmt_list_stmt(body);
+
+        self.impl_type = impl_type;
+        self.value_scopes.pop();
+        self.end_type_scope();
+    }
+
+    fn check_bounds(&mut self, argument: &StaticType, parameter: &TypeParameter, range: TextRange) {
+        for bound in &parameter.bounds {
+            let satisfied = match argument {
+                StaticType::Named(name) => {
+                    !self.is_known_type(name)
+                        || self
+                            .implementations
+                            .get(name)
+                            .is_some_and(|interfaces| interfaces.contains(bound))
+                }
+                StaticType::Parameter(argument) => argument.bounds.contains(bound),
+                StaticType::Unknown => true,
+            };
+
+            if !satisfied {
+                let argument_name = match argument {
+                    StaticType::Named(name) => name.as_str(),
+                    StaticType::Parameter(argument) => argument.name.as_str(),
+                    StaticType::Unknown => "",
+                };
+
+                self.report(
+                    &format!(
+                        "{} does not implement {} required by {}",
+                        argument_name, bound, parameter.name
+                    ),
+                    range,
+                );
+            }
+        }
+    }
+
+    fn is_known_type(&self, name: &str) -> bool {
+        // Primitive types do not implement any interface.
+        self.structs.contains_key(name) || Type::from_primitive_name(name).is_some()
+    }
+
+    fn type_of_ref(&self, node: &TypeRefNode) -> StaticType {
+        let name = node.identifier().lexeme();
+
+        match self.type_parameter(name) {
+            Some(parameter) if node.arguments().next().is_none() => {
+                StaticType::Parameter(parameter.clone())
+            }
+            _ => StaticType::Named(name.to_string()),
+        }
+    }
+
+    fn type_of_expr(&self, node: &ExprNode) -> StaticType {
+        match node.kind() {
+            ExprKind::PrimitiveLiteralExpr(node) => Type::from_literal(node.literal().token_kind())
+                .map_or(StaticType::Unknown, |t| StaticType::Named(t.to_string())),
+            ExprKind::StructLiteralExpr(node) if self.is_global(node.identifier()) => {
+                StaticType::Named(node.identifier().lexeme().to_string())
+            }
+            ExprKind::VariableExpr(node) => self.lookup(node.identifier()),
+            ExprKind::GroupingExpr(node) => self.type_of_expr(node.expr()),
+            _ => StaticType::Unknown,
+        }
+    }
+
+    fn callee_signature(&self, node: &FunctionCallExprNode) -> Option<(Signature, usize)> {
+        match node.callee().kind() {
+            ExprKind::VariableExpr(callee) if self.is_global(callee.identifier()) => self
+                .functions
+                .get(callee.identifier().lexeme())
+                .map(|signature| (signature.clone(), 0)),
+            ExprKind::ObjectPropertyExpr(callee) => {
+                let method = callee.identifier().lexeme().to_string();
+
+                if let StaticType::Named(receiver) = self.type_of_expr(callee.source_expr()) {
+                    return self
+                        .methods
+                        .get(&(receiver, method))
+                        .map(|signature| (signature.clone(), signature.takes_self as usize));
+                }
+
+                match callee.source_expr().kind() {
+                    ExprKind::VariableExpr(source) if self.is_global(source.identifier()) => self
+                        .methods
+                        .get(&(source.identifier().lexeme().to_string(), method))
+                        .map(|signature| (signature.clone(), 0)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn define(&mut self, identifier: &SyntaxToken, value_type: StaticType) {
+        if let Some(scope) = self.value_scopes.last_mut() {
+            scope.insert(identifier.lexeme().to_string(), value_type);
+        }
+    }
+
+    fn lookup(&self, identifier: &SyntaxToken) -> StaticType {
+        let name = identifier.lexeme();
+        let found = if self.is_global(identifier) {
+            self.value_scopes[0].get(name)
+        } else {
+            self.value_scopes[1..]
+                .iter()
+                .rev()
+                .find_map(|scope| scope.get(name))
+        };
+
+        found.cloned().unwrap_or(StaticType::Unknown)
+    }
+
+    fn is_global(&self, identifier: &SyntaxToken) -> bool {
+        self.resolution.depth(identifier).is_none()
+    }
+
+    fn report(&mut self, message: &str, range: TextRange) {
+        self.errors.push(NewtStaticError::new(
+            StaticErrorKind::UnsatisfiedConstraint,
+            message,
+            range,
+        ));
+    }
+}
+
+impl<'a> ExprVisitor<()> for ConstraintChecker<'a> {
+    fn visit_expr(&mut self, node: &ExprNode) {
+        match node.kind() {
+            ExprKind::BinaryExpr(node) => self.visit_binary_expr(node),
+            ExprKind::UnaryExpr(node) => self.visit_unary_expr(node),
+            ExprKind::PrimitiveLiteralExpr(node) => self.visit_primitive_literal_expr(node),
+            ExprKind::ObjectLiteralExpr(node) => self.visit_object_literal_expr(node),
+            ExprKind::ObjectPropertyExpr(node) => self.visit_object_property_expr(node),
+            ExprKind::GroupingExpr(node) => self.visit_grouping_expr(node),
+            ExprKind::VariableExpr(node) => self.visit_variable_expr(node),
+            ExprKind::FunctionCallExpr(node) => self.visit_function_call_expr(node),
+            ExprKind::ArrayLiteralExpr(node) => self.visit_array_literal_expr(node),
+            ExprKind::IndexExpr(node) => self.visit_index_expr(node),
+            ExprKind::StructLiteralExpr(node) => self.visit_struct_literal_expr(node),
+            ExprKind::FunctionExpr(node) => self.visit_function_expr(node),
+            ExprKind::InterpolatedStringExpr(node) => self.visit_interpolated_string_expr(node),
+            ExprKind::MatchExpr(node) => self.visit_match_expr(node),
+        }
+    }
+
+    fn visit_binary_expr(&mut self, node: &BinaryExprNode) {
+        self.visit_expr(node.lhs());
+        self.visit_expr(node.rhs());
+    }
+
+    fn visit_unary_expr(&mut self, node: &UnaryExprNode) {
+        self.visit_expr(node.rhs());
+    }
+
+    fn visit_primitive_literal_expr(&mut self, _node: &PrimitiveLiteralExprNode) {}
+
+    fn visit_grouping_expr(&mut self, node: &GroupingExprNode) {
+        self.visit_expr(node.expr());
+    }
+
+    fn visit_variable_expr(&mut self, _node: &VariableExprNode) {}
+
+    fn visit_function_call_expr(&mut self, node: &FunctionCallExprNode) {
+        self.visit_expr(node.callee());
+
+        for argument in node.arguments() {
+            self.visit_expr(argument);
+        }
+
+        if let Some((signature, bound)) = self.callee_signature(node) {
+            for (index, argument) in node.arguments().enumerate() {
+                if let Some(parameter) = signature.bounds_of(index + bound) {
+                    let argument_type = self.type_of_expr(argument);
+                    self.check_bounds(&argument_type, parameter, argument.syntax().range());
+                }
+            }
+        }
+    }
+
+    fn visit_object_literal_expr(&mut self, node: &ObjectLiteralExprNode) {
+        for (_, value) in node.fields().iter() {
+            self.visit_expr(value);
+        }
+    }
+
+    fn visit_object_property_expr(&mut self, node: &ObjectPropertyExprNode) {
+        self.visit_expr(node.source_expr());
+    }
+
+    fn visit_array_literal_expr(&mut self, node: &ArrayLiteralExprNode) {
+        for element in node.elements() {
+            self.visit_expr(element);
+        }
+    }
+
+    fn visit_index_expr(&mut self, node: &IndexExprNode) {
+        self.visit_expr(node.source_expr());
+        self.visit_expr(node.index());
+    }
+
+    fn visit_struct_literal_expr(&mut self, node: &StructLiteralExprNode) {
+        for (_, value) in node.fields() {
+            self.visit_expr(value);
+        }
+
+        // The type arguments of a generic struct are those of the fields they are the type of
+        let generic = match self.structs.get(node.identifier().lexeme()) {
+            Some(generic) if self.is_global(node.identifier()) => generic.clone(),
+            _ => return,
+        };
+
+        for (field, value) in node.fields() {
+            if let Some(parameter) = generic.bounds_of(field.lexeme()) {
+                let argument_type = self.type_of_expr(value);
+                self.check_bounds(&argument_type, parameter, value.syntax().range());
+            }
+        }
+    }
+
+    fn visit_function_expr(&mut self, node: &FunctionExprNode) {
+        self.check_function(
+            std::iter::empty(),
+            node.parameters(),
+            node.return_type(),
+            node.stmts(),
+        );
+    }
+
+    fn visit_interpolated_string_expr(&mut self, node: &InterpolatedStringExprNode) {
+        for expr in node.exprs() {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_match_expr(&mut self, node: &MatchExprNode) {
+        self.visit_expr(node.scrutinee());
+
+        for arm in node.arms() {
+            if let Some(guard) = arm.guard() {
+                self.visit_expr(guard);
+            }
+
+            self.visit_expr(arm.expr());
+        }
+    }
+}
+
+impl<'a> StmtVisitor<()> for ConstraintChecker<'a> {
+    fn visit_stmt(&mut self, node: &StmtNode) {
+        match node.kind() {
+            StmtKind::VariableDeclarationStmt(node) => self.visit_variable_declaration_stmt(node),
+            StmtKind::AssignmentStmt(node) => self.visit_assignment_stmt(node),
+            StmtKind::StmtListStmt(node) => self.visit_stmt_list_stmt(node),
+            StmtKind::ExprStmt(node) => self.visit_expr_stmt(node),
+            StmtKind::IfStmt(node) => self.visit_if_stmt(node),
+            StmtKind::WhileStmt(node) => self.visit_while_stmt(node),
+            StmtKind::ForStmt(node) => self.visit_for_stmt(node),
+            StmtKind::FunctionDeclarationStmt(node) => self.visit_function_declaration_stmt(node),
+            StmtKind::ReturnStmt(node) => self.visit_return_stmt(node),
+            StmtKind::ThrowStmt(node) => self.visit_throw_stmt(node),
+            StmtKind::TryStmt(node) => self.visit_try_stmt(node),
+            StmtKind::BreakStmt(node) => self.visit_break_stmt(node),
+            StmtKind::ContinueStmt(node) => self.visit_continue_stmt(node),
+            StmtKind::StructDeclarationStmt(node) => self.visit_struct_declaration_stmt(node),
+            StmtKind::ImplStmt(node) => self.visit_impl_stmt(node),
+            StmtKind::InterfaceDeclarationStmt(node) => self.visit_interface_declaration_stmt(node),
+            StmtKind::ImportStmt(node) => self.visit_import_stmt(node),
+            StmtKind::ExportStmt(node) => self.visit_export_stmt(node),
+        }
+    }
+
+    fn visit_variable_declaration_stmt(&mut self, node: &VariableDeclarationStmtNode) {
+        self.visit_expr(node.expr());
+
+        let variable_type = match node.type_ref() {
+            Some(annotation) => {
+                self.check_type_ref(annotation);
+                self.type_of_ref(annotation)
+            }
+            None => self.type_of_expr(node.expr()),
+        };
+
+        self.define(node.identifier(), variable_type);
+    }
+
+    fn visit_assignment_stmt(&mut self, node: &AssignmentStmtNode) {
+        self.visit_expr(node.expr());
+
+        match node.rval().kind() {
+            RValKind::VariableRVal(_) => {}
+            RValKind::ObjectPropertyRVal(property) => self.visit_expr(property.source_expr()),
+            RValKind::IndexRVal(element) => {
+                self.visit_expr(element.source_expr());
+                self.visit_expr(element.index());
+            }
+        }
+    }
+
+    fn visit_stmt_list_stmt(&mut self, node: &StmtListStmtNode) {
+        if node.has_braces() {
+            self.value_scopes.push(HashMap::new());
+        }
+
+        for stmt in node.stmts() {
+            self.visit_stmt(stmt);
+        }
+
+        if node.has_braces() {
+            self.value_scopes.pop();
+        }
+    }
+
+    fn visit_expr_stmt(&mut self, node: &ExprStmtNode) {
+        self.visit_expr(node.expr());
+    }
+
+    fn visit_if_stmt(&mut self, node: &IfStmtNode) {
+        self.visit_expr(node.condition());
+        self.visit_stmt_list_stmt(node.when_true());
+
+        if let Some(else_path) = node.when_false() {
+            self.visit_stmt_list_stmt(else_path);
+        }
+    }
+
+    fn visit_while_stmt(&mut self, node: &WhileStmtNode) {
+        self.visit_expr(node.condition());
+        self.visit_stmt_list_stmt(node.stmts());
+    }
+
+    fn visit_for_stmt(&mut self, node: &ForStmtNode) {
+        self.visit_expr(node.iterable());
+        self.visit_stmt_list_stmt(node.stmts());
+    }
+
+    fn visit_function_declaration_stmt(&mut self, node: &FunctionDeclarationStmtNode) {
+        self.check_function(
+            node.type_parameters(),
+            node.parameters(),
+            node.return_type(),
+            node.stmts(),
+        );
+    }
+
+    fn visit_return_stmt(&mut self, node: &ReturnStmtNode) {
+        if let Some(expr) = node.result() {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_throw_stmt(&mut self, node: &ThrowStmtNode) {
+        self.visit_expr(node.expr());
+    }
+
+    fn visit_break_stmt(&mut self, _node: &BreakStmtNode) {}
+
+    fn visit_continue_stmt(&mut self, _node: &ContinueStmtNode) {}
+
+    fn visit_try_stmt(&mut self, node: &TryStmtNode) {
+        self.visit_stmt_list_stmt(node.stmts());
+
+        if let Some(clause) = node.catch_clause() {
+            self.visit_stmt_list_stmt(clause.stmts());
+        }
+
+        if let Some(stmts) = node.finally_stmts() {
+            self.visit_stmt_list_stmt(stmts);
+        }
+    }
+
+    fn visit_struct_declaration_stmt(&mut self, node: &StructDeclarationStmtNode) {
+        self.begin_type_scope(node.type_parameters());
+
+        for field in node.fields() {
+            self.check_type_ref(field.type_ref());
+        }
+
+        self.end_type_scope();
+    }
+
+    fn visit_impl_stmt(&mut self, node: &ImplStmtNode) {
+        self.begin_type_scope(node.type_parameters());
+
+        for method in node.methods() {
+            self.impl_type = Some(node.type_identifier().lexeme().to_string());
+            self.check_function(
+                method.type_parameters(),
+                method.parameters(),
+                method.return_type(),
+                method.stmts(),
+            );
+        }
+
+        self.impl_type = None;
+        self.end_type_scope();
+    }
+
+    fn visit_interface_declaration_stmt(&mut self, node: &InterfaceDeclarationStmtNode) {
+        for method in node.methods() {
+            self.begin_type_scope(method.type_parameters());
+
+            for (_, annotation) in method.parameters() {
+                if let Some(annotation) = annotation {
+                    self.check_type_ref(annotation);
+                }
+            }
+
+            if let Some(return_type) = method.return_type() {
+                self.check_type_ref(return_type);
+            }
+
+            self.end_type_scope();
+        }
+    }
+
+    fn visit_import_stmt(&mut self, _node: &ImportStmtNode) {}
+
+    fn visit_export_stmt(&mut self, node: &ExportStmtNode) {
+        if let Some(expr) = node.expr() {
+            self.visit_expr(expr);
+        }
+    }
+}
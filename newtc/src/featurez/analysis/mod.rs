@@ -0,0 +1,9 @@
+mod constraints;
+mod resolver;
+mod type_checker;
+
+pub use self::constraints::ConstraintChecker;
+pub use self::resolver::{Globals, Resolution, Resolver};
+pub use self::type_checker::{Type, TypeChecker};
+
+mod tests;
@@ -0,0 +1,645 @@
+use crate::featurez::analysis::{ConstraintChecker, TypeChecker};
+use crate::featurez::newtypes::TransparentNewType;
+use crate::featurez::syntax::*;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+
+#[derive(Debug, Clone, Default)]
+pub struct Globals {
+    natives: HashSet<String>,
+    declared: HashSet<String>,
+    types: HashSet<String>,
+}
+
+impl Globals {
+    pub fn new() -> Globals {
+        Globals::default()
+    }
+
+    /// Natives may be redeclared by scripts, which shadows them for the rest of the session.
+    pub fn define_native(&mut self, identifier: &str) {
+        self.natives.insert(identifier.to_string());
+    }
+
+    pub fn contains(&self, identifier: &str) -> bool {
+        self.declared.contains(identifier) || self.natives.contains(identifier)
+    }
+
+    pub fn retain_declared<F: Fn(&str) -> bool>(&mut self, defined: F) {
+        self.declared.retain(|name| defined(name));
+        self.types.retain(|name| defined(name));
+    }
+}
+
+/// How many scopes out from its use each local variable was declared, keyed by the range of
+/// the identifier token that names it.  Variables without an entry are globals.
+#[derive(Debug, Clone, Default)]
+pub struct Resolution {
+    depths: HashMap<TextRange, usize>,
+    captured: HashSet<TextRange>,
+    // The structs and interfaces declared by this program, at any depth, or by earlier ones
+    types: HashSet<String>,
+}
+
+impl Resolution {
+    pub fn depth(&self, identifier: &SyntaxToken) -> Option<usize> {
+        self.depths.get(&identifier.range()).cloned()
+    }
+
+    pub fn is_captured(&self, identifier: &SyntaxToken) -> bool {
+        self.captured.contains(&identifier.range())
+    }
+
+    pub fn is_type(&self, name: &str) -> bool {
+        self.types.contains(name)
+    }
+}
+
+struct Scope {
+    declarations: HashMap<String, TextRange>,
+    function_depth: usize,
+}
+
+pub struct Resolver<'a> {
+    globals: &'a mut Globals,
+    scopes: Vec<Scope>,
+    function_depth: usize,
+    // The labels of the loops around the code being resolved, in the function it belongs to
+    loops: Vec<Option<String>>,
+    // The globals declared by this program, as opposed to earlier ones, which it may redeclare
+    declared_globals: HashSet<String>,
+    deferred: Vec<(String, TextRange)>,
+    resolution: Resolution,
+    errors: Vec<NewtStaticError>,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn resolve(
+        node: &SyntaxNode,
+        globals: &mut Globals,
+    ) -> Result<Resolution, Vec<NewtStaticError>> {
+        let mut program_globals = globals.clone();
+        let resolution = Resolution {
+            types: globals.types.clone(),
+            ..Resolution::default()
+        };
+        let mut resolver = Resolver {
+            globals: &mut program_globals,
+            scopes: Vec::new(),
+            function_depth: 0,
+            loops: Vec::new(),
+            declared_globals: HashSet::new(),
+            deferred: Vec::new(),
+            resolution,
+            errors: Vec::new(),
+        };
+
+        if let Some(expr) = ExprNode::cast(node) {
+            resolver.visit_expr(expr);
+        } else if let Some(stmt) = StmtNode::cast(node) {
+            resolver.visit_stmt(stmt);
+        }
+
+        resolver.resolve_deferred();
+
+        let mut errors = resolver.errors;
+        let resolution = resolver.resolution;
+        errors.extend(ConstraintChecker::check(node, &resolution));
+        errors.extend(TypeChecker::check(node, &resolution));
+        if !errors.is_empty() {
+            errors.sort_by_key(|error| error.range().start());
+            return Err(errors);
+        }
+
+        *globals = program_globals;
+        Ok(resolution)
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(Scope {
+            declarations: HashMap::new(),
+            function_depth: self.function_depth,
+        });
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn declare(&mut self, identifier: &SyntaxToken) {
+        let name = identifier.lexeme();
+
+        let scope = match self.scopes.last_mut() {
+            Some(scope) => scope,
+            None => {
+                if !self.declared_globals.insert(name.to_string()) {
+                    self.report(
+                        StaticErrorKind::DuplicateVariableDeclaration,
+                        &format!("'{}' is already declared", name),
+                        identifier,
+                    );
+                }
+
+                self.globals.declared.insert(name.to_string());
+                return;
+            }
+        };
+
+        if scope
+            .declarations
+            .insert(name.to_string(), identifier.range())
+            .is_some()
+        {
+            self.report(
+                StaticErrorKind::DuplicateVariableDeclaration,
+                &format!("'{}' is already declared in this scope", name),
+                identifier,
+            );
+        } else if self
+            .scopes
+            .iter()
+            .rev()
+            .skip(1)
+            .any(|s| s.declarations.contains_key(name))
+        {
+            self.report(
+                StaticErrorKind::ShadowedVariableDeclaration,
+                &format!("'{}' shadows a variable in an enclosing scope", name),
+                identifier,
+            );
+        }
+    }
+
+    fn declare_type(&mut self, identifier: &SyntaxToken) {
+        self.declare(identifier);
+
+        let name = identifier.lexeme().to_string();
+        if self.scopes.is_empty() {
+            self.globals.types.insert(name.clone());
+        }
+        self.resolution.types.insert(name);
+    }
+
+    fn resolve_identifier(&mut self, identifier: &SyntaxToken) {
+        let name = identifier.lexeme();
+
+        for (depth, scope) in self.scopes.iter().rev().enumerate() {
+            if let Some(declaration) = scope.declarations.get(name) {
+                if scope.function_depth < self.function_depth {
+                    self.resolution.captured.insert(*declaration);
+                }
+
+                self.resolution.depths.insert(identifier.range(), depth);
+                return;
+            }
+        }
+
+        if self.globals.contains(name) {
+            return;
+        }
+
+        // Function bodies run after the rest of the program has been declared, so they may
+        // refer to globals further down the source.
+        if self.function_depth > 0 {
+            self.deferred.push((name.to_string(), identifier.range()));
+        } else {
+            self.report_undeclared(name, identifier.range());
+        }
+    }
+
+    fn resolve_deferred(&mut self) {
+        for (name, range) in std::mem::replace(&mut self.deferred, Vec::new()) {
+            if !self.globals.contains(&name) {
+                self.report_undeclared(&name, range);
+            }
+        }
+    }
+
+    fn resolve_bounds<'n>(&mut self, parameters: impl Iterator<Item = &'n TypeParameterNode>) {
+        for parameter in parameters {
+            for bound in parameter.bounds() {
+                self.resolve_identifier(bound);
+            }
+        }
+    }
+
+    fn resolve_function<'n>(
+        &mut self,
+        parameters: impl Iterator<Item = &'n SyntaxToken>,
+        body: &StmtListStmtNode,
+    ) {
+        self.function_depth += 1;
+        self.begin_scope();
+        let enclosing_loops = mem::take(&mut self.loops);
+
+        for parameter in parameters {
+            self.declare(parameter);
+        }
+
+        self.visit_stmt_list_stmt(body);
+
+        self.loops = enclosing_loops;
+        self.end_scope();
+        self.function_depth -= 1;
+    }
+
+    fn expect_top_level(&mut self, keyword: &SyntaxToken) {
+        if !self.scopes.is_empty() {
+            self.report(
+                StaticErrorKind::MisplacedModuleStmt,
+                &format!(
+                    "'{}' is only allowed at the top level of a module",
+                    keyword.lexeme()
+                ),
+                keyword,
+            );
+        }
+    }
+
+    fn resolve_jump(&mut self, keyword: &SyntaxToken, label: Option<&SyntaxToken>) {
+        if self.loops.is_empty() {
+            self.report(
+                StaticErrorKind::MisplacedJumpStmt,
+                &format!("'{}' is only allowed inside a loop", keyword.lexeme()),
+                keyword,
+            );
+        } else if let Some(label) = label {
+            let name = label.lexeme();
+
+            if !self
+                .loops
+                .iter()
+                .any(|loop_label| loop_label.as_deref() == Some(name))
+            {
+                self.report(
+                    StaticErrorKind::UndeclaredLabel,
+                    &format!("'{}' does not label a loop around this statement", name),
+                    label,
+                );
+            }
+        }
+    }
+
+    fn resolve_pattern(&mut self, pattern: &PatternNode) {
+        match pattern.kind() {
+            PatternKind::WildcardPattern => {}
+            PatternKind::BindingPattern(node) => self.declare(node.identifier()),
+            PatternKind::LiteralPattern(node) => self.visit_expr(node.expr()),
+            PatternKind::ArrayPattern(node) => {
+                for element in node.elements() {
+                    self.resolve_pattern(element);
+                }
+            }
+            PatternKind::ObjectPattern(node) => self.resolve_pattern_fields(node.fields()),
+            PatternKind::StructPattern(node) => {
+                self.resolve_identifier(node.identifier());
+                self.resolve_pattern_fields(node.fields());
+            }
+        }
+    }
+
+    fn resolve_pattern_fields<'n>(&mut self, fields: impl Iterator<Item = &'n PatternFieldNode>) {
+        for field in fields {
+            match field.pattern() {
+                Some(pattern) => self.resolve_pattern(pattern),
+                None => self.declare(field.identifier()),
+            }
+        }
+    }
+
+    fn report(&mut self, kind: StaticErrorKind, message: &str, token: &SyntaxToken) {
+        self.errors
+            .push(NewtStaticError::new(kind, message, token.range()));
+    }
+
+    fn report_undeclared(&mut self, name: &str, range: TextRange) {
+        self.errors.push(NewtStaticError::new(
+            StaticErrorKind::UndeclaredVariable,
+            &format!("'{}' is not declared", name),
+            range,
+        ));
+    }
+}
+
+impl<'a> ExprVisitor<()> for Resolver<'a> {
+    fn visit_expr(&mut self, node: &ExprNode) {
+        match node.kind() {
+            ExprKind::BinaryExpr(node) => self.visit_binary_expr(node),
+            ExprKind::UnaryExpr(node) => self.visit_unary_expr(node),
+            ExprKind::PrimitiveLiteralExpr(node) => self.visit_primitive_literal_expr(node),
+            ExprKind::ObjectLiteralExpr(node) => self.visit_object_literal_expr(node),
+            ExprKind::ObjectPropertyExpr(node) => self.visit_object_property_expr(node),
+            ExprKind::GroupingExpr(node) => self.visit_grouping_expr(node),
+            ExprKind::VariableExpr(node) => self.visit_variable_expr(node),
+            ExprKind::FunctionCallExpr(node) => self.visit_function_call_expr(node),
+            ExprKind::ArrayLiteralExpr(node) => self.visit_array_literal_expr(node),
+            ExprKind::IndexExpr(node) => self.visit_index_expr(node),
+            ExprKind::StructLiteralExpr(node) => self.visit_struct_literal_expr(node),
+            ExprKind::FunctionExpr(node) => self.visit_function_expr(node),
+            ExprKind::InterpolatedStringExpr(node) => self.visit_interpolated_string_expr(node),
+            ExprKind::MatchExpr(node) => self.visit_match_expr(node),
+        }
+    }
+
+    fn visit_binary_expr(&mut self, node: &BinaryExprNode) {
+        self.visit_expr(node.lhs());
+        self.visit_expr(node.rhs());
+    }
+
+    fn visit_unary_expr(&mut self, node: &UnaryExprNode) {
+        self.visit_expr(node.rhs());
+    }
+
+    fn visit_primitive_literal_expr(&mut self, _node: &PrimitiveLiteralExprNode) {}
+
+    fn visit_grouping_expr(&mut self, node: &GroupingExprNode) {
+        self.visit_expr(node.expr());
+    }
+
+    fn visit_variable_expr(&mut self, node: &VariableExprNode) {
+        self.resolve_identifier(node.identifier());
+    }
+
+    fn visit_function_call_expr(&mut self, node: &FunctionCallExprNode) {
+        self.visit_expr(node.callee());
+
+        for argument in node.arguments() {
+            self.visit_expr(argument);
+        }
+    }
+
+    fn visit_object_literal_expr(&mut self, node: &ObjectLiteralExprNode) {
+        for (_, value) in node.fields().iter() {
+            self.visit_expr(value);
+        }
+    }
+
+    fn visit_object_property_expr(&mut self, node: &ObjectPropertyExprNode) {
+        self.visit_expr(node.source_expr());
+    }
+
+    fn visit_array_literal_expr(&mut self, node: &ArrayLiteralExprNode) {
+        for element in node.elements() {
+            self.visit_expr(element);
+        }
+    }
+
+    fn visit_index_expr(&mut self, node: &IndexExprNode) {
+        self.visit_expr(node.source_expr());
+        self.visit_expr(node.index());
+    }
+
+    fn visit_struct_literal_expr(&mut self, node: &StructLiteralExprNode) {
+        self.resolve_identifier(node.identifier());
+
+        for (_, value) in node.fields() {
+            self.visit_expr(value);
+        }
+    }
+
+    fn visit_function_expr(&mut self, node: &FunctionExprNode) {
+        self.resolve_function(node.arguments(), node.stmts());
+    }
+
+    fn visit_interpolated_string_expr(&mut self, node: &InterpolatedStringExprNode) {
+        for expr in node.exprs() {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_match_expr(&mut self, node: &MatchExprNode) {
+        self.visit_expr(node.scrutinee());
+
+        for arm in node.arms() {
+            self.begin_scope();
+            self.resolve_pattern(arm.pattern());
+
+            if let Some(guard) = arm.guard() {
+                self.visit_expr(guard);
+            }
+
+            self.visit_expr(arm.expr());
+            self.end_scope();
+        }
+    }
+}
+
+impl<'a> StmtVisitor<()> for Resolver<'a> {
+    fn visit_stmt(&mut self, node: &StmtNode) {
+        match node.kind() {
+            StmtKind::VariableDeclarationStmt(node) => self.visit_variable_declaration_stmt(node),
+            StmtKind::AssignmentStmt(node) => self.visit_assignment_stmt(node),
+            StmtKind::StmtListStmt(node) => self.visit_stmt_list_stmt(node),
+            StmtKind::ExprStmt(node) => self.visit_expr_stmt(node),
+            StmtKind::IfStmt(node) => self.visit_if_stmt(node),
+            StmtKind::WhileStmt(node) => self.visit_while_stmt(node),
+            StmtKind::ForStmt(node) => self.visit_for_stmt(node),
+            StmtKind::FunctionDeclarationStmt(node) => self.visit_function_declaration_stmt(node),
+            StmtKind::ReturnStmt(node) => self.visit_return_stmt(node),
+            StmtKind::ThrowStmt(node) => self.visit_throw_stmt(node),
+            StmtKind::TryStmt(node) => self.visit_try_stmt(node),
+            StmtKind::BreakStmt(node) => self.visit_break_stmt(node),
+            StmtKind::ContinueStmt(node) => self.visit_continue_stmt(node),
+            StmtKind::StructDeclarationStmt(node) => self.visit_struct_declaration_stmt(node),
+            StmtKind::ImplStmt(node) => self.visit_impl_stmt(node),
+            StmtKind::InterfaceDeclarationStmt(node) => self.visit_interface_declaration_stmt(node),
+            StmtKind::ImportStmt(node) => self.visit_import_stmt(node),
+            StmtKind::ExportStmt(node) => self.visit_export_stmt(node),
+        }
+    }
+
+    fn visit_variable_declaration_stmt(&mut self, node: &VariableDeclarationStmtNode) {
+        self.visit_expr(node.expr());
+        self.declare(node.identifier());
+    }
+
+    fn visit_assignment_stmt(&mut self, node: &AssignmentStmtNode) {
+        self.visit_expr(node.expr());
+
+        match node.rval().kind() {
+            RValKind::VariableRVal(variable) => self.resolve_identifier(variable.identifier()),
+            RValKind::ObjectPropertyRVal(property) => self.visit_expr(property.source_expr()),
+            RValKind::IndexRVal(element) => {
+                self.visit_expr(element.source_expr());
+                self.visit_expr(element.index());
+            }
+        }
+    }
+
+    fn visit_stmt_list_stmt(&mut self, node: &StmtListStmtNode) {
+        if node.has_braces() {
+            self.begin_scope();
+        }
+
+        for stmt in node.stmts() {
+            self.visit_stmt(stmt);
+        }
+
+        if node.has_braces() {
+            self.end_scope();
+        }
+    }
+
+    fn visit_expr_stmt(&mut self, node: &ExprStmtNode) {
+        self.visit_expr(node.expr());
+    }
+
+    fn visit_if_stmt(&mut self, node: &IfStmtNode) {
+        self.visit_expr(node.condition());
+        self.visit_stmt_list_stmt(node.when_true());
+
+        if let Some(else_path) = node.when_false() {
+            self.visit_stmt_list_stmt(else_path);
+        }
+    }
+
+    fn visit_while_stmt(&mut self, node: &WhileStmtNode) {
+        self.visit_expr(node.condition());
+
+        self.loops
+            .push(node.label().map(|label| label.lexeme().to_string()));
+        self.visit_stmt_list_stmt(node.stmts());
+        self.loops.pop();
+    }
+
+    fn visit_for_stmt(&mut self, node: &ForStmtNode) {
+        self.visit_expr(node.iterable());
+
+        self.begin_scope();
+        self.declare(node.identifier());
+        self.loops
+            .push(node.label().map(|label| label.lexeme().to_string()));
+        self.visit_stmt_list_stmt(node.stmts());
+        self.loops.pop();
+        self.end_scope();
+    }
+
+    fn visit_function_declaration_stmt(&mut self, node: &FunctionDeclarationStmtNode) {
+        self.declare(node.identifier());
+        self.resolve_bounds(node.type_parameters());
+        self.resolve_function(node.arguments(), node.stmts());
+    }
+
+    fn visit_return_stmt(&mut self, node: &ReturnStmtNode) {
+        if let Some(expr) = node.result() {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_throw_stmt(&mut self, node: &ThrowStmtNode) {
+        self.visit_expr(node.expr());
+    }
+
+    fn visit_break_stmt(&mut self, node: &BreakStmtNode) {
+        self.resolve_jump(node.keyword(), node.label());
+    }
+
+    fn visit_continue_stmt(&mut self, node: &ContinueStmtNode) {
+        self.resolve_jump(node.keyword(), node.label());
+    }
+
+    fn visit_try_stmt(&mut self, node: &TryStmtNode) {
+        self.visit_stmt_list_stmt(node.stmts());
+
+        if let Some(clause) = node.catch_clause() {
+            self.begin_scope();
+            self.declare(clause.identifier());
+            self.visit_stmt_list_stmt(clause.stmts());
+            self.end_scope();
+        }
+
+        if let Some(stmts) = node.finally_stmts() {
+            self.visit_stmt_list_stmt(stmts);
+        }
+    }
+
+    fn visit_struct_declaration_stmt(&mut self, node: &StructDeclarationStmtNode) {
+        self.declare_type(node.identifier());
+        self.resolve_bounds(node.type_parameters());
+
+        let mut names = HashSet::new();
+        for field in node.fields() {
+            let identifier = field.identifier();
+
+            if !names.insert(identifier.lexeme()) {
+                self.report(
+                    StaticErrorKind::DuplicateFieldDeclaration,
+                    &format!(
+                        "'{}' is already a field of {}",
+                        identifier.lexeme(),
+                        node.identifier().lexeme()
+                    ),
+                    identifier,
+                );
+            }
+        }
+    }
+
+    fn visit_impl_stmt(&mut self, node: &ImplStmtNode) {
+        if let Some(interface) = node.interface_identifier() {
+            self.resolve_identifier(interface);
+        }
+        self.resolve_identifier(node.type_identifier());
+        self.resolve_bounds(node.type_parameters());
+
+        let mut names = HashSet::new();
+        for method in node.methods() {
+            let identifier = method.identifier();
+
+            if !names.insert(identifier.lexeme()) {
+                self.report(
+                    StaticErrorKind::DuplicateMethodDeclaration,
+                    &format!("'{}' is already a method in this impl", identifier.lexeme()),
+                    identifier,
+                );
+            }
+
+            self.resolve_bounds(method.type_parameters());
+            self.resolve_function(method.arguments(), method.stmts());
+        }
+    }
+
+    fn visit_interface_declaration_stmt(&mut self, node: &InterfaceDeclarationStmtNode) {
+        self.declare_type(node.identifier());
+
+        let mut names = HashSet::new();
+        for method in node.methods() {
+            let identifier = method.identifier();
+
+            if !names.insert(identifier.lexeme()) {
+                self.report(
+                    StaticErrorKind::DuplicateMethodDeclaration,
+                    &format!(
+                        "'{}' is already a method of {}",
+                        identifier.lexeme(),
+                        node.identifier().lexeme()
+                    ),
+                    identifier,
+                );
+            }
+
+            self.resolve_bounds(method.type_parameters());
+        }
+    }
+
+    fn visit_import_stmt(&mut self, node: &ImportStmtNode) {
+        self.expect_top_level(node.to_inner().nth_token(0));
+        self.declare(node.identifier());
+    }
+
+    fn visit_export_stmt(&mut self, node: &ExportStmtNode) {
+        self.expect_top_level(node.to_inner().nth_token(0));
+
+        match node.expr() {
+            Some(expr) => {
+                self.visit_expr(expr);
+                self.declare(node.identifier());
+            }
+            // Exports may name globals declared further down the module.
+            None => self.deferred.push((
+                node.identifier().lexeme().to_string(),
+                node.identifier().range(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,821 @@
+#![cfg(test)]
+
+use super::*;
+
+use crate::featurez::syntax::{NewtStaticError, StaticErrorKind, SyntaxTree, TextRange};
+
+#[test]
+fn resolver_accepts_declared_variables() {
+    let result = resolve("let x = 1; let y = x + 1;");
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn resolver_reports_undeclared_variables_with_their_location() {
+    let errors = resolve("let x = 1;\nlet y = z;").unwrap_err();
+
+    assert_eq!(1, errors.len());
+    assert_eq!(StaticErrorKind::UndeclaredVariable, errors[0].kind());
+    assert_eq!("'z' is not declared", errors[0].message());
+    assert_eq!(TextRange::new(19, 20), errors[0].range());
+}
+
+#[test]
+fn resolver_reports_variables_used_before_their_declaration() {
+    let errors = resolve("let y = x; let x = 42;").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UndeclaredVariable], kinds(&errors));
+}
+
+#[test]
+fn resolver_reports_duplicate_declarations_in_the_same_scope() {
+    let errors = resolve("{ let x = 1; let x = 2; }").unwrap_err();
+
+    assert_eq!(
+        vec![StaticErrorKind::DuplicateVariableDeclaration],
+        kinds(&errors)
+    );
+    assert_eq!(TextRange::new(17, 18), errors[0].range());
+}
+
+#[test]
+fn resolver_reports_duplicate_global_declarations() {
+    let errors = resolve("let x = 1; fn x() {}").unwrap_err();
+
+    assert_eq!(
+        vec![StaticErrorKind::DuplicateVariableDeclaration],
+        kinds(&errors)
+    );
+}
+
+#[test]
+fn resolver_reports_shadowed_declarations_in_nested_scopes() {
+    let errors = resolve("fn f(x) { if x { let x = 2; } }").unwrap_err();
+
+    assert_eq!(
+        vec![StaticErrorKind::ShadowedVariableDeclaration],
+        kinds(&errors)
+    );
+}
+
+#[test]
+fn resolver_allows_locals_to_shadow_globals() {
+    let result = resolve("let x = 1; fn f(x) { return x; }");
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn resolver_reports_every_error_in_source_order() {
+    let errors = resolve("let a = b; { let c = 1; let c = d; }").unwrap_err();
+
+    assert_eq!(
+        vec![
+            StaticErrorKind::UndeclaredVariable,
+            StaticErrorKind::DuplicateVariableDeclaration,
+            StaticErrorKind::UndeclaredVariable,
+        ],
+        kinds(&errors)
+    );
+}
+
+#[test]
+fn resolver_allows_functions_to_refer_to_later_globals() {
+    let result = resolve("fn f() { return g(); } fn g() { return 1; }");
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn resolver_reports_functions_referring_to_globals_never_declared() {
+    let errors = resolve("fn f() { return g(); }").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UndeclaredVariable], kinds(&errors));
+}
+
+#[test]
+fn resolver_computes_scope_depths_for_locals() {
+    let tree: SyntaxTree = "fn f(a) { let b = 1; { a = b; } }".into();
+    let resolution = Resolver::resolve(tree.root().as_node().unwrap(), &mut Globals::new())
+        .expect("Expected the source to resolve");
+
+    let identifiers: Vec<_> = tree
+        .iter()
+        .filter_map(|element| element.as_token())
+        .filter(|token| token.lexeme() == "a" || token.lexeme() == "b")
+        .map(|token| (token.lexeme().to_string(), resolution.depth(token)))
+        .collect();
+
+    assert_eq!(
+        vec![
+            ("a".to_string(), None),
+            ("b".to_string(), None),
+            ("a".to_string(), Some(2)),
+            ("b".to_string(), Some(1)),
+        ],
+        identifiers
+    );
+}
+
+#[test]
+fn resolver_leaves_globals_unresolved() {
+    let tree: SyntaxTree = "let x = 1; let y = x;".into();
+    let resolution = Resolver::resolve(tree.root().as_node().unwrap(), &mut Globals::new())
+        .expect("Expected the source to resolve");
+
+    let depths: Vec<_> = tree
+        .iter()
+        .filter_map(|element| element.as_token())
+        .filter(|token| token.lexeme() == "x")
+        .map(|token| resolution.depth(token))
+        .collect();
+
+    assert_eq!(vec![None, None], depths);
+}
+
+#[test]
+fn resolver_keeps_globals_between_programs_only_when_they_resolve() {
+    let mut globals = Globals::new();
+
+    resolve_with("let x = 1;", &mut globals).unwrap();
+    resolve_with("let y = 1; let z = nope;", &mut globals).unwrap_err();
+
+    assert!(globals.contains("x"));
+    assert!(!globals.contains("y"));
+    assert!(resolve_with("let w = x;", &mut globals).is_ok());
+}
+
+#[test]
+fn resolver_only_reports_globals_declared_twice_by_the_same_program() {
+    let mut globals = Globals::new();
+
+    resolve_with("let x = 1;", &mut globals).unwrap();
+
+    assert!(resolve_with("let x = 2;", &mut globals).is_ok());
+    assert!(resolve_with("fn x() {}", &mut globals).is_ok());
+}
+
+#[test]
+fn resolver_allows_natives_to_be_redeclared() {
+    let mut globals = Globals::new();
+    globals.define_native("print");
+
+    assert!(resolve_with("let p = print; let print = 1;", &mut globals).is_ok());
+}
+
+#[test]
+fn resolver_reports_duplicate_struct_fields() {
+    let errors = resolve("struct Foo { a: i64, get a: i64 }").unwrap_err();
+
+    assert_eq!(
+        vec![StaticErrorKind::DuplicateFieldDeclaration],
+        kinds(&errors)
+    );
+}
+
+#[test]
+fn struct_literals_resolve_their_struct_and_field_values() {
+    let errors = resolve("struct Foo { a: i64 } let f = Foo { a: missing };").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UndeclaredVariable], kinds(&errors));
+    assert!(resolve("struct Foo { a: i64 } let x = 1; let f = Foo { a: x };").is_ok());
+}
+
+#[test]
+fn resolver_reports_duplicate_methods_in_an_impl() {
+    let errors = resolve("struct Dog; impl Dog { fn bark(self) {} fn bark(self) {} }").unwrap_err();
+
+    assert_eq!(
+        vec![StaticErrorKind::DuplicateMethodDeclaration],
+        kinds(&errors)
+    );
+}
+
+#[test]
+fn methods_are_not_declared_as_variables() {
+    let errors = resolve("struct Dog; impl Dog { fn bark(self) {} } bark(1);").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UndeclaredVariable], kinds(&errors));
+}
+
+#[test]
+fn impls_must_name_a_declared_interface() {
+    let errors = resolve("struct Dog; impl Animal for Dog { fn speak(self) {} }").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UndeclaredVariable], kinds(&errors));
+    assert!(resolve(
+        "interface Animal { fn speak(self); } struct Dog; impl Animal for Dog { fn speak(self) {} }"
+    )
+    .is_ok());
+}
+
+#[test]
+fn type_parameter_bounds_must_be_declared() {
+    let errors = resolve("struct Cage[T: Mammal] { Animal: T }").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UndeclaredVariable], kinds(&errors));
+}
+
+#[test]
+fn type_parameter_bounds_must_be_interfaces() {
+    let errors = resolve("struct Dog; fn feed[T: Dog](value: T) {}").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UnsatisfiedConstraint], kinds(&errors));
+    assert_eq!("Dog bounds T but is not an interface", errors[0].message());
+}
+
+#[test]
+fn type_arguments_must_implement_every_bound() {
+    let source = r#"
+		interface Mammal { fn feed(self); }
+		interface Pet { fn name(self); }
+		struct Dog;
+		impl Mammal for Dog { fn feed(self) {} }
+		struct Cage[T: Mammal + Pet] { Animal: T }
+		struct Zoo { Dogs: Cage[Dog], Counts: Cage[i64] }
+		"#;
+    let errors = resolve(source).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "Dog does not implement Pet required by T",
+            "i64 does not implement Mammal required by T",
+            "i64 does not implement Pet required by T",
+        ],
+        messages
+    );
+    assert_eq!(
+        TextRange::new(
+            source.find("Dog]").unwrap(),
+            source.find("Dog]").unwrap() + 3
+        ),
+        errors[0].range()
+    );
+}
+
+#[test]
+fn type_parameters_satisfy_the_bounds_they_are_declared_with() {
+    let source = r#"
+		interface Mammal { fn feed(self); }
+		struct Cage[T: Mammal] { Animal: T }
+		struct Row[U: Mammal] { First: Cage[U] }
+		struct Pile[V] { First: Cage[V] }
+		"#;
+    let errors = resolve(source).unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UnsatisfiedConstraint], kinds(&errors));
+    assert_eq!(
+        "V does not implement Mammal required by T",
+        errors[0].message()
+    );
+}
+
+#[test]
+fn arguments_to_generic_functions_must_implement_their_bounds() {
+    let source = r#"
+		interface Mammal { fn feed(self); }
+		struct Dog;
+		struct Rock;
+		impl Mammal for Dog { fn feed(self) {} }
+		fn care_for[Z: Mammal](value: Z) { value.feed(); }
+		fn unknown_value() { return Dog {}; }
+		care_for(Dog {});
+		care_for(Rock {});
+		care_for("a rock");
+		care_for(unknown_value());
+		"#;
+    let errors = resolve(source).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "Rock does not implement Mammal required by Z",
+            "string does not implement Mammal required by Z",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn arguments_to_generic_methods_must_implement_their_bounds() {
+    let source = r#"
+		interface Mammal { fn feed(self); }
+		struct Dog;
+		struct Rock;
+		struct Keeper;
+		impl Mammal for Dog { fn feed(self) {} }
+		impl Keeper {
+			fn care_for[Z: Mammal](self, value: Z) { value.feed(); }
+			fn shift(self) { self.care_for(Rock {}); }
+		}
+		Keeper {}.care_for(Dog {});
+		Keeper.care_for(Keeper {}, 42);
+		"#;
+    let errors = resolve(source).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "Rock does not implement Mammal required by Z",
+            "i64 does not implement Mammal required by Z",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn annotated_variables_and_results_must_satisfy_bounds() {
+    let source = r#"
+		interface Pet { fn name(self); }
+		struct Rock;
+		struct Cage[T: Pet] { item: T }
+		fn f() -> Cage[Rock] { return f(); }
+		let c: Cage[Rock] = f();
+		interface Keeper { fn cage(self) -> Cage[i64]; }
+		"#;
+    let errors = resolve_with(source, &mut Globals::new()).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "Rock does not implement Pet required by T",
+            "Rock does not implement Pet required by T",
+            "i64 does not implement Pet required by T",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn struct_literals_infer_their_type_arguments_from_their_fields() {
+    let source = r#"
+		interface Pet { fn name(self); }
+		struct Dog;
+		struct Rock;
+		impl Pet for Dog { fn name(self) {} }
+		struct Cage[T: Pet] { item: T, label: string }
+		let dog = Cage { item: Dog {}, label: "dog" };
+		let rock = Cage { item: Rock {}, label: "rock" };
+		fn cage(value) { return Cage { item: value, label: "?" }; }
+		fn cage_rock() {
+			let r = Rock {};
+			let n: i64 = 1;
+			return [Cage { item: r, label: "" }, Cage { item: n, label: "" }];
+		}
+		"#;
+    let errors = resolve(source).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "Rock does not implement Pet required by T",
+            "Rock does not implement Pet required by T",
+            "i64 does not implement Pet required by T",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn variables_keep_the_struct_type_they_are_declared_with() {
+    let source = r#"
+		interface Mammal { fn feed(self); }
+		struct Dog;
+		struct Rock;
+		impl Mammal for Dog { fn feed(self) {} }
+		fn care_for[Z: Mammal](value: Z) { value.feed(); }
+		let rock = Rock {};
+		let dog = Dog {};
+		care_for(rock);
+		care_for(dog);
+		{
+			let pebble: Rock = rock;
+			care_for(pebble);
+		}
+		"#;
+    let errors = resolve(source).unwrap_err();
+
+    assert_eq!(
+        vec![
+            TextRange::new(
+                source.find("rock)").unwrap(),
+                source.find("rock)").unwrap() + 4
+            ),
+            TextRange::new(
+                source.find("pebble)").unwrap(),
+                source.find("pebble)").unwrap() + 6
+            ),
+        ],
+        errors.iter().map(|error| error.range()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn bounded_parameters_can_be_passed_on_to_generic_functions() {
+    let source = r#"
+		interface Mammal { fn feed(self); }
+		interface Pet { fn name(self); }
+		fn feed[Z: Mammal](value: Z) { value.feed(); }
+		fn pet[P: Pet](value: P) { feed(value); }
+		fn groom[M: Mammal + Pet](value: M) { feed(value); }
+		"#;
+    let errors = resolve(source).unwrap_err();
+
+    assert_eq!(1, errors.len());
+    assert_eq!(
+        "P does not implement Mammal required by Z",
+        errors[0].message()
+    );
+}
+
+#[test]
+fn annotated_declarations_must_be_initialized_with_their_type() {
+    let errors = resolve("let x: i64 = 4; let y: string = 4.5;").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::TypeMismatch], kinds(&errors));
+    assert_eq!("expected string but found f64", errors[0].message());
+    assert_eq!(TextRange::new(32, 35), errors[0].range());
+}
+
+#[test]
+fn unannotated_locals_take_the_type_of_their_initializer() {
+    let errors =
+        resolve("let x = 4; let y = [x, 2]; let z: Array[string] = y; x = 'c';").unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "expected Array[string] but found Array[i64]",
+            "expected i64 but found char",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn operands_must_have_types_the_operator_accepts() {
+    let errors = resolve("let x = 1 + (2 * \"a\"); let y = -true; let z = 1 < 2.5;").unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "cannot apply '*' to i64 and string",
+            "cannot apply '-' to bool",
+        ],
+        messages
+    );
+    assert_eq!(TextRange::new(13, 20), errors[0].range());
+}
+
+#[test]
+fn ints_mixed_with_floats_are_promoted_to_floats() {
+    let errors = resolve(
+        "let a: f64 = 1 + 2.5; let b: i64 = 2 ** 0.5; let c: i64 = 7 % 2; let d: i64 = 7 % 2.5;",
+    )
+    .unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec!["expected i64 but found f64", "expected i64 but found f64"],
+        messages
+    );
+}
+
+#[test]
+fn ints_can_be_used_where_floats_are_expected() {
+    let errors = resolve(
+        r#"
+		let y: f64 = 3;
+		fn f(a: f64) -> f64 { return 1; }
+		f(2);
+		let x = 1;
+		x = 1.5;
+		let z: i64 = x;
+		let w: i64 = 2;
+		w = 2.5;
+		"#,
+    )
+    .unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec!["expected i64 but found f64", "expected i64 but found f64"],
+        messages
+    );
+}
+
+#[test]
+fn logical_operators_accept_conditions() {
+    let errors = resolve(
+        "let a: bool = 1 < 2 && true || 0; let b = \"a\" && true; let c: bool = !1 && 2 != 3;",
+    )
+    .unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(vec!["cannot apply '&&' to string and bool"], messages);
+}
+
+#[test]
+fn bitwise_operators_accept_ints_or_bools() {
+    let errors = resolve(
+        "let a: i64 = 6 & 3 | 1 ^ 8; let b: bool = true & false; let c = 1 | true; let d = 1.5 << 2;",
+    )
+    .unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "cannot apply '|' to i64 and bool",
+            "cannot apply '<<' to f64 and i64",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn strings_are_typed_through_concatenation_interpolation_and_methods() {
+    let source = r#"
+		let name = "newt";
+		let a: string = name + "s";
+		let b: string = "${name} is ${4} letters";
+		let c: i64 = name.find("w") + name.length;
+		let d: Array[string] = name.split("");
+		let e: char = name[0];
+		let f: i64 = name.upper();
+		let g = "${nope}";
+		let h = name.slice(1);
+		"#;
+    let errors = resolve(source).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "expected i64 but found string",
+            "'nope' is not declared",
+            "expected 2 arguments but found 1",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn calls_are_checked_against_annotated_parameters_and_results() {
+    let source = r#"
+		fn half(value: f64) -> f64 { return value / 2.0; }
+		fn name() -> string { return 42; }
+		let a: f64 = half(3.0);
+		let b: i64 = half(3.0);
+		half("3");
+		half(3.0, 4.0);
+		"#;
+    let errors = resolve(source).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "expected string but found i64",
+            "expected i64 but found f64",
+            "expected f64 but found string",
+            "expected 1 arguments but found 2",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn annotations_must_name_a_type() {
+    let source = r#"
+		struct Box[T] { item: T, others: Array[Strin] }
+		interface Shape { fn area(self) -> float; }
+		fn f(a: int) -> Foo { return a; }
+		let b: Box[print] = Box { item: 1, others: [] };
+		let c: Array[Shape] = [];
+		{
+			struct Local { x: i64 }
+			let l: Local = Local { x: 1 };
+		}
+		"#;
+    let mut globals = Globals::new();
+    globals.define_native("print");
+
+    let errors = resolve_with(source, &mut globals).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "'Strin' is not a type",
+            "'float' is not a type",
+            "'int' is not a type",
+            "'Foo' is not a type",
+            "'print' is not a type",
+        ],
+        messages
+    );
+    assert!(errors
+        .iter()
+        .all(|error| error.kind() == StaticErrorKind::UnknownType));
+}
+
+#[test]
+fn annotations_may_name_types_declared_by_earlier_programs() {
+    let mut globals = Globals::new();
+
+    assert!(resolve_with("struct Foo { a: i64 } interface Bar {}", &mut globals).is_ok());
+    assert!(resolve_with("fn f(foo: Foo) -> Bar { return foo; }", &mut globals).is_ok());
+}
+
+#[test]
+fn unannotated_parameters_accept_anything() {
+    assert!(resolve("fn twice(x) { return x + x; } let a = twice(1); let b: string = a;").is_ok());
+}
+
+#[test]
+fn struct_fields_and_methods_are_typed() {
+    let source = r#"
+		interface Named { fn name(self) -> string; }
+		struct Dog { get Age: i64 }
+		struct Rock;
+		impl Named for Dog { fn name(self) -> string { return "Rex"; } }
+		fn greet(value: Named) -> string { return value.name(); }
+		let dog = Dog { Age: "three" };
+		let age: string = dog.Age;
+		let name: string = dog.name();
+		dog.Age = 4.0;
+		greet(dog);
+		greet(Rock {});
+		"#;
+    let errors = resolve(source).unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "expected i64 but found string",
+            "expected string but found i64",
+            "expected i64 but found f64",
+            "expected Named but found Rock",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn conditions_must_be_truthy() {
+    let errors = resolve("if \"yes\" { } while 1 { }").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::TypeMismatch], kinds(&errors));
+    assert_eq!("expected bool but found string", errors[0].message());
+}
+
+#[test]
+fn imports_and_exports_must_be_at_the_top_level() {
+    let errors = resolve("fn f() { import Notepad.Store; export f; }").unwrap_err();
+
+    assert_eq!(
+        vec![
+            StaticErrorKind::MisplacedModuleStmt,
+            StaticErrorKind::MisplacedModuleStmt
+        ],
+        kinds(&errors)
+    );
+}
+
+#[test]
+fn break_and_continue_must_be_inside_a_loop_of_their_function() {
+    assert!(resolve("while true { if true { break; } continue; }").is_ok());
+
+    let errors = resolve("break; for x in [] { fn f() { continue; } } let g = fn () { break; };")
+        .unwrap_err();
+
+    assert_eq!(
+        vec![
+            StaticErrorKind::MisplacedJumpStmt,
+            StaticErrorKind::MisplacedJumpStmt,
+            StaticErrorKind::MisplacedJumpStmt
+        ],
+        kinds(&errors)
+    );
+    assert_eq!(
+        "'continue' is only allowed inside a loop",
+        errors[1].message()
+    );
+}
+
+#[test]
+fn labels_must_name_an_enclosing_loop() {
+    assert!(resolve("a: while true { for x in [] { break a; } }").is_ok());
+
+    let errors = resolve("a: while true {} while true { continue a; }").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UndeclaredLabel], kinds(&errors));
+    assert_eq!(
+        "'a' does not label a loop around this statement",
+        errors[0].message()
+    );
+}
+
+#[test]
+fn matches_on_structs_must_cover_every_instance() {
+    let source = r#"
+		struct Point { get x: i64, get y: i64 }
+		let p = Point { x: 1, y: 2 };
+		let a = match p { Point { x: 0, y } => y, Point { x, y: _ } => x };
+		let b = match p { q if q.x > 0 => 1, _ => 0 };
+		"#;
+    assert!(resolve(source).is_ok());
+
+    let errors = resolve(
+        "struct Point { get x: i64 } let p = Point { x: 1 }; let a = match p { Point { x: 0 } => 0 };",
+    )
+    .unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::NonExhaustiveMatch], kinds(&errors));
+    assert_eq!("patterns do not cover every Point", errors[0].message());
+    assert_eq!(TextRange::new(66, 68), errors[0].range());
+}
+
+#[test]
+fn matches_cover_structs_field_by_field() {
+    let source = r#"
+		struct Q { get X: bool }
+		struct P { get q: Q, get n: i64 }
+		let q = Q { X: true };
+		let a = match q { Q { X: true } => 1, Q { X: false } => 2 };
+		let p = P { q: q, n: 1 };
+		let b = match p {
+			P { q: Q { X: true }, n: 0 } => 1,
+			P { q: Q { X: false }, n } => n,
+			P { q: _, n: _ } => 3,
+		};
+		let c = match true { true => 1, false => 0 };
+		struct L { get next: L }
+		fn f(l: L) { return match l { L { next: L { next } } => next }; }
+		"#;
+    assert!(resolve(source).is_ok());
+
+    let errors = resolve(
+        r#"
+		struct Q { get X: bool, get Y: bool }
+		let q = Q { X: true, Y: true };
+		let a = match q { Q { X: true } => 1, Q { X: false, Y: false } => 2 };
+		let b = match q { Q { X: true, Y } if Y => 1, Q { X: false } => 2 };
+		let c = match q.X { true => 1, x if x => 2 };
+		"#,
+    )
+    .unwrap_err();
+    let messages: Vec<&str> = errors.iter().map(|error| error.message()).collect();
+
+    assert_eq!(
+        vec![
+            "patterns do not cover every Q",
+            "patterns do not cover every Q",
+            "patterns do not cover every bool",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn match_patterns_bind_within_their_arm_and_must_fit_the_scrutinee() {
+    let errors = resolve("let a = match 1 { x => x }; let b = x;").unwrap_err();
+
+    assert_eq!(vec![StaticErrorKind::UndeclaredVariable], kinds(&errors));
+
+    let errors = resolve("let a = match 1 { \"one\" => 1, [x] => x, _ => 0 };").unwrap_err();
+
+    assert_eq!(
+        vec![StaticErrorKind::TypeMismatch, StaticErrorKind::TypeMismatch],
+        kinds(&errors)
+    );
+    assert_eq!("a string pattern cannot match i64", errors[0].message());
+    assert_eq!("an array pattern cannot match i64", errors[1].message());
+}
+
+#[test]
+fn exports_may_name_later_declarations_but_not_undeclared_ones() {
+    assert!(resolve("export f; fn f() {}").is_ok());
+    assert_eq!(
+        vec![StaticErrorKind::UndeclaredVariable],
+        kinds(&resolve("export g;").unwrap_err())
+    );
+}
+
+#[test]
+fn imports_declare_the_last_segment_of_the_path() {
+    assert!(resolve("import Notepad.Store; Store.x;").is_ok());
+}
+
+fn resolve(source: &str) -> Result<Resolution, Vec<NewtStaticError>> {
+    resolve_with(source, &mut Globals::new())
+}
+
+fn resolve_with(source: &str, globals: &mut Globals) -> Result<Resolution, Vec<NewtStaticError>> {
+    let tree: SyntaxTree = source.into();
+
+    Resolver::resolve(tree.root().as_node().unwrap(), globals)
+}
+
+fn kinds(errors: &[NewtStaticError]) -> Vec<StaticErrorKind> {
+    errors.iter().map(|error| error.kind()).collect()
+}
@@ -0,0 +1,1191 @@
+use crate::featurez::analysis::Resolution;
+use crate::featurez::newtypes::TransparentNewType;
+use crate::featurez::syntax::*;
+use crate::featurez::TokenKind;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Error, Formatter};
+use std::rc::Rc;
+
+/// `Unknown` is compatible with every other type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Int,
+    Float,
+    Char,
+    String,
+    Bool,
+    Array(Box<Type>),
+    Function(Rc<FunctionType>),
+    Struct(String),
+    Interface(String),
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionType {
+    parameters: Vec<Type>,
+    result: Type,
+}
+
+impl Type {
+    pub fn from_primitive_name(name: &str) -> Option<Type> {
+        match name {
+            "i64" => Some(Type::Int),
+            "f64" => Some(Type::Float),
+            "char" => Some(Type::Char),
+            "string" => Some(Type::String),
+            "bool" => Some(Type::Bool),
+            _ => None,
+        }
+    }
+
+    pub fn from_literal(kind: TokenKind) -> Option<Type> {
+        match kind {
+            TokenKind::IntegerLiteral => Some(Type::Int),
+            TokenKind::FloatLiteral => Some(Type::Float),
+            TokenKind::GlyphLiteral => Some(Type::Char),
+            TokenKind::StringLiteral => Some(Type::String),
+            TokenKind::True | TokenKind::False => Some(Type::Bool),
+            _ => None,
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Type::Int | Type::Float)
+    }
+
+    fn is_truthy(&self) -> bool {
+        matches!(self, Type::Bool | Type::Int | Type::Unknown)
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            Type::Int => write!(f, "i64"),
+            Type::Float => write!(f, "f64"),
+            Type::Char => write!(f, "char"),
+            Type::String => write!(f, "string"),
+            Type::Bool => write!(f, "bool"),
+            Type::Array(element) => write!(f, "Array[{}]", element),
+            Type::Function(function) => {
+                write!(f, "fn(")?;
+                for (index, parameter) in function.parameters.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", parameter)?;
+                }
+                write!(f, ") -> {}", function.result)
+            }
+            Type::Struct(name) | Type::Interface(name) => write!(f, "{}", name),
+            Type::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct StructInfo {
+    fields: HashMap<String, Type>,
+    methods: HashMap<String, (Rc<FunctionType>, bool)>,
+}
+
+/// Only the fields and methods of structs declared by the program being checked are known.
+pub struct TypeChecker<'a> {
+    resolution: &'a Resolution,
+    structs: HashMap<String, StructInfo>,
+    interfaces: HashSet<String>,
+    implementations: HashMap<String, HashSet<String>>,
+    scopes: Vec<HashMap<String, Type>>,
+    // The variables of each scope declared without an annotation from an i64, which become
+    // f64 variables when assigned one
+    inferred_ints: Vec<HashSet<String>>,
+    type_parameters: Vec<HashSet<String>>,
+    results: Vec<Type>,
+    impl_type: Option<String>,
+    errors: Vec<NewtStaticError>,
+}
+
+impl<'a> TypeChecker<'a> {
+    pub fn check(node: &SyntaxNode, resolution: &'a Resolution) -> Vec<NewtStaticError> {
+        let mut checker = TypeChecker {
+            resolution,
+            structs: HashMap::new(),
+            interfaces: HashSet::new(),
+            implementations: HashMap::new(),
+            scopes: vec![HashMap::new()],
+            inferred_ints: vec![HashSet::new()],
+            type_parameters: Vec::new(),
+            results: Vec::new(),
+            impl_type: None,
+            errors: Vec::new(),
+        };
+
+        if let Some(expr) = ExprNode::cast(node) {
+            checker.visit_expr(expr);
+        } else if let Some(stmt) = StmtNode::cast(node) {
+            if node.kind() == SyntaxKind::StmtListStmt {
+                checker.declare(StmtListStmtNode::from_inner(node));
+            }
+
+            checker.visit_stmt(stmt);
+        }
+
+        checker.errors
+    }
+
+    fn declare(&mut self, node: &StmtListStmtNode) {
+        for stmt in node.stmts() {
+            match stmt.kind() {
+                StmtKind::StructDeclarationStmt(node) => {
+                    self.structs.insert(
+                        node.identifier().lexeme().to_string(),
+                        StructInfo::default(),
+                    );
+                }
+                StmtKind::InterfaceDeclarationStmt(node) => {
+                    self.interfaces
+                        .insert(node.identifier().lexeme().to_string());
+                }
+                StmtKind::ImplStmt(node) => {
+                    if let Some(interface) = node.interface_identifier() {
+                        self.implementations
+                            .entry(node.type_identifier().lexeme().to_string())
+                            .or_default()
+                            .insert(interface.lexeme().to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for stmt in node.stmts() {
+            match stmt.kind() {
+                StmtKind::StructDeclarationStmt(node) => {
+                    self.begin_type_parameters(node.type_parameters());
+                    let fields = node
+                        .fields()
+                        .map(|field| {
+                            (
+                                field.identifier().lexeme().to_string(),
+                                self.resolve_type(field.type_ref()),
+                            )
+                        })
+                        .collect();
+                    self.type_parameters.pop();
+
+                    if let Some(info) = self.structs.get_mut(node.identifier().lexeme()) {
+                        info.fields = fields;
+                    }
+                }
+                StmtKind::ImplStmt(node) => {
+                    self.begin_type_parameters(node.type_parameters());
+                    let methods: Vec<_> = node
+                        .methods()
+                        .map(|method| {
+                            let takes_self = method
+                                .arguments()
+                                .next()
+                                .is_some_and(|parameter| parameter.lexeme() == "self");
+
+                            let function = self.function_type(
+                                method.type_parameters(),
+                                method.parameters().skip(takes_self as usize),
+                                method.return_type(),
+                            );
+
+                            (
+                                method.identifier().lexeme().to_string(),
+                                (function, takes_self),
+                            )
+                        })
+                        .collect();
+                    self.type_parameters.pop();
+
+                    if let Some(info) = self.structs.get_mut(node.type_identifier().lexeme()) {
+                        info.methods.extend(methods);
+                    }
+                }
+                StmtKind::FunctionDeclarationStmt(node) => {
+                    let function = self.function_type(
+                        node.type_parameters(),
+                        node.parameters(),
+                        node.return_type(),
+                    );
+                    self.define(node.identifier(), Type::Function(function));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn begin_type_parameters<'n>(&mut self, nodes: impl Iterator<Item = &'n TypeParameterNode>) {
+        self.type_parameters.push(
+            nodes
+                .map(|node| node.identifier().lexeme().to_string())
+                .collect(),
+        );
+    }
+
+    /// Methods leave out `self`, so the type is that of the bound method.
+    fn function_type<'n>(
+        &mut self,
+        type_parameters: impl Iterator<Item = &'n TypeParameterNode>,
+        parameters: impl Iterator<Item = (&'n SyntaxToken, Option<&'n TypeRefNode>)>,
+        return_type: Option<&TypeRefNode>,
+    ) -> Rc<FunctionType> {
+        self.begin_type_parameters(type_parameters);
+
+        let parameters = parameters
+            .map(|(_, annotation)| self.resolve_optional_type(annotation))
+            .collect();
+        let result = self.resolve_optional_type(return_type);
+
+        self.type_parameters.pop();
+
+        Rc::new(FunctionType { parameters, result })
+    }
+
+    fn resolve_optional_type(&self, node: Option<&TypeRefNode>) -> Type {
+        node.map_or(Type::Unknown, |node| self.resolve_type(node))
+    }
+
+    fn resolve_type(&self, node: &TypeRefNode) -> Type {
+        let name = node.identifier().lexeme();
+
+        if let Some(primitive) = Type::from_primitive_name(name) {
+            return primitive;
+        }
+
+        if self
+            .type_parameters
+            .iter()
+            .any(|scope| scope.contains(name))
+        {
+            Type::Unknown
+        } else if name == "Array" {
+            Type::Array(Box::new(
+                node.arguments()
+                    .next()
+                    .map_or(Type::Unknown, |element| self.resolve_type(element)),
+            ))
+        } else if self.structs.contains_key(name) {
+            Type::Struct(name.to_string())
+        } else if self.interfaces.contains(name) {
+            Type::Interface(name.to_string())
+        } else {
+            Type::Unknown
+        }
+    }
+
+    fn check_type_ref(&mut self, node: &TypeRefNode) {
+        let identifier = node.identifier();
+        let name = identifier.lexeme();
+
+        let known = Type::from_primitive_name(name).is_some()
+            || name == "Array"
+            || self.resolution.is_type(name)
+            || self
+                .type_parameters
+                .iter()
+                .any(|scope| scope.contains(name));
+
+        if !known {
+            self.errors.push(NewtStaticError::new(
+                StaticErrorKind::UnknownType,
+                &format!("'{}' is not a type", name),
+                identifier.range(),
+            ));
+        }
+
+        for argument in node.arguments() {
+            self.check_type_ref(argument);
+        }
+    }
+
+    fn is_assignable(&self, expected: &Type, found: &Type) -> bool {
+        match (expected, found) {
+            (Type::Unknown, _) | (_, Type::Unknown) => true,
+            (Type::Array(expected), Type::Array(found)) => self.is_assignable(expected, found),
+            (Type::Function(expected), Type::Function(found)) => {
+                expected.parameters.len() == found.parameters.len()
+                    && expected
+                        .parameters
+                        .iter()
+                        .zip(found.parameters.iter())
+                        .all(|(e, f)| self.is_assignable(f, e))
+                    && self.is_assignable(&expected.result, &found.result)
+            }
+            (Type::Interface(interface), Type::Struct(name)) => self
+                .implementations
+                .get(name)
+                .is_some_and(|interfaces| interfaces.contains(interface)),
+            (Type::Float, Type::Int) => true,
+            (expected, found) => expected == found,
+        }
+    }
+
+    fn expect(&mut self, expected: &Type, found: &Type, range: TextRange) {
+        if !self.is_assignable(expected, found) {
+            self.report(&format!("expected {} but found {}", expected, found), range);
+        }
+    }
+
+    fn expect_condition(&mut self, node: &ExprNode) {
+        let condition = self.visit_expr(node);
+
+        if !condition.is_truthy() {
+            self.report(
+                &format!("expected bool but found {}", condition),
+                node.syntax().range(),
+            );
+        }
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+        self.inferred_ints.push(HashSet::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+        self.inferred_ints.pop();
+    }
+
+    fn define(&mut self, identifier: &SyntaxToken, value_type: Type) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(identifier.lexeme().to_string(), value_type);
+        }
+    }
+
+    fn lookup(&self, identifier: &SyntaxToken) -> Type {
+        self.scope_of(identifier)
+            .and_then(|scope| self.scopes[scope].get(identifier.lexeme()))
+            .cloned()
+            .unwrap_or(Type::Unknown)
+    }
+
+    fn scope_of(&self, identifier: &SyntaxToken) -> Option<usize> {
+        let name = identifier.lexeme();
+
+        // Globals are only visible to the checker when the program being checked declares them.
+        if self.resolution.depth(identifier).is_some() {
+            (1..self.scopes.len())
+                .rev()
+                .find(|&scope| self.scopes[scope].contains_key(name))
+        } else {
+            Some(0).filter(|_| self.scopes[0].contains_key(name))
+        }
+    }
+
+    fn widen(&mut self, identifier: &SyntaxToken, value_type: &Type) -> bool {
+        let name = identifier.lexeme();
+
+        match self.scope_of(identifier) {
+            Some(scope) if *value_type == Type::Float && self.inferred_ints[scope].remove(name) => {
+                self.scopes[scope].insert(name.to_string(), Type::Float);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn check_function<'n>(
+        &mut self,
+        type_parameters: impl Iterator<Item = &'n TypeParameterNode>,
+        parameters: impl Iterator<Item = (&'n SyntaxToken, Option<&'n TypeRefNode>)>,
+        return_type: Option<&TypeRefNode>,
+        body: &StmtListStmtNode,
+    ) {
+        let impl_type = self.impl_type.take();
+        self.begin_type_parameters(type_parameters);
+        self.begin_scope();
+
+        for (index, (parameter, annotation)) in parameters.enumerate() {
+            let parameter_type = match (annotation, &impl_type) {
+                (Some(annotation), _) => {
+                    self.check_type_ref(annotation);
+                    self.resolve_type(annotation)
+                }
+                (None, Some(impl_type)) if index == 0 && parameter.lexeme() == "self" => {
+                    Type::Struct(impl_type.clone())
+                }
+                (None, _) => Type::Unknown,
+            };
+
+            self.define(parameter, parameter_type);
+        }
+
+        if let Some(return_type) = return_type {
+            self.check_type_ref(return_type);
+        }
+
+        let result = self.resolve_optional_type(return_type);
+        self.results.push(result);
+        self.visit_stmt_list_stmt(body);
+        self.results.pop();
+
+        self.end_scope();
+        self.type_parameters.pop();
+        self.impl_type = impl_type;
+    }
+
+    fn property_type(&self, source: &Type, name: &str) -> Type {
+        let info = match source {
+            Type::Struct(struct_name) => self.structs.get(struct_name),
+            Type::String => return string_property_type(name),
+            _ => None,
+        };
+
+        info.and_then(|info| {
+            info.fields.get(name).cloned().or_else(|| {
+                info.methods
+                    .get(name)
+                    .map(|(method, _)| Type::Function(method.clone()))
+            })
+        })
+        .unwrap_or(Type::Unknown)
+    }
+
+    fn check_pattern(&mut self, pattern: &PatternNode, scrutinee: &Type) {
+        match pattern.kind() {
+            PatternKind::WildcardPattern => {}
+            PatternKind::BindingPattern(node) => self.define(node.identifier(), scrutinee.clone()),
+            PatternKind::LiteralPattern(node) => {
+                let literal = self.visit_expr(node.expr());
+                let comparable = self.is_assignable(scrutinee, &literal)
+                    || (scrutinee.is_numeric() && literal.is_numeric());
+
+                if !comparable {
+                    self.report(
+                        &format!("a {} pattern cannot match {}", literal, scrutinee),
+                        pattern.syntax().range(),
+                    );
+                }
+            }
+            PatternKind::ArrayPattern(node) => {
+                let element = match scrutinee {
+                    Type::Array(element) => (**element).clone(),
+                    Type::Unknown => Type::Unknown,
+                    _ => {
+                        self.report(
+                            &format!("an array pattern cannot match {}", scrutinee),
+                            pattern.syntax().range(),
+                        );
+                        Type::Unknown
+                    }
+                };
+
+                for element_pattern in node.elements() {
+                    self.check_pattern(element_pattern, &element);
+                }
+            }
+            PatternKind::ObjectPattern(node) => self.check_pattern_fields(node.fields(), scrutinee),
+            PatternKind::StructPattern(node) => {
+                let name = node.identifier().lexeme();
+                let struct_type = if self.resolution.depth(node.identifier()).is_none()
+                    && self.structs.contains_key(name)
+                {
+                    Type::Struct(name.to_string())
+                } else {
+                    Type::Unknown
+                };
+
+                self.expect(scrutinee, &struct_type, pattern.syntax().range());
+                self.check_pattern_fields(node.fields(), &struct_type);
+            }
+        }
+    }
+
+    fn check_pattern_fields<'n>(
+        &mut self,
+        fields: impl Iterator<Item = &'n PatternFieldNode>,
+        source: &Type,
+    ) {
+        for field in fields {
+            let name = field.identifier().lexeme();
+
+            if let Type::Struct(struct_name) = source {
+                let has_field = self
+                    .structs
+                    .get(struct_name)
+                    .is_none_or(|info| info.fields.contains_key(name));
+
+                if !has_field {
+                    self.report(
+                        &format!("{} has no field '{}'", struct_name, name),
+                        field.identifier().range(),
+                    );
+                }
+            }
+
+            let field_type = self.property_type(source, name);
+            match field.pattern() {
+                Some(pattern) => self.check_pattern(pattern, &field_type),
+                None => self.define(field.identifier(), field_type),
+            }
+        }
+    }
+
+    /// Bools and structs are split into the values they can take; values of any other type are
+    /// only covered by a pattern that matches anything.
+    fn covers(&self, rows: Vec<PatternRow>, types: &[Type]) -> bool {
+        let (first, rest) = match types.split_first() {
+            Some(split) => split,
+            None => return !rows.is_empty(),
+        };
+
+        let constructors = match first {
+            Type::Bool => vec![Constructor::Bool(true), Constructor::Bool(false)],
+            Type::Struct(name) => match self.structs.get(name) {
+                Some(info) => {
+                    let mut fields: Vec<_> = info.fields.clone().into_iter().collect();
+                    fields.sort_by(|a, b| a.0.cmp(&b.0));
+                    vec![Constructor::Struct(name.clone(), fields)]
+                }
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        // Splitting on a column that only holds catch-alls gains nothing, and would never end
+        // for a struct with a field of its own type
+        if constructors.is_empty() || rows.iter().all(|row| is_catch_all(row[0])) {
+            let rows = rows
+                .into_iter()
+                .filter(|row| is_catch_all(row[0]))
+                .map(|row| row[1..].to_vec())
+                .collect();
+            return self.covers(rows, rest);
+        }
+
+        constructors.iter().all(|constructor| {
+            let rows = rows
+                .iter()
+                .filter_map(|row| specialize(row, constructor))
+                .collect();
+            let types: Vec<Type> = match constructor {
+                Constructor::Bool(_) => Vec::new(),
+                Constructor::Struct(_, fields) => fields.iter().map(|(_, t)| t.clone()).collect(),
+            };
+
+            self.covers(rows, &[types.as_slice(), rest].concat())
+        })
+    }
+
+    fn check_escapes(&mut self, text: &str, range: TextRange) {
+        if let Err(sequence) = unescape(text) {
+            self.errors.push(NewtStaticError::new(
+                StaticErrorKind::InvalidEscapeSequence,
+                &format!("invalid escape sequence '{}'", sequence),
+                range,
+            ));
+        }
+    }
+
+    fn check_integer(&mut self, text: &str, range: TextRange) {
+        if text.parse::<i64>().is_err() {
+            self.errors.push(NewtStaticError::new(
+                StaticErrorKind::IntegerLiteralOutOfRange,
+                &format!("integer literal '{}' does not fit in an i64", text),
+                range,
+            ));
+        }
+    }
+
+    fn report(&mut self, message: &str, range: TextRange) {
+        self.errors.push(NewtStaticError::new(
+            StaticErrorKind::TypeMismatch,
+            message,
+            range,
+        ));
+    }
+}
+
+enum Constructor {
+    Bool(bool),
+    Struct(String, Vec<(String, Type)>),
+}
+
+/// `None` matches anything, like a field a struct pattern leaves out.
+type PatternRow<'n> = Vec<Option<&'n PatternNode>>;
+
+fn is_catch_all(pattern: Option<&PatternNode>) -> bool {
+    matches!(
+        pattern.map(PatternNode::kind),
+        None | Some(PatternKind::WildcardPattern) | Some(PatternKind::BindingPattern(_))
+    )
+}
+
+fn specialize<'n>(row: &PatternRow<'n>, constructor: &Constructor) -> Option<PatternRow<'n>> {
+    let fields = match (row[0].map(PatternNode::kind), constructor) {
+        _ if is_catch_all(row[0]) => match constructor {
+            Constructor::Bool(_) => Vec::new(),
+            Constructor::Struct(_, fields) => vec![None; fields.len()],
+        },
+        (Some(PatternKind::LiteralPattern(node)), Constructor::Bool(value)) => {
+            let literal = match node.expr().kind() {
+                ExprKind::PrimitiveLiteralExpr(literal) => literal.literal().token_kind(),
+                _ => return None,
+            };
+
+            let expected = if *value {
+                TokenKind::True
+            } else {
+                TokenKind::False
+            };
+
+            if literal != expected {
+                return None;
+            }
+
+            Vec::new()
+        }
+        (Some(PatternKind::StructPattern(node)), Constructor::Struct(name, fields))
+            if node.identifier().lexeme() == name =>
+        {
+            fields
+                .iter()
+                .map(|(field, _)| {
+                    node.fields()
+                        .find(|f| f.identifier().lexeme() == field)
+                        .and_then(PatternFieldNode::pattern)
+                })
+                .collect()
+        }
+        _ => return None,
+    };
+
+    Some(fields.into_iter().chain(row[1..].iter().cloned()).collect())
+}
+
+fn string_property_type(name: &str) -> Type {
+    let (parameters, result) = match name {
+        "length" => return Type::Int,
+        "slice" => (vec![Type::Int, Type::Int], Type::String),
+        "split" => (vec![Type::String], Type::Array(Box::new(Type::String))),
+        "find" => (vec![Type::String], Type::Int),
+        "replace" => (vec![Type::String, Type::String], Type::String),
+        "upper" | "lower" => (vec![], Type::String),
+        _ => return Type::Unknown,
+    };
+
+    Type::Function(Rc::new(FunctionType { parameters, result }))
+}
+
+impl<'a> ExprVisitor<Type> for TypeChecker<'a> {
+    fn visit_expr(&mut self, node: &ExprNode) -> Type {
+        match node.kind() {
+            ExprKind::BinaryExpr(node) => self.visit_binary_expr(node),
+            ExprKind::UnaryExpr(node) => self.visit_unary_expr(node),
+            ExprKind::PrimitiveLiteralExpr(node) => self.visit_primitive_literal_expr(node),
+            ExprKind::ObjectLiteralExpr(node) => self.visit_object_literal_expr(node),
+            ExprKind::ObjectPropertyExpr(node) => self.visit_object_property_expr(node),
+            ExprKind::GroupingExpr(node) => self.visit_grouping_expr(node),
+            ExprKind::VariableExpr(node) => self.visit_variable_expr(node),
+            ExprKind::FunctionCallExpr(node) => self.visit_function_call_expr(node),
+            ExprKind::ArrayLiteralExpr(node) => self.visit_array_literal_expr(node),
+            ExprKind::IndexExpr(node) => self.visit_index_expr(node),
+            ExprKind::StructLiteralExpr(node) => self.visit_struct_literal_expr(node),
+            ExprKind::FunctionExpr(node) => self.visit_function_expr(node),
+            ExprKind::InterpolatedStringExpr(node) => self.visit_interpolated_string_expr(node),
+            ExprKind::MatchExpr(node) => self.visit_match_expr(node),
+        }
+    }
+
+    fn visit_binary_expr(&mut self, node: &BinaryExprNode) -> Type {
+        let lhs = self.visit_expr(node.lhs());
+        let rhs = self.visit_expr(node.rhs());
+        let operator = node.operator();
+
+        let result = match operator {
+            TokenKind::Plus
+            | TokenKind::Minus
+            | TokenKind::Star
+            | TokenKind::Slash
+            | TokenKind::Percent
+            | TokenKind::StarStar => match (&lhs, &rhs) {
+                (Type::Unknown, _) | (_, Type::Unknown) => Some(Type::Unknown),
+                (Type::Int, Type::Int) => Some(Type::Int),
+                (Type::String, Type::String) if operator == TokenKind::Plus => Some(Type::String),
+                // Ints are promoted to floats when mixed with them.
+                (lhs, rhs) if lhs.is_numeric() && rhs.is_numeric() => Some(Type::Float),
+                _ => None,
+            },
+            TokenKind::Ampersand | TokenKind::Pipe | TokenKind::Caret => match (&lhs, &rhs) {
+                (Type::Unknown, _) | (_, Type::Unknown) => Some(Type::Unknown),
+                (Type::Int, Type::Int) => Some(Type::Int),
+                (Type::Bool, Type::Bool) => Some(Type::Bool),
+                _ => None,
+            },
+            TokenKind::LessLess | TokenKind::GreaterGreater => match (&lhs, &rhs) {
+                (Type::Unknown, _) | (_, Type::Unknown) => Some(Type::Unknown),
+                (Type::Int, Type::Int) => Some(Type::Int),
+                _ => None,
+            },
+            TokenKind::AmpersandAmpersand | TokenKind::PipePipe => {
+                if lhs.is_truthy() && rhs.is_truthy() {
+                    Some(Type::Bool)
+                } else {
+                    None
+                }
+            }
+            _ => Some(Type::Bool),
+        };
+
+        result.unwrap_or_else(|| {
+            self.report(
+                &format!(
+                    "cannot apply '{}' to {} and {}",
+                    node.to_inner().nth_token(0).lexeme(),
+                    lhs,
+                    rhs
+                ),
+                node.to_inner().range(),
+            );
+            Type::Unknown
+        })
+    }
+
+    fn visit_unary_expr(&mut self, node: &UnaryExprNode) -> Type {
+        if let Some(literal) = node.negated_integer_literal() {
+            self.check_integer(&format!("-{}", literal.lexeme()), literal.range());
+            return Type::Int;
+        }
+
+        let rhs = self.visit_expr(node.rhs());
+
+        match (node.operator(), rhs) {
+            (_, Type::Unknown) => Type::Unknown,
+            (TokenKind::Minus, rhs) if rhs.is_numeric() => rhs,
+            (TokenKind::Bang, rhs) if rhs.is_truthy() => Type::Bool,
+            (_, rhs) => {
+                self.report(
+                    &format!(
+                        "cannot apply '{}' to {}",
+                        node.to_inner().nth_token(0).lexeme(),
+                        rhs
+                    ),
+                    node.to_inner().range(),
+                );
+                Type::Unknown
+            }
+        }
+    }
+
+    fn visit_primitive_literal_expr(&mut self, node: &PrimitiveLiteralExprNode) -> Type {
+        let literal = node.literal();
+
+        if let TokenKind::StringLiteral | TokenKind::GlyphLiteral = literal.token_kind() {
+            let lexeme = literal.lexeme();
+            self.check_escapes(&lexeme[1..lexeme.len() - 1], literal.range());
+        }
+
+        if literal.token_kind() == TokenKind::IntegerLiteral {
+            self.check_integer(literal.lexeme(), literal.range());
+        }
+
+        Type::from_literal(literal.token_kind()).unwrap_or(Type::Unknown)
+    }
+
+    fn visit_grouping_expr(&mut self, node: &GroupingExprNode) -> Type {
+        self.visit_expr(node.expr())
+    }
+
+    fn visit_variable_expr(&mut self, node: &VariableExprNode) -> Type {
+        self.lookup(node.identifier())
+    }
+
+    fn visit_function_call_expr(&mut self, node: &FunctionCallExprNode) -> Type {
+        let callee = self.visit_expr(node.callee());
+        let arguments: Vec<_> = node
+            .arguments()
+            .map(|argument| (self.visit_expr(argument), argument.syntax().range()))
+            .collect();
+
+        let function = match callee {
+            Type::Function(function) => function,
+            _ => return Type::Unknown,
+        };
+
+        if function.parameters.len() != arguments.len() {
+            self.report(
+                &format!(
+                    "expected {} arguments but found {}",
+                    function.parameters.len(),
+                    arguments.len()
+                ),
+                node.to_inner().range(),
+            );
+        } else {
+            for (parameter, (argument, range)) in function.parameters.iter().zip(arguments) {
+                self.expect(parameter, &argument, range);
+            }
+        }
+
+        function.result.clone()
+    }
+
+    fn visit_object_literal_expr(&mut self, node: &ObjectLiteralExprNode) -> Type {
+        for (_, value) in node.fields().iter() {
+            self.visit_expr(value);
+        }
+
+        Type::Unknown
+    }
+
+    fn visit_object_property_expr(&mut self, node: &ObjectPropertyExprNode) -> Type {
+        let source = self.visit_expr(node.source_expr());
+
+        self.property_type(&source, node.identifier().lexeme())
+    }
+
+    fn visit_array_literal_expr(&mut self, node: &ArrayLiteralExprNode) -> Type {
+        let elements: Vec<_> = node.elements().map(|e| self.visit_expr(e)).collect();
+        let element = match elements.split_first() {
+            Some((first, rest)) if rest.iter().all(|e| e == first) => first.clone(),
+            _ => Type::Unknown,
+        };
+
+        Type::Array(Box::new(element))
+    }
+
+    fn visit_index_expr(&mut self, node: &IndexExprNode) -> Type {
+        let source = self.visit_expr(node.source_expr());
+        let index = self.visit_expr(node.index());
+
+        match source {
+            Type::Array(element) => {
+                self.expect(&Type::Int, &index, node.index().syntax().range());
+                *element
+            }
+            Type::String => {
+                self.expect(&Type::Int, &index, node.index().syntax().range());
+                Type::Char
+            }
+            _ => Type::Unknown,
+        }
+    }
+
+    fn visit_struct_literal_expr(&mut self, node: &StructLiteralExprNode) -> Type {
+        let name = node.identifier().lexeme();
+        let struct_type = if self.resolution.depth(node.identifier()).is_none()
+            && self.structs.contains_key(name)
+        {
+            Type::Struct(name.to_string())
+        } else {
+            Type::Unknown
+        };
+
+        for (field, value) in node.fields() {
+            let value_type = self.visit_expr(value);
+            let field_type = self.property_type(&struct_type, field.lexeme());
+
+            self.expect(&field_type, &value_type, value.syntax().range());
+        }
+
+        struct_type
+    }
+
+    fn visit_function_expr(&mut self, node: &FunctionExprNode) -> Type {
+        let function =
+            self.function_type(std::iter::empty(), node.parameters(), node.return_type());
+
+        self.check_function(
+            std::iter::empty(),
+            node.parameters(),
+            node.return_type(),
+            node.stmts(),
+        );
+
+        Type::Function(function)
+    }
+
+    fn visit_interpolated_string_expr(&mut self, node: &InterpolatedStringExprNode) -> Type {
+        for (fragment, text) in node.fragments().zip(node.texts()) {
+            self.check_escapes(text, fragment.range());
+        }
+
+        for expr in node.exprs() {
+            self.visit_expr(expr);
+        }
+
+        Type::String
+    }
+
+    fn visit_match_expr(&mut self, node: &MatchExprNode) -> Type {
+        let scrutinee = self.visit_expr(node.scrutinee());
+        let mut arms = Vec::new();
+
+        for arm in node.arms() {
+            self.begin_scope();
+            self.check_pattern(arm.pattern(), &scrutinee);
+
+            if let Some(guard) = arm.guard() {
+                self.expect_condition(guard);
+            }
+
+            arms.push(self.visit_expr(arm.expr()));
+            self.end_scope();
+        }
+
+        if let Type::Struct(_) | Type::Bool = scrutinee {
+            let rows = node
+                .arms()
+                .filter(|arm| arm.guard().is_none())
+                .map(|arm| vec![Some(arm.pattern())])
+                .collect();
+
+            if !self.covers(rows, std::slice::from_ref(&scrutinee)) {
+                self.errors.push(NewtStaticError::new(
+                    StaticErrorKind::NonExhaustiveMatch,
+                    &format!("patterns do not cover every {}", scrutinee),
+                    node.scrutinee().syntax().range(),
+                ));
+            }
+        }
+
+        match arms.split_first() {
+            Some((first, rest)) if rest.iter().all(|a| a == first) => first.clone(),
+            _ => Type::Unknown,
+        }
+    }
+}
+
+impl<'a> StmtVisitor<()> for TypeChecker<'a> {
+    fn visit_stmt(&mut self, node: &StmtNode) {
+        match node.kind() {
+            StmtKind::VariableDeclarationStmt(node) => self.visit_variable_declaration_stmt(node),
+            StmtKind::AssignmentStmt(node) => self.visit_assignment_stmt(node),
+            StmtKind::StmtListStmt(node) => self.visit_stmt_list_stmt(node),
+            StmtKind::ExprStmt(node) => self.visit_expr_stmt(node),
+            StmtKind::IfStmt(node) => self.visit_if_stmt(node),
+            StmtKind::WhileStmt(node) => self.visit_while_stmt(node),
+            StmtKind::ForStmt(node) => self.visit_for_stmt(node),
+            StmtKind::FunctionDeclarationStmt(node) => self.visit_function_declaration_stmt(node),
+            StmtKind::ReturnStmt(node) => self.visit_return_stmt(node),
+            StmtKind::ThrowStmt(node) => self.visit_throw_stmt(node),
+            StmtKind::TryStmt(node) => self.visit_try_stmt(node),
+            StmtKind::BreakStmt(node) => self.visit_break_stmt(node),
+            StmtKind::ContinueStmt(node) => self.visit_continue_stmt(node),
+            StmtKind::StructDeclarationStmt(node) => self.visit_struct_declaration_stmt(node),
+            StmtKind::ImplStmt(node) => self.visit_impl_stmt(node),
+            StmtKind::InterfaceDeclarationStmt(node) => self.visit_interface_declaration_stmt(node),
+            StmtKind::ImportStmt(node) => self.visit_import_stmt(node),
+            StmtKind::ExportStmt(node) => self.visit_export_stmt(node),
+        }
+    }
+
+    fn visit_variable_declaration_stmt(&mut self, node: &VariableDeclarationStmtNode) {
+        let value_type = self.visit_expr(node.expr());
+
+        let variable_type = match node.type_ref() {
+            Some(annotation) => {
+                self.check_type_ref(annotation);
+                let annotated = self.resolve_type(annotation);
+                self.expect(&annotated, &value_type, node.expr().syntax().range());
+                annotated
+            }
+            None => value_type,
+        };
+
+        if let Some(inferred) = self.inferred_ints.last_mut() {
+            let name = node.identifier().lexeme().to_string();
+
+            if node.type_ref().is_none() && variable_type == Type::Int {
+                inferred.insert(name);
+            } else {
+                inferred.remove(&name);
+            }
+        }
+
+        self.define(node.identifier(), variable_type);
+    }
+
+    fn visit_assignment_stmt(&mut self, node: &AssignmentStmtNode) {
+        let value_type = self.visit_expr(node.expr());
+
+        let target_type = match node.rval().kind() {
+            RValKind::VariableRVal(variable) => {
+                if self.widen(variable.identifier(), &value_type) {
+                    return;
+                }
+                self.lookup(variable.identifier())
+            }
+            RValKind::ObjectPropertyRVal(property) => {
+                let source = self.visit_expr(property.source_expr());
+                self.property_type(&source, property.identifier().lexeme())
+            }
+            RValKind::IndexRVal(element) => {
+                let source = self.visit_expr(element.source_expr());
+                let index = self.visit_expr(element.index());
+
+                match source {
+                    Type::Array(element_type) => {
+                        self.expect(&Type::Int, &index, element.index().syntax().range());
+                        *element_type
+                    }
+                    _ => Type::Unknown,
+                }
+            }
+        };
+
+        self.expect(&target_type, &value_type, node.expr().syntax().range());
+    }
+
+    fn visit_stmt_list_stmt(&mut self, node: &StmtListStmtNode) {
+        if node.has_braces() {
+            self.begin_scope();
+        }
+
+        for stmt in node.stmts() {
+            self.visit_stmt(stmt);
+        }
+
+        if node.has_braces() {
+            self.end_scope();
+        }
+    }
+
+    fn visit_expr_stmt(&mut self, node: &ExprStmtNode) {
+        self.visit_expr(node.expr());
+    }
+
+    fn visit_if_stmt(&mut self, node: &IfStmtNode) {
+        self.expect_condition(node.condition());
+        self.visit_stmt_list_stmt(node.when_true());
+
+        if let Some(else_path) = node.when_false() {
+            self.visit_stmt_list_stmt(else_path);
+        }
+    }
+
+    fn visit_while_stmt(&mut self, node: &WhileStmtNode) {
+        self.expect_condition(node.condition());
+        self.visit_stmt_list_stmt(node.stmts());
+    }
+
+    fn visit_for_stmt(&mut self, node: &ForStmtNode) {
+        let element_type = match self.visit_expr(node.iterable()) {
+            Type::Array(element) => *element,
+            _ => Type::Unknown,
+        };
+
+        self.begin_scope();
+        self.define(node.identifier(), element_type);
+        self.visit_stmt_list_stmt(node.stmts());
+        self.end_scope();
+    }
+
+    fn visit_function_declaration_stmt(&mut self, node: &FunctionDeclarationStmtNode) {
+        let function = self.function_type(
+            node.type_parameters(),
+            node.parameters(),
+            node.return_type(),
+        );
+        self.define(node.identifier(), Type::Function(function));
+
+        self.check_function(
+            node.type_parameters(),
+            node.parameters(),
+            node.return_type(),
+            node.stmts(),
+        );
+    }
+
+    fn visit_return_stmt(&mut self, node: &ReturnStmtNode) {
+        let expected = self.results.last().cloned().unwrap_or(Type::Unknown);
+
+        match node.result() {
+            Some(result) => {
+                let found = self.visit_expr(result);
+                self.expect(&expected, &found, result.syntax().range());
+            }
+            None if expected != Type::Unknown => self.report(
+                &format!("expected {} but found nothing", expected),
+                node.to_inner().range(),
+            ),
+            None => {}
+        }
+    }
+
+    fn visit_throw_stmt(&mut self, node: &ThrowStmtNode) {
+        self.visit_expr(node.expr());
+    }
+
+    fn visit_break_stmt(&mut self, _node: &BreakStmtNode) {}
+
+    fn visit_continue_stmt(&mut self, _node: &ContinueStmtNode) {}
+
+    fn visit_try_stmt(&mut self, node: &TryStmtNode) {
+        self.visit_stmt_list_stmt(node.stmts());
+
+        // Anything may be thrown, so nothing is known of the caught value
+        if let Some(clause) = node.catch_clause() {
+            self.begin_scope();
+            self.define(clause.identifier(), Type::Unknown);
+            self.visit_stmt_list_stmt(clause.stmts());
+            self.end_scope();
+        }
+
+        if let Some(stmts) = node.finally_stmts() {
+            self.visit_stmt_list_stmt(stmts);
+        }
+    }
+
+    fn visit_struct_declaration_stmt(&mut self, node: &StructDeclarationStmtNode) {
+        self.begin_type_parameters(node.type_parameters());
+
+        for field in node.fields() {
+            self.check_type_ref(field.type_ref());
+        }
+
+        self.type_parameters.pop();
+    }
+
+    fn visit_impl_stmt(&mut self, node: &ImplStmtNode) {
+        self.begin_type_parameters(node.type_parameters());
+
+        for method in node.methods() {
+            self.impl_type = Some(node.type_identifier().lexeme().to_string());
+            self.check_function(
+                method.type_parameters(),
+                method.parameters(),
+                method.return_type(),
+                method.stmts(),
+            );
+        }
+
+        self.impl_type = None;
+        self.type_parameters.pop();
+    }
+
+    fn visit_interface_declaration_stmt(&mut self, node: &InterfaceDeclarationStmtNode) {
+        for method in node.methods() {
+            self.begin_type_parameters(method.type_parameters());
+
+            for (_, annotation) in method.parameters() {
+                if let Some(annotation) = annotation {
+                    self.check_type_ref(annotation);
+                }
+            }
+
+            if let Some(return_type) = method.return_type() {
+                self.check_type_ref(return_type);
+            }
+
+            self.type_parameters.pop();
+        }
+    }
+
+    fn visit_import_stmt(&mut self, node: &ImportStmtNode) {
+        self.define(node.identifier(), Type::Unknown);
+    }
+
+    fn visit_export_stmt(&mut self, node: &ExportStmtNode) {
+        if let Some(expr) = node.expr() {
+            let value_type = self.visit_expr(expr);
+            self.define(node.identifier(), value_type);
+        }
+    }
+}
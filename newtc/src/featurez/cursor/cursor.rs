@@ -10,6 +10,7 @@ impl<'a> Cursor<'a> {
         Cursor { text, len: 0 }
     }
 
+    /// The length in bytes of the text consumed so far.
     pub fn len(&self) -> usize {
         self.len
     }
@@ -52,7 +53,7 @@ impl<'a> Iterator for Cursor<'a> {
 
     fn next(&mut self) -> Option<char> {
         let next = self.chars().next()?;
-        self.len += 1;
+        self.len += next.len_utf8();
         Some(next)
     }
 }
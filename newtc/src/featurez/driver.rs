@@ -1,20 +1,82 @@
-use crate::featurez::parse::Parser;
-use crate::featurez::runtime::VirtualMachine;
-use crate::featurez::syntax::{
-    AstNode, ExprNode, ExprVisitor, NewtResult, NewtRuntimeError, NewtStaticError, NewtValue,
-    SyntaxNode,
-};
-use crate::featurez::syntax::{StmtNode, StmtVisitor, SyntaxElement, SyntaxTree};
-use crate::featurez::tokens::{tokenize, StrTokenSource, Token, TokenKind};
-
-use crate::featurez::grammar::{root_expr, root_stmt};
-use std::collections::HashMap;
-use std::fmt::Debug;
+use crate::featurez::syntax::{ErrorReport, NewtRuntimeError, NewtStaticError};
 use std::fmt::Display;
 use std::fmt::Error;
 use std::fmt::Formatter;
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum NewtError {
-    Static(NewtStaticError),
+    Syntax(Vec<ErrorReport>),
+    Static(Vec<NewtStaticError>),
     Runtime(NewtRuntimeError),
+    // An error in an imported module, which carries the module's source to be rendered against
+    Module {
+        name: String,
+        source: String,
+        error: Box<NewtError>,
+    },
+}
+
+impl NewtError {
+    pub fn render(&self, source: &str) -> String {
+        match self {
+            NewtError::Syntax(errors) => errors
+                .iter()
+                .map(|error| error.render(source))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            NewtError::Static(errors) => errors
+                .iter()
+                .map(|error| error.render(source))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            NewtError::Runtime(error) => error.render(source),
+            NewtError::Module {
+                name,
+                source,
+                error,
+            } => format!("in module {}:\n{}", name, error.render(source)),
+        }
+    }
+
+    /// Attributes the error to the module `name`, unless it already belongs to a module
+    /// that `name` imports.
+    pub fn in_module(self, name: &str, source: &str) -> NewtError {
+        match self {
+            NewtError::Module { .. } => self,
+            error => NewtError::Module {
+                name: name.to_string(),
+                source: source.to_string(),
+                error: Box::new(error),
+            },
+        }
+    }
+}
+
+impl From<NewtRuntimeError> for NewtError {
+    fn from(error: NewtRuntimeError) -> Self {
+        NewtError::Runtime(error)
+    }
+}
+
+impl Display for NewtError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            NewtError::Syntax(errors) => {
+                for error in errors.iter() {
+                    writeln!(f, "{}", error)?;
+                }
+
+                Ok(())
+            }
+            NewtError::Static(errors) => {
+                for error in errors.iter() {
+                    writeln!(f, "{}", error)?;
+                }
+
+                Ok(())
+            }
+            NewtError::Runtime(error) => write!(f, "{}", error),
+            NewtError::Module { name, error, .. } => write!(f, "in module {}: {}", name, error),
+        }
+    }
 }
@@ -0,0 +1,27 @@
+mod printer;
+
+use self::printer::Printer;
+use crate::featurez::syntax::{ErrorReport, SyntaxKind, SyntaxTree};
+
+pub fn format_source(source: &str) -> Result<String, Vec<ErrorReport>> {
+    let tree = SyntaxTree::from_stmts(source);
+    let errors: Vec<ErrorReport> = tree.errors().cloned().collect();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(format_tree(&tree))
+}
+
+/// Formats an error free syntax tree.  Only the layout of the tree's text changes: every
+/// token and comment comes out in the order it went in, trailing commas aside.
+pub fn format_tree(tree: &SyntaxTree) -> String {
+    let mut printer = Printer::new();
+
+    printer.element(tree.root(), SyntaxKind::StmtListStmt);
+
+    printer.finish()
+}
+
+mod tests;
@@ -0,0 +1,344 @@
+use crate::featurez::syntax::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+use crate::featurez::TokenKind;
+
+const MAX_WIDTH: usize = 100;
+const TAB_WIDTH: usize = 4;
+
+/// What separates the last thing written from the next.  Asking for a break only ever widens
+/// it, so a statement ending doesn't undo the newline a comment asked for.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+enum Break {
+    None,
+    Space,
+    Newline,
+    BlankLine,
+}
+
+pub struct Printer {
+    output: String,
+    indent: usize,
+    column: usize,
+    pending: Break,
+    previous: Option<(TokenKind, SyntaxKind)>,
+    newlines: usize,
+    flat: bool,
+}
+
+impl Printer {
+    pub fn new() -> Printer {
+        Printer {
+            output: String::new(),
+            indent: 0,
+            column: 0,
+            pending: Break::None,
+            previous: None,
+            newlines: 0,
+            flat: false,
+        }
+    }
+
+    pub fn finish(mut self) -> String {
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+
+        self.output
+    }
+
+    pub fn element(&mut self, element: &SyntaxElement, parent: SyntaxKind) {
+        match element {
+            SyntaxElement::Node(node) => self.node(node),
+            SyntaxElement::Token(token) => self.token(token, parent),
+        }
+    }
+
+    fn node(&mut self, node: &SyntaxNode) {
+        match node.kind() {
+            SyntaxKind::StmtListStmt
+            | SyntaxKind::StructDeclarationStmt
+            | SyntaxKind::ImplStmt
+            | SyntaxKind::InterfaceDeclarationStmt
+            | SyntaxKind::MatchExpr => self.body(node),
+            SyntaxKind::ObjectLiteralExpr
+            | SyntaxKind::StructLiteralExpr
+            | SyntaxKind::ArrayLiteralExpr
+            | SyntaxKind::ObjectPattern
+            | SyntaxKind::StructPattern
+            | SyntaxKind::ArrayPattern => self.literal(node),
+            _ => self.children(node),
+        }
+    }
+
+    fn children(&mut self, node: &SyntaxNode) {
+        for child in node.children() {
+            self.element(child, node.kind());
+        }
+    }
+
+    fn body(&mut self, node: &SyntaxNode) {
+        let braced = has_token(node, TokenKind::LeftBrace);
+        let empty = !node
+            .children()
+            .iter()
+            .skip_while(|child| !is_token(child, TokenKind::LeftBrace))
+            .take_while(|child| !is_token(child, TokenKind::RightBrace))
+            .any(|child| child.is_node() || is_comment(child));
+        let mut in_body = !braced && node.kind() == SyntaxKind::StmtListStmt;
+
+        let children = node.children();
+
+        for (index, child) in children.iter().enumerate() {
+            match child {
+                SyntaxElement::Node(member) if in_body => {
+                    self.blank_line();
+                    self.newline();
+                    self.node(member);
+                }
+                SyntaxElement::Token(token) if token.token_kind() == TokenKind::LeftBrace => {
+                    self.token(token, node.kind());
+                    in_body = true;
+
+                    if !empty {
+                        self.indent += 1;
+                        self.newline();
+                    }
+                }
+                SyntaxElement::Token(token) if token.token_kind() == TokenKind::RightBrace => {
+                    if !empty {
+                        self.indent -= 1;
+                        self.newline();
+                    }
+
+                    self.token(token, node.kind());
+                    in_body = false;
+                }
+                SyntaxElement::Token(token)
+                    if in_body && token.token_kind() == TokenKind::Comma =>
+                {
+                    if !is_closing_next(&children[index + 1..]) {
+                        self.token(token, node.kind());
+                    }
+                }
+                _ => self.element(child, node.kind()),
+            }
+        }
+    }
+
+    fn literal(&mut self, node: &SyntaxNode) {
+        let broken = !self.flat && !self.fits(node);
+        let children = node.children();
+
+        for (index, child) in children.iter().enumerate() {
+            match child {
+                SyntaxElement::Token(token) if is_opening(token.token_kind()) => {
+                    self.token(token, node.kind());
+
+                    if broken && !is_closing_next(&children[index + 1..]) {
+                        self.indent += 1;
+                        self.newline();
+                    }
+                }
+                SyntaxElement::Token(token) if is_closing(token.token_kind()) => {
+                    if broken && self.previous.map(|(kind, _)| is_opening(kind)) != Some(true) {
+                        self.indent -= 1;
+                        self.newline();
+                    }
+
+                    self.token(token, node.kind());
+                }
+                SyntaxElement::Token(token) if token.token_kind() == TokenKind::Comma => {
+                    if is_closing_next(&children[index + 1..]) {
+                        continue;
+                    }
+
+                    self.token(token, node.kind());
+
+                    if broken {
+                        self.newline();
+                    }
+                }
+                _ => self.element(child, node.kind()),
+            }
+        }
+    }
+
+    fn fits(&self, node: &SyntaxNode) -> bool {
+        let mut flat = Printer::new();
+        flat.flat = true;
+        flat.node(node);
+
+        let start = match self.pending {
+            Break::Newline | Break::BlankLine => self.indent * TAB_WIDTH,
+            _ => self.column + 1,
+        };
+
+        !flat.output.contains('\n') && start + flat.column <= MAX_WIDTH
+    }
+
+    fn token(&mut self, token: &SyntaxToken, parent: SyntaxKind) {
+        let kind = token.token_kind();
+
+        match kind {
+            TokenKind::WhiteSpace => {
+                self.newlines += token.lexeme().matches('\n').count();
+            }
+            TokenKind::CommentLine | TokenKind::CommentBlock => self.comment(token),
+            TokenKind::EndOfFile => {}
+            _ => {
+                if let Some(previous) = self.previous {
+                    if is_spaced(previous, (kind, parent)) {
+                        self.space();
+                    }
+                }
+
+                self.write(token.lexeme());
+                self.previous = Some((kind, parent));
+            }
+        }
+    }
+
+    fn comment(&mut self, token: &SyntaxToken) {
+        let trailing = self.newlines == 0 && self.previous.is_some();
+
+        if trailing {
+            self.pending = Break::Space;
+        } else {
+            self.blank_line();
+            self.newline();
+        }
+
+        self.write(token.lexeme().trim_end());
+
+        if token.token_kind() == TokenKind::CommentLine || !trailing {
+            self.newline();
+        } else {
+            self.space();
+        }
+    }
+
+    fn space(&mut self) {
+        self.request(Break::Space);
+    }
+
+    fn newline(&mut self) {
+        self.request(Break::Newline);
+    }
+
+    fn blank_line(&mut self) {
+        let after_opening = self.previous.map(|(kind, _)| is_opening(kind)) == Some(true);
+
+        if self.newlines > 1 && !after_opening {
+            self.request(Break::BlankLine);
+        }
+    }
+
+    fn request(&mut self, separator: Break) {
+        if separator > self.pending {
+            self.pending = separator;
+        }
+    }
+
+    fn write(&mut self, text: &str) {
+        match self.pending {
+            Break::None => {}
+            Break::Space => {
+                self.output.push(' ');
+                self.column += 1;
+            }
+            Break::Newline | Break::BlankLine if self.output.is_empty() => {}
+            Break::Newline | Break::BlankLine => {
+                if self.pending == Break::BlankLine {
+                    self.output.push('\n');
+                }
+
+                self.output.push('\n');
+
+                for _ in 0..self.indent {
+                    self.output.push('\t');
+                }
+
+                self.column = self.indent * TAB_WIDTH;
+            }
+        }
+
+        self.output.push_str(text);
+
+        match text.rfind('\n') {
+            Some(line_start) => self.column = text[line_start + 1..].chars().count(),
+            None => self.column += text.chars().count(),
+        }
+
+        self.pending = Break::None;
+        self.newlines = 0;
+    }
+}
+
+fn is_spaced(previous: (TokenKind, SyntaxKind), next: (TokenKind, SyntaxKind)) -> bool {
+    match previous {
+        (TokenKind::LeftParenthesis, _) | (TokenKind::LeftBracket, _) | (TokenKind::Dot, _) => {
+            return false
+        }
+        (TokenKind::StringHead, _) | (TokenKind::StringMiddle, _) => return false,
+        (TokenKind::Minus, SyntaxKind::UnaryExpr) | (TokenKind::Bang, SyntaxKind::UnaryExpr) => {
+            return false
+        }
+        (TokenKind::LeftBrace, _) if next.0 == TokenKind::RightBrace => return false,
+        _ => {}
+    }
+
+    match next {
+        (TokenKind::RightParenthesis, _)
+        | (TokenKind::RightBracket, _)
+        | (TokenKind::Comma, _)
+        | (TokenKind::SemiColon, _)
+        | (TokenKind::Dot, _)
+        | (TokenKind::Colon, _)
+        | (TokenKind::StringMiddle, _)
+        | (TokenKind::StringTail, _) => false,
+        (TokenKind::LeftParenthesis, SyntaxKind::FunctionCallExpr)
+        | (TokenKind::LeftParenthesis, SyntaxKind::FunctionDeclarationStmt)
+        | (TokenKind::LeftParenthesis, SyntaxKind::InterfaceMethod) => false,
+        (TokenKind::LeftBracket, SyntaxKind::IndexExpr)
+        | (TokenKind::LeftBracket, SyntaxKind::IndexRVal)
+        | (TokenKind::LeftBracket, SyntaxKind::TypeRef)
+        | (TokenKind::LeftBracket, SyntaxKind::TypeParameters) => false,
+        _ => true,
+    }
+}
+
+fn is_opening(kind: TokenKind) -> bool {
+    kind == TokenKind::LeftBrace || kind == TokenKind::LeftBracket
+}
+
+fn is_closing(kind: TokenKind) -> bool {
+    kind == TokenKind::RightBrace || kind == TokenKind::RightBracket
+}
+
+fn is_closing_next(elements: &[SyntaxElement]) -> bool {
+    for element in elements {
+        match element {
+            SyntaxElement::Node(_) => return false,
+            SyntaxElement::Token(token) => match token.token_kind() {
+                TokenKind::WhiteSpace | TokenKind::CommentLine | TokenKind::CommentBlock => {}
+                kind => return is_closing(kind),
+            },
+        }
+    }
+
+    false
+}
+
+fn is_token(element: &SyntaxElement, kind: TokenKind) -> bool {
+    match element {
+        SyntaxElement::Token(token) => token.token_kind() == kind,
+        _ => false,
+    }
+}
+
+fn is_comment(element: &SyntaxElement) -> bool {
+    is_token(element, TokenKind::CommentLine) || is_token(element, TokenKind::CommentBlock)
+}
+
+fn has_token(node: &SyntaxNode, kind: TokenKind) -> bool {
+    node.children().iter().any(|child| is_token(child, kind))
+}
@@ -0,0 +1,212 @@
+#![cfg(test)]
+
+use super::*;
+
+fn format(source: &str) -> String {
+    let formatted = format_source(source).unwrap();
+
+    assert_eq!(formatted, format_source(&formatted).unwrap());
+
+    formatted
+}
+
+#[test]
+fn format_normalizes_indentation_and_brace_placement() {
+    let source = "fn add(a,b)\n{\n        return a+b;\n  }\nif (x)\n{ y = 1; }\nelse { y = 2;\n}\n";
+
+    let expected = r"fn add(a, b) {
+	return a + b;
+}
+if (x) {
+	y = 1;
+} else {
+	y = 2;
+}
+";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_lays_out_try_statements_like_if_else() {
+    let source = "try { f(); }\ncatch(e){ throw e ; }\nfinally\n{ g(); }\n";
+
+    let expected = r"try {
+	f();
+} catch (e) {
+	throw e;
+} finally {
+	g();
+}
+";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_keeps_labels_on_their_loops() {
+    let source = "outer : while x {\nfor y in ys { break outer ; continue; }\n}\n";
+
+    let expected = r"outer: while x {
+	for y in ys {
+		break outer;
+		continue;
+	}
+}
+";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_normalizes_operator_spacing() {
+    let source =
+        "let x=-a*(b+c)-!d;\nlet y : i64=items [0].length;\nfn f [T : Pet] (p : T)->List[T] {}\n";
+
+    let expected = r"let x = -a * (b + c) - !d;
+let y: i64 = items[0].length;
+fn f[T: Pet](p: T) -> List[T] {}
+";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_spaces_interpolations_like_other_expressions() {
+    let source = "let s = \"${ a+b } and ${ f( c ) }\\n\";\n";
+
+    let expected = "let s = \"${a + b} and ${f(c)}\\n\";\n";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_keeps_short_literals_on_one_line() {
+    let source =
+        "let point = Point {x: 1, y: 2,};\nlet o = {a: [1,2,3], b: {}};\nlet empty = [ ];\n";
+
+    let expected = r"let point = Point { x: 1, y: 2 };
+let o = { a: [1, 2, 3], b: {} };
+let empty = [];
+";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_breaks_long_literals_one_element_per_line() {
+    let source = "fn f() { return {first: \"a long string value\", second: \"another string value, long enough to break\", third: [1, 2, 3]}; }\n";
+
+    let expected = r#"fn f() {
+	return {
+		first: "a long string value",
+		second: "another string value, long enough to break",
+		third: [1, 2, 3]
+	};
+}
+"#;
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_puts_members_on_their_own_lines() {
+    let source = "struct Point { imm x: i64, y: i64, }\ninterface Shape { fn area() -> f64; fn name(); }\nimpl Point { fn x() { return self.x; } }\n";
+
+    let expected = r"struct Point {
+	imm x: i64,
+	y: i64
+}
+interface Shape {
+	fn area() -> f64;
+	fn name();
+}
+impl Point {
+	fn x() {
+		return self.x;
+	}
+}
+";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_preserves_comments() {
+    let source = "// header\nlet x = 1;   // trailing\n{ // opening\n  // own line\n  x = 2;\n  // closing\n}\nlet o = {a: 1, // about a\nb: 2};\n";
+
+    let expected = r"// header
+let x = 1; // trailing
+{ // opening
+	// own line
+	x = 2;
+	// closing
+}
+let o = {
+	a: 1, // about a
+	b: 2
+};
+";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_keeps_at_most_one_blank_line_between_statements() {
+    let source =
+        "\n\nlet a = 1;\n\n\n\nlet b = 2;\nfn f() {\n\n  a = 3;\n\n\n  // note\n  b = 4;\n\n}\n\n";
+
+    let expected = r"let a = 1;
+
+let b = 2;
+fn f() {
+	a = 3;
+
+	// note
+	b = 4;
+}
+";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_puts_match_arms_on_their_own_lines() {
+    let source = "let a = match p { Point {x:0,y}=>y,[first,..] if first>1=>-1,_=>0, };";
+
+    let expected = r"let a = match p {
+	Point { x: 0, y } => y,
+	[first, ..] if first > 1 => -1,
+	_ => 0
+};
+";
+
+    assert_eq!(expected, format(source));
+}
+
+#[test]
+fn format_is_idempotent() {
+    let sources = [
+        include_str!("../../../../lang/Notepad/App.newt"),
+        "import Notepad.Store;\nexport total = Store.sum([1, 2, 3]);\nfor x in xs { while x > 0 { x = x - 1; } }\n",
+        "struct Pair[T, U] { first: T, second: U }\nimpl Pair[T, U] { fn swap() -> Pair[U, T] { return Pair { first: self.second, second: self.first }; } }\n",
+        "let nested = {a: {b: {c: [{d: 1}, {e: 2}], f: \"a value long enough to push the object past the line\"}}};\n",
+    ];
+
+    for source in sources.iter() {
+        format(source);
+    }
+}
+
+#[test]
+fn format_refuses_sources_with_syntax_errors() {
+    let errors = format_source("let x = ;\n").unwrap_err();
+
+    assert_ne!(0, errors.len());
+}
+
+#[test]
+fn format_of_an_empty_source_is_empty() {
+    assert_eq!("", format(""));
+    assert_eq!("", format("\n\n  \n"));
+}
@@ -3,6 +3,8 @@ use crate::featurez::parse::{CompletedMarker, Marker};
 use crate::featurez::syntax::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, SyntaxTree};
 use crate::featurez::tokens::TokenKind::TombStone;
 use crate::featurez::{Token, TokenKind};
+
+use super::{fn_body, fn_parameters, pattern};
 use std::collections::HashMap;
 
 type RhsParseFunction = fn(&mut Parser, CompletedMarker) -> CompletedMarker;
@@ -66,14 +68,28 @@ fn get_precedence(token_kind: TokenKind) -> PrecedenceLevel {
 }
 
 const PRIMARY_PRECEDENCE: PrecedenceLevel = 1;
-const MULTIPLICATION_PRECEDENCE: PrecedenceLevel = 2;
-const ADDITION_PRECEDENCE: PrecedenceLevel = 3;
-const LOGIC_PRECEDENCE: PrecedenceLevel = 4;
+const EXPONENT_PRECEDENCE: PrecedenceLevel = 2;
+const MULTIPLICATION_PRECEDENCE: PrecedenceLevel = 3;
+const ADDITION_PRECEDENCE: PrecedenceLevel = 4;
+const SHIFT_PRECEDENCE: PrecedenceLevel = 5;
+const BITWISE_AND_PRECEDENCE: PrecedenceLevel = 6;
+const BITWISE_XOR_PRECEDENCE: PrecedenceLevel = 7;
+const BITWISE_OR_PRECEDENCE: PrecedenceLevel = 8;
+const COMPARISON_PRECEDENCE: PrecedenceLevel = 9;
+const LOGICAL_AND_PRECEDENCE: PrecedenceLevel = 10;
+const LOGICAL_OR_PRECEDENCE: PrecedenceLevel = 11;
 const NO_PRECEDENCE: PrecedenceLevel = 1000;
 
 fn build_precedence_rules_table() -> HashMap<TokenKind, PrecedenceRule> {
     vec![
         // basic math operators
+        PrecedenceRule::rule(
+            EXPONENT_PRECEDENCE,
+            TokenKind::StarStar,
+            None,
+            Some(binary_expr),
+            None,
+        ),
         PrecedenceRule::rule(
             MULTIPLICATION_PRECEDENCE,
             TokenKind::Star,
@@ -88,6 +104,13 @@ fn build_precedence_rules_table() -> HashMap<TokenKind, PrecedenceRule> {
             Some(binary_expr),
             None,
         ),
+        PrecedenceRule::rule(
+            MULTIPLICATION_PRECEDENCE,
+            TokenKind::Percent,
+            None,
+            Some(binary_expr),
+            None,
+        ),
         PrecedenceRule::rule(
             ADDITION_PRECEDENCE,
             TokenKind::Plus,
@@ -102,42 +125,100 @@ fn build_precedence_rules_table() -> HashMap<TokenKind, PrecedenceRule> {
             Some(binary_expr),
             None,
         ),
-        // logical operators
+        // bitwise operators
+        PrecedenceRule::rule(
+            SHIFT_PRECEDENCE,
+            TokenKind::LessLess,
+            None,
+            Some(binary_expr),
+            None,
+        ),
+        PrecedenceRule::rule(
+            SHIFT_PRECEDENCE,
+            TokenKind::GreaterGreater,
+            None,
+            Some(binary_expr),
+            None,
+        ),
+        PrecedenceRule::rule(
+            BITWISE_AND_PRECEDENCE,
+            TokenKind::Ampersand,
+            None,
+            Some(binary_expr),
+            None,
+        ),
+        PrecedenceRule::rule(
+            BITWISE_XOR_PRECEDENCE,
+            TokenKind::Caret,
+            None,
+            Some(binary_expr),
+            None,
+        ),
+        PrecedenceRule::rule(
+            BITWISE_OR_PRECEDENCE,
+            TokenKind::Pipe,
+            None,
+            Some(binary_expr),
+            None,
+        ),
+        // comparison operators
         PrecedenceRule::rule(
-            LOGIC_PRECEDENCE,
+            COMPARISON_PRECEDENCE,
             TokenKind::LessEquals,
             None,
             Some(binary_expr),
             None,
         ),
         PrecedenceRule::rule(
-            LOGIC_PRECEDENCE,
+            COMPARISON_PRECEDENCE,
             TokenKind::Less,
             None,
             Some(binary_expr),
             None,
         ),
         PrecedenceRule::rule(
-            LOGIC_PRECEDENCE,
+            COMPARISON_PRECEDENCE,
             TokenKind::GreaterEquals,
             None,
             Some(binary_expr),
             None,
         ),
         PrecedenceRule::rule(
-            LOGIC_PRECEDENCE,
+            COMPARISON_PRECEDENCE,
             TokenKind::Greater,
             None,
             Some(binary_expr),
             None,
         ),
         PrecedenceRule::rule(
-            LOGIC_PRECEDENCE,
+            COMPARISON_PRECEDENCE,
             TokenKind::EqualsEquals,
             None,
             Some(binary_expr),
             None,
         ),
+        PrecedenceRule::rule(
+            COMPARISON_PRECEDENCE,
+            TokenKind::BangEquals,
+            None,
+            Some(binary_expr),
+            None,
+        ),
+        // logical operators
+        PrecedenceRule::rule(
+            LOGICAL_AND_PRECEDENCE,
+            TokenKind::AmpersandAmpersand,
+            None,
+            Some(binary_expr),
+            None,
+        ),
+        PrecedenceRule::rule(
+            LOGICAL_OR_PRECEDENCE,
+            TokenKind::PipePipe,
+            None,
+            Some(binary_expr),
+            None,
+        ),
         // primaries
         PrecedenceRule::rule(
             PRIMARY_PRECEDENCE,
@@ -174,6 +255,13 @@ fn build_precedence_rules_table() -> HashMap<TokenKind, PrecedenceRule> {
             None,
             None,
         ),
+        PrecedenceRule::rule(
+            PRIMARY_PRECEDENCE,
+            TokenKind::StringHead,
+            Some(primary_expr),
+            None,
+            None,
+        ),
         PrecedenceRule::rule(
             PRIMARY_PRECEDENCE,
             TokenKind::Identifier,
@@ -195,6 +283,20 @@ fn build_precedence_rules_table() -> HashMap<TokenKind, PrecedenceRule> {
             None,
             None,
         ),
+        PrecedenceRule::rule(
+            PRIMARY_PRECEDENCE,
+            TokenKind::Fn,
+            Some(primary_expr),
+            None,
+            None,
+        ),
+        PrecedenceRule::rule(
+            PRIMARY_PRECEDENCE,
+            TokenKind::Match,
+            Some(primary_expr),
+            None,
+            None,
+        ),
         PrecedenceRule::rule(
             NO_PRECEDENCE,
             TokenKind::LeftParenthesis,
@@ -209,6 +311,13 @@ fn build_precedence_rules_table() -> HashMap<TokenKind, PrecedenceRule> {
             None,
             None,
         ),
+        PrecedenceRule::rule(
+            NO_PRECEDENCE,
+            TokenKind::LeftBracket,
+            Some(primary_expr),
+            None,
+            Some(unary_suffix),
+        ),
         PrecedenceRule::rule(
             NO_PRECEDENCE,
             TokenKind::Dot,
@@ -223,27 +332,41 @@ fn build_precedence_rules_table() -> HashMap<TokenKind, PrecedenceRule> {
 }
 
 pub fn expr(p: &mut Parser) -> CompletedMarker {
-    parse_expr(p, LOGIC_PRECEDENCE)
+    parse_expr(p, LOGICAL_OR_PRECEDENCE)
+}
+
+pub fn condition_expr(p: &mut Parser) -> CompletedMarker {
+    let allowed = p.allow_struct_literals(false);
+    let completed = expr(p);
+    p.allow_struct_literals(allowed);
+
+    completed
+}
+
+/// Parses an expression nested inside delimiters, where struct literals are unambiguous again.
+fn nested_expr(p: &mut Parser) -> CompletedMarker {
+    let allowed = p.allow_struct_literals(true);
+    let completed = expr(p);
+    p.allow_struct_literals(allowed);
+
+    completed
 }
 
 fn parse_expr(p: &mut Parser, precedence: PrecedenceLevel) -> CompletedMarker {
     let prefix_parser = if let Some(prefix_parser) = get_prefix(p.current()) {
         prefix_parser
     } else {
-        let marker = p.begin_node();
-        return p.end_node(marker, SyntaxKind::Error("Expected expression"));
+        return p.recover("Expected expression", &[]);
     };
 
     let mut lhs = prefix_parser(p);
 
-    while precedence >= get_precedence(p.current()) {
-        let binary_parser = get_binary(p.current()).expect(
-            format!(
-                "Precedence has no meaning without binary operators for {:?}",
-                p.current()
-            )
-            .as_str(),
-        );
+    // Tokens that can only begin an expression, like an identifier after a complete one, end
+    // it here and are left for the enclosing statement to report
+    while let Some(binary_parser) = get_binary(p.current()) {
+        if precedence < get_precedence(p.current()) {
+            break;
+        }
 
         lhs = binary_parser(p, lhs);
     }
@@ -256,7 +379,13 @@ fn binary_expr(p: &mut Parser, mut lhs: CompletedMarker) -> CompletedMarker {
     p.precede_node(&mut lhs, &marker);
     let operator = p.current();
     p.token(operator);
-    parse_expr(p, get_precedence(operator) - 1);
+
+    // Exponents associate to the right, so `2 ** 3 ** 2` is `2 ** (3 ** 2)`
+    if operator == TokenKind::StarStar {
+        parse_expr(p, get_precedence(operator));
+    } else {
+        parse_expr(p, get_precedence(operator) - 1);
+    }
 
     p.end_node(marker, SyntaxKind::BinaryExpr)
 }
@@ -265,7 +394,13 @@ fn unary_prefix(p: &mut Parser) -> CompletedMarker {
     let marker = p.begin_node();
     let operator = p.current();
     p.token(operator);
-    parse_expr(p, get_precedence(operator) - 1);
+
+    // Negation binds tighter than every binary operator but `**`, so `-2 ** 2` is `-(2 ** 2)`
+    if operator == TokenKind::Minus {
+        parse_expr(p, EXPONENT_PRECEDENCE);
+    } else {
+        parse_expr(p, get_precedence(operator) - 1);
+    }
 
     p.end_node(marker, SyntaxKind::UnaryExpr)
 }
@@ -274,6 +409,7 @@ fn unary_suffix(p: &mut Parser, mut marker: CompletedMarker) -> CompletedMarker
     match p.current() {
         TokenKind::Dot => object_property_expr(p, marker),
         TokenKind::LeftParenthesis => call_expr(p, marker),
+        TokenKind::LeftBracket => index_expr(p, marker),
         _ => {
             let error = p.begin_node();
             p.end_node(
@@ -284,7 +420,7 @@ fn unary_suffix(p: &mut Parser, mut marker: CompletedMarker) -> CompletedMarker
     }
 }
 
-fn literal_expr(p: &mut Parser) -> CompletedMarker {
+pub fn literal_expr(p: &mut Parser) -> CompletedMarker {
     let mut node = p.begin_node();
     p.token(p.current());
     p.end_node(node, SyntaxKind::PrimitiveLiteralExpr)
@@ -293,7 +429,7 @@ fn literal_expr(p: &mut Parser) -> CompletedMarker {
 fn grouping_expr(p: &mut Parser) -> CompletedMarker {
     let mut node = p.begin_node();
     p.token_if(TokenKind::LeftParenthesis);
-    expr(p);
+    nested_expr(p);
     p.expect_token_kind(
         TokenKind::RightParenthesis,
         "Expected ')' to close grouping",
@@ -314,11 +450,20 @@ fn primary_expr(p: &mut Parser) -> CompletedMarker {
         TokenKind::FloatLiteral => literal_expr(p),
         TokenKind::GlyphLiteral => literal_expr(p),
         TokenKind::StringLiteral => literal_expr(p),
+        TokenKind::StringHead => interpolated_string_expr(p),
         TokenKind::True => literal_expr(p),
         TokenKind::False => literal_expr(p),
         TokenKind::LeftParenthesis => grouping_expr(p),
-        TokenKind::Identifier => variable_expr(p),
+        TokenKind::Identifier => match p.current2() {
+            Some((_, TokenKind::LeftBrace)) if p.struct_literals_allowed() => {
+                struct_literal_expr(p)
+            }
+            _ => variable_expr(p),
+        },
         TokenKind::LeftBrace => object_literal_expr(p),
+        TokenKind::LeftBracket => array_literal_expr(p),
+        TokenKind::Fn => function_expr(p),
+        TokenKind::Match => match_expr(p),
         _ => {
             let mut error = p.begin_node();
             p.end_node(error, SyntaxKind::Error("Expected primary expression"))
@@ -332,6 +477,83 @@ fn primary_expr(p: &mut Parser) -> CompletedMarker {
     completed
 }
 
+fn interpolated_string_expr(p: &mut Parser) -> CompletedMarker {
+    let node = p.begin_node();
+    p.token(TokenKind::StringHead);
+    nested_expr(p);
+
+    let fragments = [TokenKind::StringMiddle, TokenKind::StringTail];
+    loop {
+        match p.current() {
+            TokenKind::StringMiddle => {
+                p.token(TokenKind::StringMiddle);
+                nested_expr(p);
+            }
+            TokenKind::StringTail => {
+                p.token(TokenKind::StringTail);
+                break;
+            }
+            _ => {
+                p.recover("Expected '}' to close interpolation", &fragments);
+
+                if !fragments.contains(&p.current()) {
+                    break;
+                }
+            }
+        }
+    }
+
+    p.end_node(node, SyntaxKind::InterpolatedStringExpr)
+}
+
+fn function_expr(p: &mut Parser) -> CompletedMarker {
+    let node = p.begin_node();
+    p.token(TokenKind::Fn);
+    fn_parameters(p);
+
+    let allowed = p.allow_struct_literals(true);
+    fn_body(p);
+    p.allow_struct_literals(allowed);
+
+    p.end_node(node, SyntaxKind::FunctionExpr)
+}
+
+fn match_expr(p: &mut Parser) -> CompletedMarker {
+    let node = p.begin_node();
+    p.token(TokenKind::Match);
+    condition_expr(p);
+
+    let allowed = p.allow_struct_literals(true);
+    p.expect_token_kind(TokenKind::LeftBrace, "Expected '{'");
+
+    while p.current() != TokenKind::RightBrace && p.current() != TokenKind::EndOfFile {
+        match_arm(p);
+
+        if !p.token_if(TokenKind::Comma) {
+            break;
+        }
+    }
+
+    p.expect_token_kind(TokenKind::RightBrace, "Expected '}'");
+    p.allow_struct_literals(allowed);
+
+    p.end_node(node, SyntaxKind::MatchExpr)
+}
+
+fn match_arm(p: &mut Parser) {
+    let node = p.begin_node();
+    pattern(p);
+
+    if p.token_if(TokenKind::If) {
+        expr(p);
+    }
+
+    p.expect_token_kind(TokenKind::FatArrow, "Expected '=>'");
+    expr(p);
+
+    p.end_node(node, SyntaxKind::MatchArm);
+}
+
 fn object_property_expr(p: &mut Parser, mut node: CompletedMarker) -> CompletedMarker {
     let mut previous = node;
     while p.current() == TokenKind::Dot {
@@ -339,7 +561,7 @@ fn object_property_expr(p: &mut Parser, mut node: CompletedMarker) -> CompletedM
         p.precede_node(&mut previous, &next);
 
         p.token(TokenKind::Dot);
-        p.token(TokenKind::Identifier);
+        p.expect_token_kind(TokenKind::Identifier, "Expected property name");
 
         previous = p.end_node(next, SyntaxKind::ObjectPropertyExpr);
     }
@@ -352,35 +574,91 @@ fn object_literal_expr(p: &mut Parser) -> CompletedMarker {
     p.token(TokenKind::LeftBrace);
 
     if p.current() == TokenKind::Identifier {
-        p.token(TokenKind::Identifier);
-        p.token(TokenKind::Colon);
-        expr(p);
+        object_literal_field(p);
     }
 
-    while p.current() == TokenKind::Comma && p.current() != TokenKind::EndOfFile {
-        p.token(TokenKind::Comma);
-        p.token(TokenKind::Identifier);
-        p.token(TokenKind::Colon);
-        expr(p);
+    while p.token_if(TokenKind::Comma) {
+        object_literal_field(p);
     }
 
-    p.token(TokenKind::RightBrace);
+    p.expect_token_kind(TokenKind::RightBrace, "Expected '}'");
 
     p.end_node(node, SyntaxKind::ObjectLiteralExpr)
 }
 
+fn object_literal_field(p: &mut Parser) {
+    p.expect_token_kind(TokenKind::Identifier, "Expected property name");
+    p.expect_token_kind(TokenKind::Colon, "Expected ':'");
+    nested_expr(p);
+}
+
+fn struct_literal_expr(p: &mut Parser) -> CompletedMarker {
+    let node = p.begin_node();
+    p.token(TokenKind::Identifier);
+    p.token(TokenKind::LeftBrace);
+
+    while p.current() != TokenKind::RightBrace && p.current() != TokenKind::EndOfFile {
+        p.expect_token_kind(TokenKind::Identifier, "Expected field name");
+        p.expect_token_kind(TokenKind::Colon, "Expected ':'");
+        nested_expr(p);
+
+        if !p.token_if(TokenKind::Comma) {
+            break;
+        }
+    }
+
+    p.expect_token_kind(TokenKind::RightBrace, "Expected '}'");
+
+    p.end_node(node, SyntaxKind::StructLiteralExpr)
+}
+
+fn array_literal_expr(p: &mut Parser) -> CompletedMarker {
+    let node = p.begin_node();
+    p.token(TokenKind::LeftBracket);
+
+    if p.current() != TokenKind::EndOfFile && p.current() != TokenKind::RightBracket {
+        nested_expr(p);
+    }
+
+    while p.current() != TokenKind::EndOfFile && p.current() != TokenKind::RightBracket {
+        if !p.expect_token_kind(TokenKind::Comma, "Expected ','") {
+            break;
+        }
+
+        nested_expr(p);
+    }
+
+    p.expect_token_kind(TokenKind::RightBracket, "Expected ']'");
+
+    p.end_node(node, SyntaxKind::ArrayLiteralExpr)
+}
+
+fn index_expr(p: &mut Parser, mut lhs: CompletedMarker) -> CompletedMarker {
+    let index_begin = p.begin_node();
+    p.precede_node(&mut lhs, &index_begin);
+
+    p.expect_token_kind(TokenKind::LeftBracket, "Expected '['");
+    nested_expr(p);
+    p.expect_token_kind(TokenKind::RightBracket, "Expected ']'");
+
+    p.end_node(index_begin, SyntaxKind::IndexExpr)
+}
+
 fn call_expr(p: &mut Parser, mut lhs: CompletedMarker) -> CompletedMarker {
     let mut call_begin = p.begin_node();
     p.precede_node(&mut lhs, &call_begin);
 
     p.expect_token_kind(TokenKind::LeftParenthesis, "Expected '('");
     if p.current() != TokenKind::EndOfFile && p.current() != TokenKind::RightParenthesis {
-        expr(p);
+        nested_expr(p);
     }
 
     while p.current() != TokenKind::EndOfFile && p.current() != TokenKind::RightParenthesis {
-        p.expect_token_kind(TokenKind::Comma, "Expected ','");
-        expr(p);
+        if !p.expect_token_kind(TokenKind::Comma, "Expected ','") {
+            break;
+        }
+
+        nested_expr(p);
     }
 
     p.expect_token_kind(TokenKind::RightParenthesis, "Expected ')'");
@@ -1,22 +1,29 @@
-use self::expr::expr;
-use self::stmt::stmt;
+use self::expr::{condition_expr, expr, literal_expr};
+use self::pattern::pattern;
+use self::stmt::{fn_body, fn_parameters, stmt};
+use self::types::{type_parameters_if, type_ref};
 
 use crate::featurez::parse::{CompletedParsing, Parser};
 use crate::featurez::syntax::SyntaxKind;
 use crate::featurez::TokenKind;
 
 mod expr;
+mod pattern;
 mod stmt;
+mod types;
 
 pub fn root_stmt(mut p: Parser) -> CompletedParsing {
     let node = p.begin_node();
 
     loop {
-        if p.current() == TokenKind::EndOfFile {
-            break;
+        match p.current() {
+            TokenKind::EndOfFile => break,
+            TokenKind::RightBrace => {
+                p.synchronize();
+                p.unexpected_token("Unexpected '}'");
+            }
+            _ => stmt(&mut p),
         }
-
-        stmt(&mut p);
     }
 
     p.end_node(node, SyntaxKind::StmtListStmt);
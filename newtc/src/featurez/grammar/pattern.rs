@@ -0,0 +1,112 @@
+use super::literal_expr;
+use crate::featurez::parse::{CompletedMarker, Parser};
+use crate::featurez::syntax::SyntaxKind;
+use crate::featurez::TokenKind;
+
+pub fn pattern(p: &mut Parser) -> CompletedMarker {
+    match p.current() {
+        TokenKind::UnderScore => {
+            let node = p.begin_node();
+            p.token(TokenKind::UnderScore);
+            p.end_node(node, SyntaxKind::WildcardPattern)
+        }
+        TokenKind::Identifier => match p.current2() {
+            Some((_, TokenKind::LeftBrace)) => struct_pattern(p),
+            _ => {
+                let node = p.begin_node();
+                p.token(TokenKind::Identifier);
+                p.end_node(node, SyntaxKind::BindingPattern)
+            }
+        },
+        TokenKind::IntegerLiteral
+        | TokenKind::FloatLiteral
+        | TokenKind::GlyphLiteral
+        | TokenKind::StringLiteral
+        | TokenKind::True
+        | TokenKind::False => {
+            let node = p.begin_node();
+            literal_expr(p);
+            p.end_node(node, SyntaxKind::LiteralPattern)
+        }
+        TokenKind::Minus => negative_literal_pattern(p),
+        TokenKind::LeftBrace => {
+            let node = p.begin_node();
+            pattern_fields(p);
+            p.end_node(node, SyntaxKind::ObjectPattern)
+        }
+        TokenKind::LeftBracket => array_pattern(p),
+        _ => p.recover("Expected pattern", &[]),
+    }
+}
+
+fn negative_literal_pattern(p: &mut Parser) -> CompletedMarker {
+    let node = p.begin_node();
+    let negation = p.begin_node();
+    p.token(TokenKind::Minus);
+
+    match p.current() {
+        TokenKind::IntegerLiteral | TokenKind::FloatLiteral => {
+            literal_expr(p);
+        }
+        _ => {
+            p.recover(
+                "Expected a number after '-'",
+                &[TokenKind::IntegerLiteral, TokenKind::FloatLiteral],
+            );
+        }
+    }
+
+    p.end_node(negation, SyntaxKind::UnaryExpr);
+    p.end_node(node, SyntaxKind::LiteralPattern)
+}
+
+fn struct_pattern(p: &mut Parser) -> CompletedMarker {
+    let node = p.begin_node();
+    p.token(TokenKind::Identifier);
+    pattern_fields(p);
+
+    p.end_node(node, SyntaxKind::StructPattern)
+}
+
+fn pattern_fields(p: &mut Parser) {
+    p.token(TokenKind::LeftBrace);
+
+    while p.current() != TokenKind::RightBrace && p.current() != TokenKind::EndOfFile {
+        let field = p.begin_node();
+        p.expect_token_kind(TokenKind::Identifier, "Expected field name");
+
+        if p.token_if(TokenKind::Colon) {
+            pattern(p);
+        }
+
+        p.end_node(field, SyntaxKind::PatternField);
+
+        if !p.token_if(TokenKind::Comma) {
+            break;
+        }
+    }
+
+    p.expect_token_kind(TokenKind::RightBrace, "Expected '}'");
+}
+
+fn array_pattern(p: &mut Parser) -> CompletedMarker {
+    let node = p.begin_node();
+    p.token(TokenKind::LeftBracket);
+
+    while p.current() != TokenKind::RightBracket && p.current() != TokenKind::EndOfFile {
+        // `..` matches any elements left over, so nothing can follow it
+        if p.token_if(TokenKind::DotDot) {
+            break;
+        }
+
+        pattern(p);
+
+        if !p.token_if(TokenKind::Comma) {
+            break;
+        }
+    }
+
+    p.expect_token_kind(TokenKind::RightBracket, "Expected ']'");
+
+    p.end_node(node, SyntaxKind::ArrayPattern)
+}
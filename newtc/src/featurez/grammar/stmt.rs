@@ -5,7 +5,7 @@ use crate::featurez::syntax::{
 };
 use crate::featurez::{Token, TokenKind};
 
-use super::expr;
+use super::{condition_expr, expr, type_parameters_if, type_ref};
 use crate::featurez::grammar::root_expr;
 
 pub fn stmt(p: &mut Parser) {
@@ -15,10 +15,26 @@ pub fn stmt(p: &mut Parser) {
         TokenKind::If,
         TokenKind::Let,
         TokenKind::Return,
+        TokenKind::Throw,
+        TokenKind::Try,
+        TokenKind::Break,
+        TokenKind::Continue,
+        TokenKind::Struct,
+        TokenKind::Impl,
+        TokenKind::Interface,
+        TokenKind::Import,
+        TokenKind::Export,
         TokenKind::While,
         TokenKind::LeftBrace,
     ];
 
+    p.synchronize();
+
+    if p.current() == TokenKind::RightParenthesis || p.current() == TokenKind::RightBracket {
+        p.unexpected_token("Unexpected closing bracket");
+        return;
+    }
+
     let node = p.begin_node();
 
     match p.current() {
@@ -26,8 +42,26 @@ pub fn stmt(p: &mut Parser) {
         TokenKind::LeftBrace => stmt_list(p, node),
         TokenKind::If => stmt_if(p, node),
         TokenKind::While => stmt_while(p, node),
-        TokenKind::Fn => stmt_fn(p, node),
+        TokenKind::For => stmt_for(p, node),
+        // An anonymous function starts an expression statement rather than declaring one
+        TokenKind::Fn if p.current2() != Some((TokenKind::Fn, TokenKind::LeftParenthesis)) => {
+            stmt_fn(p, node)
+        }
         TokenKind::Return => stmt_return(p, node),
+        TokenKind::Throw => stmt_throw(p, node),
+        TokenKind::Try => stmt_try(p, node),
+        TokenKind::Break => stmt_jump(p, node, TokenKind::Break, SyntaxKind::BreakStmt),
+        TokenKind::Continue => stmt_jump(p, node, TokenKind::Continue, SyntaxKind::ContinueStmt),
+        TokenKind::Identifier
+            if p.current2() == Some((TokenKind::Identifier, TokenKind::Colon)) =>
+        {
+            stmt_labeled_loop(p, node)
+        }
+        TokenKind::Struct => stmt_struct(p, node),
+        TokenKind::Impl => stmt_impl(p, node),
+        TokenKind::Interface => stmt_interface(p, node),
+        TokenKind::Import => stmt_import(p, node),
+        TokenKind::Export => stmt_export(p, node),
         _ => stmt_assignment_or_expr(p, node),
     }
 }
@@ -39,33 +73,256 @@ fn stmt_return(p: &mut Parser, node: Marker) {
         expr(p);
     }
 
-    p.token(TokenKind::SemiColon);
+    p.expect_token_kind(TokenKind::SemiColon, "Expected ';'");
     p.end_node(node, SyntaxKind::ReturnStmt);
 }
 
-fn stmt_fn(p: &mut Parser, node: Marker) {
-    p.token(TokenKind::Fn);
-    p.token(TokenKind::Identifier);
-    p.token(TokenKind::LeftParenthesis);
+fn stmt_throw(p: &mut Parser, node: Marker) {
+    p.token(TokenKind::Throw);
 
-    if !p.token_if(TokenKind::RightParenthesis) {
-        p.token(TokenKind::Identifier);
+    expr(p);
 
-        while !p.token_if(TokenKind::RightParenthesis) {
-            p.token(TokenKind::Comma);
-            p.token(TokenKind::Identifier);
-        }
+    p.expect_token_kind(TokenKind::SemiColon, "Expected ';'");
+    p.end_node(node, SyntaxKind::ThrowStmt);
+}
+
+fn stmt_jump(p: &mut Parser, node: Marker, keyword: TokenKind, kind: SyntaxKind) {
+    p.token(keyword);
+    p.token_if(TokenKind::Identifier);
+
+    p.expect_token_kind(TokenKind::SemiColon, "Expected ';'");
+    p.end_node(node, kind);
+}
+
+fn stmt_try(p: &mut Parser, node: Marker) {
+    p.token(TokenKind::Try);
+
+    let body = p.begin_node();
+    stmt_list(p, body);
+
+    let has_catch = p.current() == TokenKind::Catch;
+    if has_catch {
+        catch_clause(p);
     }
 
+    if p.token_if(TokenKind::Finally) {
+        let finally = p.begin_node();
+        stmt_list(p, finally);
+    } else if !has_catch {
+        p.recover(
+            "Expected 'catch' or 'finally'",
+            &[TokenKind::Catch, TokenKind::Finally],
+        );
+    }
+
+    p.end_node(node, SyntaxKind::TryStmt);
+}
+
+fn catch_clause(p: &mut Parser) {
+    let node = p.begin_node();
+
+    p.token(TokenKind::Catch);
+    p.expect_token_kind(TokenKind::LeftParenthesis, "Expected '('");
+    p.expect_token_kind(TokenKind::Identifier, "Expected identifier");
+    p.expect_token_kind(TokenKind::RightParenthesis, "Expected ')'");
+
+    let stmts = p.begin_node();
+    stmt_list(p, stmts);
+
+    p.end_node(node, SyntaxKind::CatchClause);
+}
+
+fn stmt_fn(p: &mut Parser, node: Marker) {
+    fn_signature(p);
+
     let mut stmt_list_node = p.begin_node();
     stmt_list(p, stmt_list_node);
     p.end_node(node, SyntaxKind::FunctionDeclarationStmt);
 }
 
+fn fn_signature(p: &mut Parser) {
+    p.token(TokenKind::Fn);
+    p.expect_token_kind(TokenKind::Identifier, "Expected identifier");
+    type_parameters_if(p);
+    fn_parameters(p);
+}
+
+pub fn fn_parameters(p: &mut Parser) {
+    if p.expect_token_kind(TokenKind::LeftParenthesis, "Expected '('")
+        && !p.token_if(TokenKind::RightParenthesis)
+    {
+        fn_parameter(p);
+
+        while p.token_if(TokenKind::Comma) {
+            fn_parameter(p);
+        }
+
+        p.expect_token_kind(TokenKind::RightParenthesis, "Expected ',' or ')'");
+    }
+
+    if p.token_if(TokenKind::Arrow) {
+        type_ref(p);
+    }
+}
+
+fn fn_parameter(p: &mut Parser) {
+    p.expect_token_kind(TokenKind::Identifier, "Expected parameter name");
+
+    if p.token_if(TokenKind::Colon) {
+        type_ref(p);
+    }
+}
+
+fn stmt_struct(p: &mut Parser, node: Marker) {
+    p.token(TokenKind::Struct);
+    p.expect_token_kind(TokenKind::Identifier, "Expected identifier");
+    type_parameters_if(p);
+
+    if !p.token_if(TokenKind::SemiColon) {
+        p.expect_token_kind(TokenKind::LeftBrace, "Expected '{' or ';'");
+
+        while p.current() != TokenKind::RightBrace && p.current() != TokenKind::EndOfFile {
+            struct_field(p);
+
+            if !p.token_if(TokenKind::Comma) {
+                break;
+            }
+        }
+
+        p.expect_token_kind(TokenKind::RightBrace, "Expected '}'");
+    }
+
+    p.end_node(node, SyntaxKind::StructDeclarationStmt);
+}
+
+fn struct_field(p: &mut Parser) {
+    let node = p.begin_node();
+
+    let mut modifiers = Vec::new();
+
+    // Modifiers are only keywords when a name follows, so `get: i64` is a field named `get`
+    while let (Some(modifier), Some((_, TokenKind::Identifier))) =
+        (p.current_contextual(), p.current2())
+    {
+        if modifiers.contains(&modifier) {
+            p.unexpected_token("Field modifiers cannot be repeated");
+        } else {
+            p.contextual_token(modifier);
+            modifiers.push(modifier);
+        }
+    }
+
+    p.expect_token_kind(TokenKind::Identifier, "Expected field name");
+    p.expect_token_kind(TokenKind::Colon, "Expected ':'");
+    type_ref(p);
+
+    p.end_node(node, SyntaxKind::StructField);
+}
+
+fn stmt_impl(p: &mut Parser, node: Marker) {
+    p.token(TokenKind::Impl);
+    p.expect_token_kind(TokenKind::Identifier, "Expected identifier");
+
+    if p.token_if(TokenKind::For) {
+        p.expect_token_kind(TokenKind::Identifier, "Expected identifier");
+    }
+
+    type_parameters_if(p);
+
+    p.expect_token_kind(TokenKind::LeftBrace, "Expected '{'");
+
+    while p.current() != TokenKind::RightBrace && p.current() != TokenKind::EndOfFile {
+        p.synchronize();
+
+        if p.current() == TokenKind::Fn {
+            let method = p.begin_node();
+            stmt_fn(p, method);
+        } else {
+            p.recover_to("Expected a method", &[TokenKind::Fn], is_right_brace);
+        }
+    }
+
+    p.expect_token_kind(TokenKind::RightBrace, "Expected '}'");
+    p.end_node(node, SyntaxKind::ImplStmt);
+}
+
+fn stmt_interface(p: &mut Parser, node: Marker) {
+    p.token(TokenKind::Interface);
+    p.expect_token_kind(TokenKind::Identifier, "Expected identifier");
+    p.expect_token_kind(TokenKind::LeftBrace, "Expected '{'");
+
+    while p.current() != TokenKind::RightBrace && p.current() != TokenKind::EndOfFile {
+        p.synchronize();
+
+        if p.current() == TokenKind::Fn {
+            let method = p.begin_node();
+            fn_signature(p);
+            p.expect_token_kind(TokenKind::SemiColon, "Expected ';'");
+            p.end_node(method, SyntaxKind::InterfaceMethod);
+        } else {
+            p.recover_to(
+                "Expected a method signature",
+                &[TokenKind::Fn],
+                is_right_brace,
+            );
+        }
+    }
+
+    p.expect_token_kind(TokenKind::RightBrace, "Expected '}'");
+    p.end_node(node, SyntaxKind::InterfaceDeclarationStmt);
+}
+
+fn stmt_import(p: &mut Parser, node: Marker) {
+    p.token(TokenKind::Import);
+    p.expect_token_kind(TokenKind::Identifier, "Expected a module name");
+
+    while p.token_if(TokenKind::Dot) {
+        p.expect_token_kind(TokenKind::Identifier, "Expected a module name");
+    }
+
+    p.expect_token_kind(TokenKind::SemiColon, "Expected ';'");
+    p.end_node(node, SyntaxKind::ImportStmt);
+}
+
+fn stmt_export(p: &mut Parser, node: Marker) {
+    p.token(TokenKind::Export);
+    p.expect_token_kind(TokenKind::Identifier, "Expected identifier");
+
+    if p.token_if(TokenKind::Equals) {
+        expr(p);
+    }
+
+    p.expect_token_kind(TokenKind::SemiColon, "Expected ';'");
+    p.end_node(node, SyntaxKind::ExportStmt);
+}
+
+fn stmt_labeled_loop(p: &mut Parser, node: Marker) {
+    p.token(TokenKind::Identifier);
+    p.token(TokenKind::Colon);
+
+    if p.current() != TokenKind::While && p.current() != TokenKind::For {
+        p.recover(
+            "Expected 'while' or 'for' after a label",
+            &[TokenKind::While, TokenKind::For],
+        );
+    }
+
+    match p.current() {
+        TokenKind::For => stmt_for(p, node),
+        _ => {
+            p.token_if(TokenKind::While);
+            while_loop(p, node);
+        }
+    }
+}
+
 fn stmt_while(p: &mut Parser, node: Marker) {
     p.token(TokenKind::While);
+    while_loop(p, node);
+}
 
-    expr(p);
+fn while_loop(p: &mut Parser, node: Marker) {
+    condition_expr(p);
 
     let stmts = p.begin_node();
     stmt_list(p, stmts);
@@ -73,10 +330,23 @@ fn stmt_while(p: &mut Parser, node: Marker) {
     p.end_node(node, SyntaxKind::WhileStmt);
 }
 
+fn stmt_for(p: &mut Parser, node: Marker) {
+    p.token(TokenKind::For);
+    p.expect_token_kind(TokenKind::Identifier, "Expected identifier");
+    p.expect_token_kind(TokenKind::In, "Expected 'in'");
+
+    condition_expr(p);
+
+    let stmts = p.begin_node();
+    stmt_list(p, stmts);
+
+    p.end_node(node, SyntaxKind::ForStmt);
+}
+
 fn stmt_if(p: &mut Parser, node: Marker) {
     p.token(TokenKind::If);
 
-    expr(p);
+    condition_expr(p);
 
     let truth_list = p.begin_node();
     stmt_list(p, truth_list);
@@ -89,14 +359,15 @@ fn stmt_if(p: &mut Parser, node: Marker) {
     p.end_node(node, SyntaxKind::IfStmt);
 }
 
-/**
+/*
 This function needs to parse a node which could be either an
     assignment to a variable 'x = 42;'
     an assignment to a property 'x.y = 42;'
+    an assignment to an element 'x[0] = 42;'
     an expression statement 'x.y();'
 
     We start by parsing an expression.
-    If it's a variable or property expression and the next token is an equals then
+    If it's a variable, property or index expression and the next token is an equals then
         we parse an assignment statement
     If it's any expression and followed by an equals token then
         it's a error -- assignment to a non-rval
@@ -109,11 +380,7 @@ fn stmt_assignment_or_expr(p: &mut Parser, node: Marker) {
 
     match p.current() {
         TokenKind::Equals => stmt_assignment(p, node, expr),
-        TokenKind::SemiColon => stmt_expr(p, node),
-        _ => {
-            p.expect_token_kind(TokenKind::SemiColon, "Expected ';'");
-            p.end_node(node, SyntaxKind::Error("Expected a valid statement"));
-        }
+        _ => stmt_expr(p, node),
     }
 }
 
@@ -133,8 +400,16 @@ fn stmt_expr(p: &mut Parser, node: Marker) {
     p.end_node(node, SyntaxKind::ExprStmt);
 }
 
+pub fn fn_body(p: &mut Parser) {
+    let node = p.begin_node();
+    stmt_list(p, node);
+}
+
 fn stmt_list(p: &mut Parser, node: Marker) {
-    p.expect_token_kind(TokenKind::LeftBrace, "Expected '{'");
+    if !p.expect_token_kind(TokenKind::LeftBrace, "Expected '{'") {
+        p.end_node(node, SyntaxKind::StmtListStmt);
+        return;
+    }
 
     loop {
         if p.token_if(TokenKind::RightBrace) {
@@ -155,6 +430,11 @@ fn stmt_list(p: &mut Parser, node: Marker) {
 fn stmt_let(p: &mut Parser, node: Marker) {
     p.expect_token_kind(TokenKind::Let, "Expected 'let'");
     p.expect_token_kind(TokenKind::Identifier, "Expected identifier");
+
+    if p.token_if(TokenKind::Colon) {
+        type_ref(p);
+    }
+
     p.expect_token_kind(TokenKind::Equals, "Expected equals");
 
     expr(p);
@@ -167,8 +447,15 @@ fn rval(p: &mut Parser, rval: CompletedMarker) {
     let remap_target = match rval.kind() {
         SyntaxKind::ObjectPropertyExpr => SyntaxKind::ObjectPropertyRVal,
         SyntaxKind::VariableExpr => SyntaxKind::VariableRval,
+        SyntaxKind::IndexExpr => SyntaxKind::IndexRVal,
         _ => SyntaxKind::Error("Expected an acceptable r-val"),
     };
 
     p.remap_node(&rval, remap_target);
 }
+
+/// Members of a struct, impl or interface are recovered at the next one, skipping any
+/// statements in between, or at the end of the declaration.
+fn is_right_brace(kind: TokenKind) -> bool {
+    kind == TokenKind::RightBrace
+}
@@ -0,0 +1,54 @@
+use crate::featurez::parse::Parser;
+use crate::featurez::syntax::SyntaxKind;
+use crate::featurez::TokenKind;
+
+pub fn type_ref(p: &mut Parser) {
+    let node = p.begin_node();
+
+    p.expect_token_kind(TokenKind::Identifier, "Expected a type");
+
+    if p.token_if(TokenKind::LeftBracket) {
+        type_ref(p);
+
+        while p.token_if(TokenKind::Comma) {
+            type_ref(p);
+        }
+
+        p.expect_token_kind(TokenKind::RightBracket, "Expected ']'");
+    }
+
+    p.end_node(node, SyntaxKind::TypeRef);
+}
+
+pub fn type_parameters_if(p: &mut Parser) {
+    if p.current() != TokenKind::LeftBracket {
+        return;
+    }
+
+    let node = p.begin_node();
+    p.token(TokenKind::LeftBracket);
+
+    type_parameter(p);
+    while p.token_if(TokenKind::Comma) {
+        type_parameter(p);
+    }
+
+    p.expect_token_kind(TokenKind::RightBracket, "Expected ']'");
+    p.end_node(node, SyntaxKind::TypeParameters);
+}
+
+fn type_parameter(p: &mut Parser) {
+    let node = p.begin_node();
+
+    p.expect_token_kind(TokenKind::Identifier, "Expected a type parameter");
+
+    if p.token_if(TokenKind::Colon) {
+        p.expect_token_kind(TokenKind::Identifier, "Expected an interface");
+
+        while p.token_if(TokenKind::Plus) {
+            p.expect_token_kind(TokenKind::Identifier, "Expected an interface");
+        }
+    }
+
+    p.end_node(node, SyntaxKind::TypeParameter);
+}
@@ -1,5 +1,7 @@
+mod analysis;
 mod cursor;
 mod driver;
+mod format;
 mod grammar;
 mod newtypes;
 mod parse;
@@ -7,6 +9,11 @@ mod runtime;
 mod syntax;
 mod tokens;
 
+pub use self::driver::NewtError;
+pub use self::format::{format_source, format_tree};
 pub use self::runtime::VirtualMachine;
-pub use self::syntax::SyntaxTree;
+pub use self::syntax::{
+    ErrorReport, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, SyntaxTree, TextEdit,
+    TextRange,
+};
 pub use self::tokens::{tokenize, StrTokenSource, Token, TokenKind};
@@ -4,6 +4,7 @@ use crate::featurez::syntax::{SyntaxKind, TokenSource};
 use crate::featurez::tokens::TokenKind;
 use crate::featurez::StrTokenSource;
 
+use std::collections::{HashMap, HashSet};
 use std::mem::replace;
 
 #[derive(Clone)]
@@ -13,12 +14,20 @@ pub struct Parser {
     events: Vec<ParseEvent>,
     panicking: bool,
     root_marker: Option<Marker>,
+    expectations: HashMap<usize, Vec<TokenKind>>,
+    silenced: HashSet<usize>,
+    struct_literals_allowed: bool,
 }
 
 #[derive(Debug)]
 pub struct CompletedParsing {
     pub events: Box<[ParseEvent]>,
     pub consumed_tokens: usize,
+    // The token kinds an error node was expecting, keyed by the index of its BeginNode event
+    pub expectations: HashMap<usize, Vec<TokenKind>>,
+    // The BeginNode events of error nodes that follow an error already reported in the same
+    // statement, and so aren't reported themselves
+    pub silenced: HashSet<usize>,
 }
 
 impl Parser {
@@ -29,6 +38,9 @@ impl Parser {
             events: vec![],
             panicking: false,
             root_marker: None,
+            expectations: HashMap::new(),
+            silenced: HashSet::new(),
+            struct_literals_allowed: true,
         };
 
         // begin implicit root node
@@ -55,6 +67,10 @@ impl Parser {
         Some((current0, current1.token_kind()))
     }
 
+    pub fn current_contextual(&self) -> Option<TokenKind> {
+        self.source.token(self.consumed_tokens).contextual_kind()
+    }
+
     pub fn nth(&self, n: usize) -> TokenKind {
         self.source.token(self.consumed_tokens + n).token_kind()
     }
@@ -69,6 +85,16 @@ impl Parser {
         }
     }
 
+    pub fn struct_literals_allowed(&self) -> bool {
+        self.struct_literals_allowed
+    }
+
+    /// Struct literals are disallowed where an expression is followed by a block, so that the
+    /// `{` in `if done {` opens the block.  Returns the previous setting so callers can restore it.
+    pub fn allow_struct_literals(&mut self, allowed: bool) -> bool {
+        replace(&mut self.struct_literals_allowed, allowed)
+    }
+
     pub fn token_if(&mut self, kind: TokenKind) -> bool {
         if self.current() != kind {
             return false;
@@ -86,52 +112,104 @@ impl Parser {
         return true;
     }
 
-    pub fn expect_token_kind(&mut self, kind: TokenKind, msg: &'static str) {
-        if self.token_if(kind) {
-            self.panicking = false;
-            return;
-        }
+    pub fn contextual_token(&mut self, kind: TokenKind) {
+        assert_eq!(Some(kind), self.current_contextual());
 
-        if self.panicking {
-            self.remap_token(TokenKind::TombStone);
-        } else {
-            self.panicking = true;
+        let token = self.source.token(self.consumed_tokens);
+        self.consumed_tokens += 1;
+        self.events.push(ParseEvent::Token {
+            kind,
+            length: token.lexeme_length(),
+        });
 
-            self.report_error(msg);
-        }
+        self.eat_trivia();
+    }
+
+    pub fn expect_token_kind(&mut self, kind: TokenKind, msg: &'static str) -> bool {
+        self.expect_token_kind_in(&[kind], msg)
     }
 
-    pub fn expect_token_kind_in(&mut self, kinds: &[TokenKind], msg: &'static str) {
+    pub fn expect_token_kind_in(&mut self, kinds: &[TokenKind], msg: &'static str) -> bool {
+        if !self.panicking && !kinds.contains(&self.current()) {
+            self.recover(msg, kinds);
+        }
+
         for kind in kinds {
             if self.token_if(*kind) {
                 self.panicking = false;
-                return;
+                return true;
             }
         }
 
+        false
+    }
+
+    /// While the parser is recovering from an earlier error in the same statement, the error
+    /// node is always empty and isn't reported.
+    pub fn recover(&mut self, message: &'static str, expected: &[TokenKind]) -> CompletedMarker {
+        self.recover_to(message, expected, is_recovery_token)
+    }
+
+    pub fn recover_to(
+        &mut self,
+        message: &'static str,
+        expected: &[TokenKind],
+        recovery: fn(TokenKind) -> bool,
+    ) -> CompletedMarker {
+        let error = self.begin_node();
+
         if self.panicking {
-            self.remap_token(TokenKind::TombStone);
-        } else {
-            self.panicking = true;
+            self.silenced.insert(error.index());
+            return self.end_node(error, SyntaxKind::Error(message));
+        }
+
+        self.panicking = true;
+        self.expectations.insert(error.index(), expected.to_vec());
+
+        let mut depth = 0;
+
+        loop {
+            let current = self.current();
 
-            self.report_error(msg);
+            if current == TokenKind::EndOfFile
+                || depth == 0 && (expected.contains(&current) || recovery(current))
+            {
+                break;
+            }
+
+            match current {
+                TokenKind::LeftBrace | TokenKind::LeftParenthesis | TokenKind::LeftBracket => {
+                    depth += 1
+                }
+                TokenKind::RightBrace | TokenKind::RightParenthesis | TokenKind::RightBracket
+                    if depth > 0 =>
+                {
+                    depth -= 1
+                }
+                _ => {}
+            }
+
+            self.token_if(current);
         }
+
+        self.end_node(error, SyntaxKind::Error(message))
     }
 
-    fn report_error(&mut self, message: &'static str) {
-        let mut error = self.begin_node();
+    pub fn unexpected_token(&mut self, message: &'static str) -> CompletedMarker {
+        let error = self.begin_node();
 
-        let token = self.source.token(self.consumed_tokens);
+        if self.panicking {
+            self.silenced.insert(error.index());
+        }
 
-        self.consumed_tokens += 1;
-        self.events.push(ParseEvent::Token {
-            kind: token.token_kind(),
-            length: token.lexeme_length(),
-        });
+        self.panicking = true;
+        self.token_if(self.current());
 
-        self.eat_trivia();
+        self.end_node(error, SyntaxKind::Error(message))
+    }
 
-        self.end_node(error, SyntaxKind::Error(message));
+    pub fn synchronize(&mut self) {
+        self.panicking = false;
     }
 
     pub fn begin_node(&mut self) -> Marker {
@@ -219,21 +297,11 @@ impl Parser {
         CompletedParsing {
             events: self.events.into_boxed_slice(),
             consumed_tokens: self.consumed_tokens,
+            expectations: self.expectations,
+            silenced: self.silenced,
         }
     }
 
-    fn remap_token(&mut self, kind: TokenKind) {
-        let token = self.source.token(self.consumed_tokens);
-
-        self.consumed_tokens += 1;
-        self.events.push(ParseEvent::Token {
-            kind,
-            length: token.lexeme_length(),
-        });
-
-        self.eat_trivia();
-    }
-
     fn eat_trivia(&mut self) {
         loop {
             match self.current() {
@@ -282,3 +350,31 @@ impl Parser {
         self.events.push(ParseEvent::EndNode); // close the root element
     }
 }
+
+/// Tokens an error isn't skipped past: those that begin or end a statement, and closing braces
+/// and brackets an enclosing block or expression is waiting on.
+fn is_recovery_token(kind: TokenKind) -> bool {
+    match kind {
+        TokenKind::SemiColon
+        | TokenKind::RightBrace
+        | TokenKind::RightParenthesis
+        | TokenKind::RightBracket
+        | TokenKind::Fn
+        | TokenKind::Let
+        | TokenKind::If
+        | TokenKind::While
+        | TokenKind::For
+        | TokenKind::Return
+        | TokenKind::Throw
+        | TokenKind::Try
+        | TokenKind::Break
+        | TokenKind::Continue
+        | TokenKind::Struct
+        | TokenKind::Impl
+        | TokenKind::Interface
+        | TokenKind::Import
+        | TokenKind::Export
+        | TokenKind::EndOfFile => true,
+        _ => false,
+    }
+}
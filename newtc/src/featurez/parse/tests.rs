@@ -150,63 +150,68 @@ fn parser_expect_token_kind_produces_error_syntax_node_on_mismatch() {
 }
 
 #[test]
-fn parser_expect_token_kind_produces_tombstone_tokens_while_panicking() {
-    let token_source = StrTokenSource::new(tokenize("+-/"));
+fn parser_expect_token_kind_skips_to_the_expected_token() {
+    let token_source = StrTokenSource::new(tokenize("+*-"));
     let mut parser = Parser::new(token_source);
 
-    parser.expect_token_kind(TokenKind::Minus, "Expected '-'");
-    parser.expect_token_kind(
-        TokenKind::Plus,
-        "This shouldn't error, but rather should be a tombstone token",
-    );
-    parser.expect_token_kind(TokenKind::Slash, "This should actually succeed");
+    assert!(parser.expect_token_kind(TokenKind::Minus, "Expected '-'"));
 
     let events = parser.end_parsing().events;
     /*
-        (error (Plus '+'))
-        (TombStone '-')
-        (Slash '/')
+        (error (Plus '+') (Star '*'))
+        (Minus '-')
     */
 
-    let expected_tombstone_token = &events[3];
-
     assert_eq!(
-        expected_tombstone_token,
+        &events[2],
+        &ParseEvent::Token {
+            kind: TokenKind::Star,
+            length: 1
+        }
+    );
+    assert_eq!(&events[3], &ParseEvent::EndNode);
+    assert_eq!(
+        &events[4],
         &ParseEvent::Token {
-            kind: TokenKind::TombStone,
+            kind: TokenKind::Minus,
             length: 1
         }
     );
 }
 
 #[test]
-fn parser_expect_token_kind_recovers_from_panicking_if_expectation_is_met() {
-    let token_source = StrTokenSource::new(tokenize("+-/+"));
+fn parser_expect_token_kind_stops_skipping_at_statement_boundaries() {
+    let token_source = StrTokenSource::new(tokenize("+ let"));
+    let mut parser = Parser::new(token_source);
+
+    assert!(!parser.expect_token_kind(TokenKind::Minus, "Expected '-'"));
+    assert_eq!(TokenKind::Let, parser.current());
+}
+
+#[test]
+fn parser_expect_token_kind_reports_one_error_until_an_expectation_is_met() {
+    let token_source = StrTokenSource::new(tokenize("+;"));
     let mut parser = Parser::new(token_source);
 
     parser.expect_token_kind(TokenKind::Minus, "Expected '-'");
-    parser.expect_token_kind(
-        TokenKind::Plus,
-        "This shouldn't error, but rather should be a tombstone token",
-    );
-    parser.expect_token_kind(TokenKind::Slash, "This should actually succeed");
+    parser.expect_token_kind(TokenKind::Plus, "This shouldn't be reported");
+    parser.expect_token_kind(TokenKind::SemiColon, "This should actually succeed");
     parser.expect_token_kind(TokenKind::Minus, "Second error");
 
     let events = parser.end_parsing().events;
     /*
         (error (Plus '+'))
-        (TombStone '-')
-        (Slash '/')
-        (error (Plus '+'))
+        (SemiColon ';')
+        (error)
     */
 
-    let expected_slash_token = &events[4];
-    let expected_second_error_node = &events[5];
+    let expected_semicolon_token = &events[3];
+    let expected_second_error_node = &events[4];
 
     assert_eq!(
-        expected_slash_token,
+        expected_semicolon_token,
         &ParseEvent::Token {
-            kind: TokenKind::Slash,
+            kind: TokenKind::SemiColon,
             length: 1
         }
     );
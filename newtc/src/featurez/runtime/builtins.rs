@@ -0,0 +1,70 @@
+use crate::featurez::runtime::VirtualMachine;
+use crate::featurez::syntax::{NewtArray, NewtRuntimeError, NewtString, NewtValue};
+
+pub fn define_builtins(vm: &mut VirtualMachine) {
+    vm.define_native("print", 1, |_, arguments| {
+        println!("{}", arguments[0]);
+        Ok(NewtValue::Null)
+    });
+
+    vm.define_native("str", 1, |_, arguments| {
+        Ok(NewtValue::String(NewtString::new(
+            &arguments[0].to_string(),
+        )))
+    });
+
+    vm.define_native("len", 1, |_, arguments| {
+        let length = match &arguments[0] {
+            NewtValue::Array(array) => array.len(),
+            NewtValue::String(string) => string.len(),
+            NewtValue::Object(object) => object.keys().len(),
+            value => return Err(argument_type_error("len", value)),
+        };
+
+        Ok(NewtValue::Int(length as i64))
+    });
+
+    vm.define_native("range", 1, |_, arguments| match arguments[0] {
+        NewtValue::Int(end) => Ok(NewtValue::Range(0, end)),
+        ref value => Err(argument_type_error("range", value)),
+    });
+
+    vm.define_native("keys", 1, |_, arguments| match &arguments[0] {
+        NewtValue::Object(object) => {
+            let keys: Vec<NewtValue> = object
+                .keys()
+                .iter()
+                .map(|key| NewtValue::String(NewtString::new(key)))
+                .collect();
+
+            Ok(NewtArray::from(keys).into())
+        }
+        value => Err(argument_type_error("keys", value)),
+    });
+
+    vm.define_native("values", 1, |_, arguments| match &arguments[0] {
+        NewtValue::Object(object) => {
+            let values: Vec<NewtValue> = object
+                .keys()
+                .iter()
+                .filter_map(|key| object.get(key))
+                .collect();
+
+            Ok(NewtArray::from(values).into())
+        }
+        value => Err(argument_type_error("values", value)),
+    });
+
+    vm.define_native("implements", 2, |_, arguments| match &arguments[1] {
+        NewtValue::Interface(interface) => Ok(NewtValue::Bool(match &arguments[0] {
+            NewtValue::Struct(instance) => instance.struct_type().implements(interface),
+            NewtValue::StructType(struct_type) => struct_type.implements(interface),
+            _ => false,
+        })),
+        value => Err(argument_type_error("implements", value)),
+    });
+}
+
+fn argument_type_error(symbol: &str, value: &NewtValue) -> NewtRuntimeError {
+    NewtRuntimeError::type_error(&format!("{} does not accept {}", symbol, value.type_name()))
+}
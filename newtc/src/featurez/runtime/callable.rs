@@ -1,11 +1,9 @@
-use crate::featurez::runtime::scope::{Environment, ScopeNode};
-use crate::featurez::syntax::{
-    FunctionCallExprNode, FunctionDeclarationStmtNode, NewtRuntimeError, NewtValue, StmtVisitor,
-};
+use crate::featurez::runtime::chunk::Function;
+use crate::featurez::syntax::{NewtModule, NewtRuntimeError, NewtValue};
 use crate::featurez::VirtualMachine;
-use std::collections::HashMap;
-use std::convert::TryFrom;
+use std::cell::RefCell;
 use std::fmt::{Debug, Error, Formatter};
+use std::rc::Rc;
 
 pub trait Callable {
     fn symbol(&self) -> &str;
@@ -23,27 +21,45 @@ impl Debug for dyn Callable {
     }
 }
 
-pub struct NewtCallable {
-    definition: FunctionDeclarationStmtNode,
-    closure: Environment,
+pub struct Closure {
+    function: Rc<Function>,
+    upvalues: Vec<Rc<RefCell<NewtValue>>>,
+    module: Rc<NewtModule>,
 }
 
-impl NewtCallable {
-    pub fn new(node: &FunctionDeclarationStmtNode, closure: &Environment) -> NewtCallable {
-        NewtCallable {
-            definition: node.clone(),
-            closure: closure.clone(),
+impl Closure {
+    pub fn new(
+        function: Rc<Function>,
+        upvalues: Vec<Rc<RefCell<NewtValue>>>,
+        module: Rc<NewtModule>,
+    ) -> Closure {
+        Closure {
+            function,
+            upvalues,
+            module,
         }
     }
+
+    pub fn function(&self) -> &Function {
+        &self.function
+    }
+
+    pub fn module(&self) -> &Rc<NewtModule> {
+        &self.module
+    }
+
+    pub fn upvalue(&self, index: usize) -> &Rc<RefCell<NewtValue>> {
+        &self.upvalues[index]
+    }
 }
 
-impl Callable for NewtCallable {
+impl Callable for Closure {
     fn symbol(&self) -> &str {
-        self.definition.identifier().lexeme()
+        &self.function.name
     }
 
     fn arity(&self) -> usize {
-        self.definition.arguments().count()
+        self.function.arity
     }
 
     fn call(
@@ -51,21 +67,79 @@ impl Callable for NewtCallable {
         vm: &mut VirtualMachine,
         arguments: &[NewtValue],
     ) -> Result<NewtValue, NewtRuntimeError> {
-        let mut environment = self.closure.clone();
-        environment.push_scope();
+        vm.call_closure(self, arguments)
+    }
+}
 
-        for parameter in self.definition.arguments().enumerate() {
-            environment.bind(parameter.1.lexeme(), arguments[parameter.0].clone());
-        }
+pub struct BoundMethod {
+    receiver: NewtValue,
+    method: Rc<dyn Callable>,
+}
+
+impl BoundMethod {
+    pub fn new(receiver: NewtValue, method: Rc<dyn Callable>) -> BoundMethod {
+        BoundMethod { receiver, method }
+    }
+}
+
+impl Callable for BoundMethod {
+    fn symbol(&self) -> &str {
+        self.method.symbol()
+    }
+
+    fn arity(&self) -> usize {
+        self.method.arity() - 1
+    }
+
+    fn call(
+        &self,
+        vm: &mut VirtualMachine,
+        arguments: &[NewtValue],
+    ) -> Result<NewtValue, NewtRuntimeError> {
+        let mut bound = Vec::with_capacity(arguments.len() + 1);
+        bound.push(self.receiver.clone());
+        bound.extend_from_slice(arguments);
+
+        self.method.call(vm, &bound)
+    }
+}
 
-        let mut next_vm = VirtualMachine::new_with_scope(&environment);
-        let result = next_vm.visit_stmt_list_stmt(self.definition.stmts());
-        environment.pop_scope();
+type NativeFunction =
+    dyn Fn(&mut VirtualMachine, &[NewtValue]) -> Result<NewtValue, NewtRuntimeError>;
 
-        match result {
-            Ok(value) => Ok(NewtValue::Null),
-            Err(NewtRuntimeError::ReturnedValue(value)) => Ok(value),
-            Err(error) => Err(error),
+pub struct NativeCallable {
+    symbol: String,
+    arity: usize,
+    function: Box<NativeFunction>,
+}
+
+impl NativeCallable {
+    pub fn new<F>(symbol: &str, arity: usize, function: F) -> NativeCallable
+    where
+        F: Fn(&mut VirtualMachine, &[NewtValue]) -> Result<NewtValue, NewtRuntimeError> + 'static,
+    {
+        NativeCallable {
+            symbol: symbol.to_string(),
+            arity,
+            function: Box::new(function),
         }
     }
 }
+
+impl Callable for NativeCallable {
+    fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    fn arity(&self) -> usize {
+        self.arity
+    }
+
+    fn call(
+        &self,
+        vm: &mut VirtualMachine,
+        arguments: &[NewtValue],
+    ) -> Result<NewtValue, NewtRuntimeError> {
+        (self.function)(vm, arguments)
+    }
+}
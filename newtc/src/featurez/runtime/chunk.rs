@@ -0,0 +1,156 @@
+use crate::featurez::syntax::{NewtValue, TextRange};
+use std::rc::Rc;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Instruction {
+    Constant(usize),
+    Null,
+    Pop,
+    PopN(usize),
+
+    GetLocal(usize),
+    SetLocal(usize),
+    DefineCell(usize),
+    GetCell(usize),
+    SetCell(usize),
+    GetUpvalue(usize),
+    SetUpvalue(usize),
+    DefineGlobal(usize),
+    GetGlobal(usize),
+    SetGlobal(usize),
+    Import(usize),
+
+    GetProperty(usize),
+    SetProperty(usize),
+    GetIndex,
+    SetIndex,
+    Array(usize),
+    Object(usize),
+    Struct(usize),
+    Interpolate(usize),
+    DefineMethod { name: usize, is_static: bool },
+    // Pops an interface and the methods an impl provides, described as one, and checks that
+    // the type under them can implement the interface before the impl defines its methods.
+    Implement,
+
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+    Negate,
+    Not,
+    Equal,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+
+    Jump(usize),
+    JumpIfFalse(usize),
+    PushIterator,
+    IterateNext(usize),
+    PopIterator,
+
+    // Pattern tests pop the value being matched and push whether it matches, except that
+    // `MatchStruct` pops the struct type to match against first.  `NoMatch` raises the value
+    // no arm of a match matched as an error.
+    MatchArray { length: usize, rest: bool },
+    HasProperty(usize),
+    MatchStruct,
+    NoMatch,
+
+    // Handlers catch errors raised while they are the innermost, jumping to their target with
+    // the stack as it was when they were pushed.  A catch handler pushes the caught value, and
+    // a finally handler holds on to the error until `Rethrow` raises it again.
+    PushCatch(usize),
+    PushFinally(usize),
+    PopHandler,
+    Throw,
+    Rethrow,
+
+    Closure(usize),
+    Call(usize),
+    Return,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Capture {
+    Cell(usize),
+    Upvalue(usize),
+}
+
+#[derive(Debug, Default)]
+pub struct Chunk {
+    pub code: Vec<Instruction>,
+    pub spans: Vec<TextRange>,
+    // The 1-based source line each instruction's span starts on
+    pub lines: Vec<usize>,
+    pub constants: Vec<NewtValue>,
+    pub names: Vec<String>,
+    pub functions: Vec<Rc<Function>>,
+}
+
+#[derive(Debug)]
+pub struct Function {
+    pub name: String,
+    // The struct whose impl the function was declared in, whose private fields it may access
+    pub owner: Option<String>,
+    pub arity: usize,
+    pub cell_count: usize,
+    pub captures: Vec<Capture>,
+    pub chunk: Chunk,
+}
+
+impl Chunk {
+    pub fn new() -> Chunk {
+        Chunk::default()
+    }
+
+    pub fn emit(&mut self, instruction: Instruction, span: TextRange, line: usize) -> usize {
+        self.code.push(instruction);
+        self.spans.push(span);
+        self.lines.push(line);
+
+        self.code.len() - 1
+    }
+
+    pub fn patch_jump(&mut self, index: usize) {
+        let target = self.code.len();
+
+        self.code[index] = match self.code[index] {
+            Instruction::Jump(_) => Instruction::Jump(target),
+            Instruction::JumpIfFalse(_) => Instruction::JumpIfFalse(target),
+            Instruction::IterateNext(_) => Instruction::IterateNext(target),
+            Instruction::PushCatch(_) => Instruction::PushCatch(target),
+            Instruction::PushFinally(_) => Instruction::PushFinally(target),
+            instruction => panic!("{:?} is not a jump", instruction),
+        };
+    }
+
+    pub fn add_constant(&mut self, value: NewtValue) -> usize {
+        self.constants.push(value);
+        self.constants.len() - 1
+    }
+
+    pub fn add_name(&mut self, name: &str) -> usize {
+        match self.names.iter().position(|n| n == name) {
+            Some(index) => index,
+            None => {
+                self.names.push(name.to_string());
+                self.names.len() - 1
+            }
+        }
+    }
+
+    pub fn add_function(&mut self, function: Function) -> usize {
+        self.functions.push(Rc::new(function));
+        self.functions.len() - 1
+    }
+}
@@ -0,0 +1,1152 @@
+use crate::featurez::analysis::Resolution;
+use crate::featurez::newtypes::TransparentNewType;
+use crate::featurez::runtime::chunk::{Capture, Chunk, Function, Instruction};
+use crate::featurez::syntax::*;
+use crate::featurez::TokenKind;
+use std::mem;
+use std::rc::Rc;
+
+#[derive(Copy, Clone)]
+enum Storage {
+    Slot(usize),
+    Cell(usize),
+}
+
+enum Access {
+    Local(Storage),
+    Upvalue(usize),
+    Global(usize),
+}
+
+struct Local {
+    name: String,
+    storage: Storage,
+}
+
+/// The `finally` block of a try statement being compiled, which every `return`, `break` or
+/// `continue` leaving the try runs on its way out.
+#[derive(Clone)]
+struct FinallyBlock {
+    stmts: StmtListStmtNode,
+    // The scopes, handlers and loops there were outside the try statement
+    scopes: usize,
+    handlers: usize,
+    loops: usize,
+}
+
+struct Loop {
+    label: Option<String>,
+    start: usize,
+    // The jumps out of the loop, patched to its end once that is compiled
+    breaks: Vec<usize>,
+    // The stack slots, iterators, handlers and finally blocks there were at its start
+    slots: usize,
+    iterators: usize,
+    handlers: usize,
+    finally_blocks: usize,
+}
+
+#[derive(Copy, Clone)]
+enum PatternStep {
+    Property(usize),
+    Index(usize),
+}
+
+struct FunctionState {
+    name: String,
+    owner: Option<String>,
+    arity: usize,
+    chunk: Chunk,
+    scopes: Vec<Vec<Local>>,
+    slot_count: usize,
+    cell_count: usize,
+    captures: Vec<Capture>,
+    // The (function, cell) pair each capture ultimately refers to, so that a variable used
+    // several times is only captured once.
+    captured_cells: Vec<(usize, usize)>,
+    // How many error handlers are active at the code being compiled
+    handlers: usize,
+    finally_blocks: Vec<FinallyBlock>,
+    loops: Vec<Loop>,
+    // How many for loops' iterators are active at the code being compiled
+    iterators: usize,
+}
+
+impl FunctionState {
+    fn new(name: &str, owner: Option<String>, arity: usize) -> FunctionState {
+        FunctionState {
+            name: name.to_string(),
+            owner,
+            arity,
+            chunk: Chunk::new(),
+            scopes: Vec::new(),
+            slot_count: arity,
+            cell_count: 0,
+            captures: Vec::new(),
+            captured_cells: Vec::new(),
+            handlers: 0,
+            finally_blocks: Vec::new(),
+            loops: Vec::new(),
+            iterators: 0,
+        }
+    }
+
+    fn into_function(self) -> Function {
+        Function {
+            name: self.name,
+            owner: self.owner,
+            arity: self.arity,
+            cell_count: self.cell_count,
+            captures: self.captures,
+            chunk: self.chunk,
+        }
+    }
+}
+
+/// Locals live in stack slots unless a nested function captures them, and then in cells.
+pub struct Compiler<'a> {
+    resolution: &'a Resolution,
+    functions: Vec<FunctionState>,
+    span: TextRange,
+    // The offset each line of the source starts at
+    line_starts: Vec<usize>,
+}
+
+impl<'a> Compiler<'a> {
+    pub fn compile(node: &SyntaxNode, resolution: &Resolution) -> Function {
+        let mut compiler = Compiler {
+            resolution,
+            functions: vec![FunctionState::new("<script>", None, 0)],
+            span: node.range(),
+            line_starts: line_starts(&node.text()),
+        };
+
+        if let Some(expr) = ExprNode::cast(node) {
+            compiler.visit_expr(expr);
+        } else if let Some(stmt) = StmtNode::cast(node) {
+            compiler.visit_stmt(stmt);
+            compiler.emit(Instruction::Null);
+        }
+        compiler.emit(Instruction::Return);
+
+        compiler
+            .functions
+            .pop()
+            .expect("The script function is always present")
+            .into_function()
+    }
+
+    fn current(&mut self) -> &mut FunctionState {
+        self.functions
+            .last_mut()
+            .expect("The script function is always present")
+    }
+
+    fn chunk(&mut self) -> &mut Chunk {
+        &mut self.current().chunk
+    }
+
+    fn emit(&mut self, instruction: Instruction) -> usize {
+        let span = self.span;
+        let line = self
+            .line_starts
+            .partition_point(|start| *start <= span.start());
+
+        self.chunk().emit(instruction, span, line)
+    }
+
+    fn push_handler(&mut self, instruction: Instruction) -> usize {
+        self.current().handlers += 1;
+        self.emit(instruction)
+    }
+
+    fn pop_handler(&mut self) {
+        self.current().handlers -= 1;
+        self.emit(Instruction::PopHandler);
+    }
+
+    /// Runs the `finally` blocks of the try statements a jump leaves, innermost first, keeping
+    /// the first `finally_blocks` of them, and pops the handlers it leaves down to `handlers`.
+    fn leave_try_stmts(&mut self, finally_blocks: usize, handlers: usize) {
+        let blocks = self.current().finally_blocks.clone();
+        let enclosing_handlers = self.current().handlers;
+
+        for (index, block) in blocks.iter().enumerate().skip(finally_blocks).rev() {
+            while self.current().handlers > block.handlers {
+                self.pop_handler();
+            }
+
+            // The block sees only the variables and loops declared outside its try statement,
+            // though the variables declared inside stay on the stack until the jump.
+            let hidden_scopes = self.current().scopes.split_off(block.scopes);
+            let hidden_loops = self.current().loops.split_off(block.loops);
+            self.current().finally_blocks = blocks[..index].to_vec();
+            self.visit_stmt_list_stmt(&block.stmts);
+            self.current().scopes.extend(hidden_scopes);
+            self.current().loops.extend(hidden_loops);
+        }
+
+        while self.current().handlers > handlers {
+            self.pop_handler();
+        }
+
+        self.current().finally_blocks = blocks;
+        self.current().handlers = enclosing_handlers;
+    }
+
+    fn begin_loop(&mut self, label: Option<&SyntaxToken>, start: usize) {
+        let function = self.current();
+        let state = Loop {
+            label: label.map(|label| label.lexeme().to_string()),
+            start,
+            breaks: Vec::new(),
+            slots: function.slot_count,
+            iterators: function.iterators,
+            handlers: function.handlers,
+            finally_blocks: function.finally_blocks.len(),
+        };
+
+        function.loops.push(state);
+    }
+
+    fn end_loop(&mut self) {
+        let state = self.current().loops.pop().expect("Unbalanced loops");
+
+        for jump in state.breaks {
+            self.chunk().patch_jump(jump);
+        }
+    }
+
+    fn leave_loop_body(&mut self, label: Option<&SyntaxToken>) -> usize {
+        let index = self
+            .current()
+            .loops
+            .iter()
+            .rposition(|state| match label {
+                Some(label) => state.label.as_deref() == Some(label.lexeme()),
+                None => true,
+            })
+            .expect("The resolver only allows jumps to enclosing loops");
+        let (finally_blocks, handlers) = {
+            let state = &self.current().loops[index];
+            (state.finally_blocks, state.handlers)
+        };
+
+        self.leave_try_stmts(finally_blocks, handlers);
+
+        let function = self.current();
+        let slots = function.slot_count - function.loops[index].slots;
+        let iterators = function.iterators - function.loops[index].iterators;
+
+        if slots > 0 {
+            self.emit(Instruction::PopN(slots));
+        }
+
+        for _ in 0..iterators {
+            self.emit(Instruction::PopIterator);
+        }
+
+        index
+    }
+
+    fn emit_constant(&mut self, value: NewtValue) {
+        let index = self.chunk().add_constant(value);
+        self.emit(Instruction::Constant(index));
+    }
+
+    fn compile_and(&mut self, node: &BinaryExprNode) {
+        self.visit_expr(node.lhs());
+        let lhs_false = self.emit(Instruction::JumpIfFalse(0));
+
+        self.visit_expr(node.rhs());
+        let rhs_false = self.emit(Instruction::JumpIfFalse(0));
+
+        self.emit_constant(NewtValue::Bool(true));
+        let end = self.emit(Instruction::Jump(0));
+
+        self.chunk().patch_jump(lhs_false);
+        self.chunk().patch_jump(rhs_false);
+        self.emit_constant(NewtValue::Bool(false));
+        self.chunk().patch_jump(end);
+    }
+
+    fn compile_or(&mut self, node: &BinaryExprNode) {
+        self.visit_expr(node.lhs());
+        let lhs_false = self.emit(Instruction::JumpIfFalse(0));
+
+        self.emit_constant(NewtValue::Bool(true));
+        let lhs_true = self.emit(Instruction::Jump(0));
+
+        self.chunk().patch_jump(lhs_false);
+        self.visit_expr(node.rhs());
+        let rhs_false = self.emit(Instruction::JumpIfFalse(0));
+
+        self.emit_constant(NewtValue::Bool(true));
+        let rhs_true = self.emit(Instruction::Jump(0));
+
+        self.chunk().patch_jump(rhs_false);
+        self.emit_constant(NewtValue::Bool(false));
+        self.chunk().patch_jump(lhs_true);
+        self.chunk().patch_jump(rhs_true);
+    }
+
+    fn name(&mut self, name: &str) -> usize {
+        self.chunk().add_name(name)
+    }
+
+    fn begin_scope(&mut self) {
+        self.current().scopes.push(Vec::new());
+    }
+
+    fn end_scope(&mut self) {
+        let scope = self.current().scopes.pop().expect("Unbalanced scopes");
+        let slots = scope
+            .iter()
+            .filter(|local| match local.storage {
+                Storage::Slot(_) => true,
+                Storage::Cell(_) => false,
+            })
+            .count();
+
+        if slots > 0 {
+            self.current().slot_count -= slots;
+            self.emit(Instruction::PopN(slots));
+        }
+    }
+
+    fn is_global_scope(&mut self) -> bool {
+        self.functions.len() == 1 && self.current().scopes.is_empty()
+    }
+
+    fn add_local(&mut self, identifier: &SyntaxToken, storage: Storage) {
+        self.current()
+            .scopes
+            .last_mut()
+            .expect("Locals are declared inside a scope")
+            .push(Local {
+                name: identifier.lexeme().to_string(),
+                storage,
+            });
+    }
+
+    fn new_cell(&mut self) -> usize {
+        let function = self.current();
+        function.cell_count += 1;
+
+        function.cell_count - 1
+    }
+
+    fn declare(&mut self, identifier: &SyntaxToken) {
+        if self.is_global_scope() {
+            let name = self.name(identifier.lexeme());
+            self.emit(Instruction::DefineGlobal(name));
+        } else if self.resolution.is_captured(identifier) {
+            let cell = self.new_cell();
+            self.emit(Instruction::DefineCell(cell));
+            self.add_local(identifier, Storage::Cell(cell));
+        } else {
+            let slot = self.current().slot_count;
+            self.current().slot_count += 1;
+            self.add_local(identifier, Storage::Slot(slot));
+        }
+    }
+
+    fn access(&mut self, identifier: &SyntaxToken) -> Access {
+        let depth = match self.resolution.depth(identifier) {
+            Some(depth) => depth,
+            None => return Access::Global(self.name(identifier.lexeme())),
+        };
+
+        let (function, storage) = self.locate(identifier.lexeme(), depth);
+        let current = self.functions.len() - 1;
+
+        match storage {
+            _ if function == current => Access::Local(storage),
+            Storage::Cell(cell) => Access::Upvalue(self.capture(current, function, cell)),
+            Storage::Slot(_) => unreachable!("The resolver marks locals used by nested functions"),
+        }
+    }
+
+    fn locate(&self, name: &str, depth: usize) -> (usize, Storage) {
+        let mut remaining = depth;
+
+        for (index, function) in self.functions.iter().enumerate().rev() {
+            if remaining < function.scopes.len() {
+                let scope = &function.scopes[function.scopes.len() - 1 - remaining];
+                let local = scope
+                    .iter()
+                    .rev()
+                    .find(|local| local.name == name)
+                    .expect("The resolver only resolves declared locals");
+
+                return (index, local.storage);
+            }
+
+            remaining -= function.scopes.len();
+        }
+
+        unreachable!(
+            "Resolved depth {} is deeper than the enclosing scopes",
+            depth
+        )
+    }
+
+    fn capture(&mut self, function: usize, owner: usize, cell: usize) -> usize {
+        if let Some(index) = self.functions[function]
+            .captured_cells
+            .iter()
+            .position(|captured| *captured == (owner, cell))
+        {
+            return index;
+        }
+
+        let capture = if function - 1 == owner {
+            Capture::Cell(cell)
+        } else {
+            Capture::Upvalue(self.capture(function - 1, owner, cell))
+        };
+
+        let state = &mut self.functions[function];
+        state.captures.push(capture);
+        state.captured_cells.push((owner, cell));
+
+        state.captures.len() - 1
+    }
+
+    fn emit_get(&mut self, identifier: &SyntaxToken) {
+        let instruction = match self.access(identifier) {
+            Access::Local(Storage::Slot(slot)) => Instruction::GetLocal(slot),
+            Access::Local(Storage::Cell(cell)) => Instruction::GetCell(cell),
+            Access::Upvalue(index) => Instruction::GetUpvalue(index),
+            Access::Global(name) => Instruction::GetGlobal(name),
+        };
+
+        self.emit(instruction);
+    }
+
+    fn emit_set(&mut self, identifier: &SyntaxToken) {
+        let instruction = match self.access(identifier) {
+            Access::Local(Storage::Slot(slot)) => Instruction::SetLocal(slot),
+            Access::Local(Storage::Cell(cell)) => Instruction::SetCell(cell),
+            Access::Upvalue(index) => Instruction::SetUpvalue(index),
+            Access::Global(name) => Instruction::SetGlobal(name),
+        };
+
+        self.emit(instruction);
+    }
+
+    fn emit_pattern_path(&mut self, cell: usize, path: &[PatternStep]) {
+        self.emit(Instruction::GetCell(cell));
+
+        for step in path {
+            match *step {
+                PatternStep::Property(name) => {
+                    self.emit(Instruction::GetProperty(name));
+                }
+                PatternStep::Index(index) => {
+                    self.emit_constant(NewtValue::Int(index as i64));
+                    self.emit(Instruction::GetIndex);
+                }
+            }
+        }
+    }
+
+    /// Parts are only read once the tests before them have shown they exist.
+    fn compile_pattern_tests(
+        &mut self,
+        pattern: &PatternNode,
+        cell: usize,
+        path: &mut Vec<PatternStep>,
+        failures: &mut Vec<usize>,
+    ) {
+        match pattern.kind() {
+            PatternKind::WildcardPattern | PatternKind::BindingPattern(_) => {}
+            PatternKind::LiteralPattern(node) => {
+                self.emit_pattern_path(cell, path);
+                self.visit_expr(node.expr());
+                self.emit(Instruction::Equal);
+                failures.push(self.emit(Instruction::JumpIfFalse(0)));
+            }
+            PatternKind::ArrayPattern(node) => {
+                self.emit_pattern_path(cell, path);
+                self.emit(Instruction::MatchArray {
+                    length: node.elements().count(),
+                    rest: node.has_rest(),
+                });
+                failures.push(self.emit(Instruction::JumpIfFalse(0)));
+
+                for (index, element) in node.elements().enumerate() {
+                    path.push(PatternStep::Index(index));
+                    self.compile_pattern_tests(element, cell, path, failures);
+                    path.pop();
+                }
+            }
+            PatternKind::ObjectPattern(node) => {
+                self.compile_field_tests(node.fields(), cell, path, failures);
+            }
+            PatternKind::StructPattern(node) => {
+                self.emit_pattern_path(cell, path);
+                self.emit_get(node.identifier());
+                self.emit(Instruction::MatchStruct);
+                failures.push(self.emit(Instruction::JumpIfFalse(0)));
+
+                self.compile_field_tests(node.fields(), cell, path, failures);
+            }
+        }
+    }
+
+    fn compile_field_tests<'n>(
+        &mut self,
+        fields: impl Iterator<Item = &'n PatternFieldNode>,
+        cell: usize,
+        path: &mut Vec<PatternStep>,
+        failures: &mut Vec<usize>,
+    ) {
+        for field in fields {
+            let name = self.name(field.identifier().lexeme());
+
+            self.emit_pattern_path(cell, path);
+            self.emit(Instruction::HasProperty(name));
+            failures.push(self.emit(Instruction::JumpIfFalse(0)));
+
+            if let Some(pattern) = field.pattern() {
+                path.push(PatternStep::Property(name));
+                self.compile_pattern_tests(pattern, cell, path, failures);
+                path.pop();
+            }
+        }
+    }
+
+    /// Bindings live in cells so that the stack can hold the temporaries of the enclosing expression.
+    fn compile_pattern_bindings(
+        &mut self,
+        pattern: &PatternNode,
+        cell: usize,
+        path: &mut Vec<PatternStep>,
+    ) {
+        match pattern.kind() {
+            PatternKind::WildcardPattern | PatternKind::LiteralPattern(_) => {}
+            PatternKind::BindingPattern(node) => {
+                self.bind_pattern_path(node.identifier(), cell, path)
+            }
+            PatternKind::ArrayPattern(node) => {
+                for (index, element) in node.elements().enumerate() {
+                    path.push(PatternStep::Index(index));
+                    self.compile_pattern_bindings(element, cell, path);
+                    path.pop();
+                }
+            }
+            PatternKind::ObjectPattern(node) => {
+                self.compile_field_bindings(node.fields(), cell, path)
+            }
+            PatternKind::StructPattern(node) => {
+                self.compile_field_bindings(node.fields(), cell, path)
+            }
+        }
+    }
+
+    fn compile_field_bindings<'n>(
+        &mut self,
+        fields: impl Iterator<Item = &'n PatternFieldNode>,
+        cell: usize,
+        path: &mut Vec<PatternStep>,
+    ) {
+        for field in fields {
+            let name = self.name(field.identifier().lexeme());
+            path.push(PatternStep::Property(name));
+
+            match field.pattern() {
+                Some(pattern) => self.compile_pattern_bindings(pattern, cell, path),
+                None => self.bind_pattern_path(field.identifier(), cell, path),
+            }
+
+            path.pop();
+        }
+    }
+
+    fn bind_pattern_path(&mut self, identifier: &SyntaxToken, cell: usize, path: &[PatternStep]) {
+        self.emit_pattern_path(cell, path);
+
+        let binding = self.new_cell();
+        self.emit(Instruction::DefineCell(binding));
+        self.add_local(identifier, Storage::Cell(binding));
+    }
+
+    fn compile_function<'n>(
+        &mut self,
+        parameters: impl Iterator<Item = &'n SyntaxToken>,
+        body: &StmtListStmtNode,
+        name: &str,
+        owner: Option<String>,
+    ) -> usize {
+        let parameters: Vec<&SyntaxToken> = parameters.collect();
+
+        self.functions
+            .push(FunctionState::new(name, owner, parameters.len()));
+        self.begin_scope();
+
+        for (slot, parameter) in parameters.iter().enumerate() {
+            if self.resolution.is_captured(parameter) {
+                let cell = self.new_cell();
+                self.emit(Instruction::GetLocal(slot));
+                self.emit(Instruction::DefineCell(cell));
+                self.add_local(parameter, Storage::Cell(cell));
+            } else {
+                self.add_local(parameter, Storage::Slot(slot));
+            }
+        }
+
+        self.visit_stmt_list_stmt(body);
+        self.emit(Instruction::Null);
+        self.emit(Instruction::Return);
+
+        let function = self.functions.pop().expect("Pushed above").into_function();
+
+        self.chunk().add_function(function)
+    }
+}
+
+impl<'a> ExprVisitor<()> for Compiler<'a> {
+    fn visit_expr(&mut self, node: &ExprNode) {
+        let enclosing = mem::replace(&mut self.span, node.to_inner().range());
+
+        match node.kind() {
+            ExprKind::BinaryExpr(node) => self.visit_binary_expr(node),
+            ExprKind::UnaryExpr(node) => self.visit_unary_expr(node),
+            ExprKind::PrimitiveLiteralExpr(node) => self.visit_primitive_literal_expr(node),
+            ExprKind::ObjectLiteralExpr(node) => self.visit_object_literal_expr(node),
+            ExprKind::ObjectPropertyExpr(node) => self.visit_object_property_expr(node),
+            ExprKind::GroupingExpr(node) => self.visit_grouping_expr(node),
+            ExprKind::VariableExpr(node) => self.visit_variable_expr(node),
+            ExprKind::FunctionCallExpr(node) => self.visit_function_call_expr(node),
+            ExprKind::ArrayLiteralExpr(node) => self.visit_array_literal_expr(node),
+            ExprKind::IndexExpr(node) => self.visit_index_expr(node),
+            ExprKind::StructLiteralExpr(node) => self.visit_struct_literal_expr(node),
+            ExprKind::FunctionExpr(node) => self.visit_function_expr(node),
+            ExprKind::InterpolatedStringExpr(node) => self.visit_interpolated_string_expr(node),
+            ExprKind::MatchExpr(node) => self.visit_match_expr(node),
+        }
+
+        self.span = enclosing;
+    }
+
+    fn visit_binary_expr(&mut self, node: &BinaryExprNode) {
+        match node.operator() {
+            TokenKind::AmpersandAmpersand => return self.compile_and(node),
+            TokenKind::PipePipe => return self.compile_or(node),
+            _ => {}
+        }
+
+        self.visit_expr(node.lhs());
+        self.visit_expr(node.rhs());
+
+        if node.operator() == TokenKind::BangEquals {
+            self.emit(Instruction::Equal);
+            self.emit(Instruction::Not);
+            return;
+        }
+
+        self.emit(match node.operator() {
+            TokenKind::Plus => Instruction::Add,
+            TokenKind::Minus => Instruction::Subtract,
+            TokenKind::Star => Instruction::Multiply,
+            TokenKind::Slash => Instruction::Divide,
+            TokenKind::Percent => Instruction::Modulo,
+            TokenKind::StarStar => Instruction::Power,
+            TokenKind::Ampersand => Instruction::BitAnd,
+            TokenKind::Pipe => Instruction::BitOr,
+            TokenKind::Caret => Instruction::BitXor,
+            TokenKind::LessLess => Instruction::ShiftLeft,
+            TokenKind::GreaterGreater => Instruction::ShiftRight,
+            TokenKind::Greater => Instruction::Greater,
+            TokenKind::GreaterEquals => Instruction::GreaterEqual,
+            TokenKind::Less => Instruction::Less,
+            TokenKind::LessEquals => Instruction::LessEqual,
+            TokenKind::EqualsEquals => Instruction::Equal,
+            kind => unreachable!("TokenKind {:?} is not a binary", kind),
+        });
+    }
+
+    fn visit_unary_expr(&mut self, node: &UnaryExprNode) {
+        if let Some(literal) = node.negated_integer_literal() {
+            let value = format!("-{}", literal.lexeme());
+            self.emit_constant(NewtValue::Int(
+                value.parse().expect("unparsable literal token"),
+            ));
+            return;
+        }
+
+        self.visit_expr(node.rhs());
+
+        self.emit(match node.operator() {
+            TokenKind::Bang => Instruction::Not,
+            TokenKind::Minus => Instruction::Negate,
+            _ => unreachable!("not a unary"),
+        });
+    }
+
+    fn visit_primitive_literal_expr(&mut self, node: &PrimitiveLiteralExprNode) {
+        self.emit_constant(NewtValue::from_primitive_literal_node(node));
+    }
+
+    fn visit_grouping_expr(&mut self, node: &GroupingExprNode) {
+        self.visit_expr(node.expr());
+    }
+
+    fn visit_variable_expr(&mut self, node: &VariableExprNode) {
+        self.emit_get(node.identifier());
+    }
+
+    fn visit_function_call_expr(&mut self, node: &FunctionCallExprNode) {
+        self.visit_expr(node.callee());
+
+        for argument in node.arguments() {
+            self.visit_expr(argument);
+        }
+
+        self.emit(Instruction::Call(node.arguments().count()));
+    }
+
+    fn visit_object_literal_expr(&mut self, node: &ObjectLiteralExprNode) {
+        let mut fields: Vec<(String, ExprNode)> = node.fields().into_iter().collect();
+        fields.sort_by_key(|(_, value)| value.to_inner().offset());
+
+        for (key, value) in fields.iter() {
+            self.emit_constant(NewtValue::from(key.as_str()));
+            self.visit_expr(value);
+        }
+
+        self.emit(Instruction::Object(fields.len()));
+    }
+
+    fn visit_object_property_expr(&mut self, node: &ObjectPropertyExprNode) {
+        self.visit_expr(node.source_expr());
+
+        let name = self.name(node.identifier().lexeme());
+        self.emit(Instruction::GetProperty(name));
+    }
+
+    fn visit_array_literal_expr(&mut self, node: &ArrayLiteralExprNode) {
+        for element in node.elements() {
+            self.visit_expr(element);
+        }
+
+        self.emit(Instruction::Array(node.elements().count()));
+    }
+
+    fn visit_index_expr(&mut self, node: &IndexExprNode) {
+        self.visit_expr(node.source_expr());
+        self.visit_expr(node.index());
+        self.emit(Instruction::GetIndex);
+    }
+
+    fn visit_struct_literal_expr(&mut self, node: &StructLiteralExprNode) {
+        self.emit_get(node.identifier());
+
+        for (name, value) in node.fields() {
+            self.emit_constant(NewtValue::from(name.lexeme()));
+            self.visit_expr(value);
+        }
+
+        self.emit(Instruction::Struct(node.fields().count()));
+    }
+
+    fn visit_function_expr(&mut self, node: &FunctionExprNode) {
+        let owner = self.current().owner.clone();
+        let function = self.compile_function(node.arguments(), node.stmts(), "<anonymous>", owner);
+
+        self.emit(Instruction::Closure(function));
+    }
+
+    fn visit_interpolated_string_expr(&mut self, node: &InterpolatedStringExprNode) {
+        let mut exprs = node.exprs();
+        let mut parts = 0;
+
+        for text in node.texts() {
+            self.emit_constant(NewtValue::String(NewtString::new(&literal_text(text))));
+            parts += 1;
+
+            if let Some(expr) = exprs.next() {
+                self.visit_expr(expr);
+                parts += 1;
+            }
+        }
+
+        self.emit(Instruction::Interpolate(parts));
+    }
+
+    fn visit_match_expr(&mut self, node: &MatchExprNode) {
+        self.visit_expr(node.scrutinee());
+        let scrutinee = self.new_cell();
+        self.emit(Instruction::DefineCell(scrutinee));
+
+        let mut ends = Vec::new();
+        for arm in node.arms() {
+            let mut failures = Vec::new();
+
+            self.begin_scope();
+            self.compile_pattern_tests(arm.pattern(), scrutinee, &mut Vec::new(), &mut failures);
+            self.compile_pattern_bindings(arm.pattern(), scrutinee, &mut Vec::new());
+
+            if let Some(guard) = arm.guard() {
+                self.visit_expr(guard);
+                failures.push(self.emit(Instruction::JumpIfFalse(0)));
+            }
+
+            self.visit_expr(arm.expr());
+            self.end_scope();
+            ends.push(self.emit(Instruction::Jump(0)));
+
+            for failure in failures {
+                self.chunk().patch_jump(failure);
+            }
+        }
+
+        self.emit(Instruction::GetCell(scrutinee));
+        self.emit(Instruction::NoMatch);
+
+        for end in ends {
+            self.chunk().patch_jump(end);
+        }
+    }
+}
+
+impl<'a> StmtVisitor<()> for Compiler<'a> {
+    fn visit_stmt(&mut self, node: &StmtNode) {
+        let enclosing = mem::replace(&mut self.span, node.to_inner().range());
+
+        match node.kind() {
+            StmtKind::VariableDeclarationStmt(node) => self.visit_variable_declaration_stmt(node),
+            StmtKind::AssignmentStmt(node) => self.visit_assignment_stmt(node),
+            StmtKind::StmtListStmt(node) => self.visit_stmt_list_stmt(node),
+            StmtKind::ExprStmt(node) => self.visit_expr_stmt(node),
+            StmtKind::IfStmt(node) => self.visit_if_stmt(node),
+            StmtKind::WhileStmt(node) => self.visit_while_stmt(node),
+            StmtKind::ForStmt(node) => self.visit_for_stmt(node),
+            StmtKind::FunctionDeclarationStmt(node) => self.visit_function_declaration_stmt(node),
+            StmtKind::ReturnStmt(node) => self.visit_return_stmt(node),
+            StmtKind::ThrowStmt(node) => self.visit_throw_stmt(node),
+            StmtKind::TryStmt(node) => self.visit_try_stmt(node),
+            StmtKind::BreakStmt(node) => self.visit_break_stmt(node),
+            StmtKind::ContinueStmt(node) => self.visit_continue_stmt(node),
+            StmtKind::StructDeclarationStmt(node) => self.visit_struct_declaration_stmt(node),
+            StmtKind::ImplStmt(node) => self.visit_impl_stmt(node),
+            StmtKind::InterfaceDeclarationStmt(node) => self.visit_interface_declaration_stmt(node),
+            StmtKind::ImportStmt(node) => self.visit_import_stmt(node),
+            StmtKind::ExportStmt(node) => self.visit_export_stmt(node),
+        }
+
+        self.span = enclosing;
+    }
+
+    fn visit_variable_declaration_stmt(&mut self, node: &VariableDeclarationStmtNode) {
+        self.visit_expr(node.expr());
+        self.declare(node.identifier());
+    }
+
+    fn visit_assignment_stmt(&mut self, node: &AssignmentStmtNode) {
+        self.visit_expr(node.expr());
+
+        match node.rval().kind() {
+            RValKind::VariableRVal(variable) => self.emit_set(variable.identifier()),
+            RValKind::ObjectPropertyRVal(property) => {
+                self.visit_expr(property.source_expr());
+
+                let name = self.name(property.identifier().lexeme());
+                self.emit(Instruction::SetProperty(name));
+            }
+            RValKind::IndexRVal(element) => {
+                self.visit_expr(element.source_expr());
+                self.visit_expr(element.index());
+                self.emit(Instruction::SetIndex);
+            }
+        };
+    }
+
+    fn visit_stmt_list_stmt(&mut self, node: &StmtListStmtNode) {
+        if node.has_braces() {
+            self.begin_scope();
+        }
+
+        for stmt in node.stmts() {
+            self.visit_stmt(stmt);
+        }
+
+        if node.has_braces() {
+            self.end_scope();
+        }
+    }
+
+    fn visit_expr_stmt(&mut self, node: &ExprStmtNode) {
+        self.visit_expr(node.expr());
+        self.emit(Instruction::Pop);
+    }
+
+    fn visit_if_stmt(&mut self, node: &IfStmtNode) {
+        self.visit_expr(node.condition());
+
+        let when_false = self.emit(Instruction::JumpIfFalse(0));
+        self.visit_stmt_list_stmt(node.when_true());
+
+        match node.when_false() {
+            Some(else_path) => {
+                let end = self.emit(Instruction::Jump(0));
+                self.chunk().patch_jump(when_false);
+                self.visit_stmt_list_stmt(else_path);
+                self.chunk().patch_jump(end);
+            }
+            None => self.chunk().patch_jump(when_false),
+        }
+    }
+
+    fn visit_while_stmt(&mut self, node: &WhileStmtNode) {
+        let loop_start = self.chunk().code.len();
+        self.visit_expr(node.condition());
+
+        let exit = self.emit(Instruction::JumpIfFalse(0));
+        self.begin_loop(node.label(), loop_start);
+        self.visit_stmt_list_stmt(node.stmts());
+        self.emit(Instruction::Jump(loop_start));
+
+        self.chunk().patch_jump(exit);
+        self.end_loop();
+    }
+
+    fn visit_for_stmt(&mut self, node: &ForStmtNode) {
+        self.visit_expr(node.iterable());
+        self.emit(Instruction::PushIterator);
+        self.current().iterators += 1;
+
+        let loop_start = self.chunk().code.len();
+        let exit = self.emit(Instruction::IterateNext(0));
+
+        self.begin_loop(node.label(), loop_start);
+        self.begin_scope();
+        self.declare(node.identifier());
+        self.visit_stmt_list_stmt(node.stmts());
+        self.end_scope();
+        self.emit(Instruction::Jump(loop_start));
+
+        // Breaks leave the iterator for the loop's own exit to pop
+        self.chunk().patch_jump(exit);
+        self.end_loop();
+        self.emit(Instruction::PopIterator);
+        self.current().iterators -= 1;
+    }
+
+    fn visit_function_declaration_stmt(&mut self, node: &FunctionDeclarationStmtNode) {
+        let identifier = node.identifier();
+        let owner = self.current().owner.clone();
+
+        // A captured function may call itself, so its cell has to exist before the closure
+        // capturing it is created.
+        if !self.is_global_scope() && self.resolution.is_captured(identifier) {
+            let cell = self.new_cell();
+            self.emit(Instruction::Null);
+            self.emit(Instruction::DefineCell(cell));
+            self.add_local(identifier, Storage::Cell(cell));
+
+            let function = self.compile_function(
+                node.arguments(),
+                node.stmts(),
+                identifier.lexeme(),
+                owner.clone(),
+            );
+            self.emit(Instruction::Closure(function));
+            self.emit(Instruction::SetCell(cell));
+        } else {
+            let function =
+                self.compile_function(node.arguments(), node.stmts(), identifier.lexeme(), owner);
+            self.emit(Instruction::Closure(function));
+            self.declare(identifier);
+        }
+    }
+
+    fn visit_return_stmt(&mut self, node: &ReturnStmtNode) {
+        match node.result() {
+            Some(expr) => self.visit_expr(expr),
+            None => {
+                self.emit(Instruction::Null);
+            }
+        }
+
+        // The result waits on the stack beneath any finally blocks run on the way out
+        self.current().slot_count += 1;
+        self.leave_try_stmts(0, 0);
+        self.current().slot_count -= 1;
+        self.emit(Instruction::Return);
+    }
+
+    fn visit_break_stmt(&mut self, node: &BreakStmtNode) {
+        let index = self.leave_loop_body(node.label());
+        let jump = self.emit(Instruction::Jump(0));
+
+        self.current().loops[index].breaks.push(jump);
+    }
+
+    fn visit_continue_stmt(&mut self, node: &ContinueStmtNode) {
+        let index = self.leave_loop_body(node.label());
+        let start = self.current().loops[index].start;
+
+        self.emit(Instruction::Jump(start));
+    }
+
+    fn visit_throw_stmt(&mut self, node: &ThrowStmtNode) {
+        self.visit_expr(node.expr());
+        self.emit(Instruction::Throw);
+    }
+
+    /// The finally block is compiled twice: once for normal completion and once for an error,
+    /// which is raised again after.
+    fn visit_try_stmt(&mut self, node: &TryStmtNode) {
+        let finally_handler = node.finally_stmts().map(|stmts| {
+            let block = FinallyBlock {
+                stmts: stmts.clone(),
+                scopes: self.current().scopes.len(),
+                handlers: self.current().handlers,
+                loops: self.current().loops.len(),
+            };
+
+            self.current().finally_blocks.push(block);
+            self.push_handler(Instruction::PushFinally(0))
+        });
+
+        match node.catch_clause() {
+            Some(clause) => {
+                let catch_handler = self.push_handler(Instruction::PushCatch(0));
+                self.visit_stmt_list_stmt(node.stmts());
+                self.pop_handler();
+                let end = self.emit(Instruction::Jump(0));
+
+                // The handler is gone by the time it jumps here, leaving the caught value
+                self.chunk().patch_jump(catch_handler);
+                self.begin_scope();
+                self.declare(clause.identifier());
+                self.visit_stmt_list_stmt(clause.stmts());
+                self.end_scope();
+
+                self.chunk().patch_jump(end);
+            }
+            None => self.visit_stmt_list_stmt(node.stmts()),
+        }
+
+        if let Some(finally_handler) = finally_handler {
+            let block = self
+                .current()
+                .finally_blocks
+                .pop()
+                .expect("The try statement's finally block was pushed above");
+
+            self.pop_handler();
+            self.visit_stmt_list_stmt(&block.stmts);
+            let end = self.emit(Instruction::Jump(0));
+
+            self.chunk().patch_jump(finally_handler);
+            self.visit_stmt_list_stmt(&block.stmts);
+            self.emit(Instruction::Rethrow);
+
+            self.chunk().patch_jump(end);
+        }
+    }
+
+    fn visit_struct_declaration_stmt(&mut self, node: &StructDeclarationStmtNode) {
+        let fields = node
+            .fields()
+            .map(|field| {
+                StructField::new(
+                    field.identifier().lexeme(),
+                    &field.type_ref().to_string(),
+                    field.is_immutable(),
+                    field.is_readable(),
+                    field.is_writable(),
+                )
+            })
+            .collect();
+        let struct_type = StructType::new(node.identifier().lexeme(), fields);
+
+        self.emit_constant(NewtValue::StructType(Rc::new(struct_type)));
+        self.declare(node.identifier());
+    }
+
+    fn visit_impl_stmt(&mut self, node: &ImplStmtNode) {
+        let type_name = node.type_identifier().lexeme();
+
+        self.emit_get(node.type_identifier());
+
+        // The interface is checked against the methods the impl provides before any of them
+        // are defined, so that an impl which fails leaves the type as it was
+        if let Some(interface) = node.interface_identifier() {
+            let provided = node
+                .methods()
+                .map(|method| interface_method(method.identifier(), method.arguments()))
+                .collect();
+            let provided = Interface::new(type_name, provided);
+
+            self.emit_constant(NewtValue::Interface(Rc::new(provided)));
+            self.emit_get(interface);
+            self.emit(Instruction::Implement);
+        }
+
+        for method in node.methods() {
+            let identifier = method.identifier().lexeme();
+            let is_static = interface_method(method.identifier(), method.arguments()).is_static();
+
+            let function = self.compile_function(
+                method.arguments(),
+                method.stmts(),
+                &format!("{}.{}", type_name, identifier),
+                Some(type_name.to_string()),
+            );
+            self.emit(Instruction::Closure(function));
+
+            let name = self.name(identifier);
+            self.emit(Instruction::DefineMethod { name, is_static });
+        }
+
+        self.emit(Instruction::Pop);
+    }
+
+    fn visit_interface_declaration_stmt(&mut self, node: &InterfaceDeclarationStmtNode) {
+        let methods = node
+            .methods()
+            .map(|method| interface_method(method.identifier(), method.arguments()))
+            .collect();
+        let interface = Interface::new(node.identifier().lexeme(), methods);
+
+        self.emit_constant(NewtValue::Interface(Rc::new(interface)));
+        self.declare(node.identifier());
+    }
+
+    fn visit_import_stmt(&mut self, node: &ImportStmtNode) {
+        let module = self.name(&node.module_name());
+
+        self.emit(Instruction::Import(module));
+        self.declare(node.identifier());
+    }
+
+    fn visit_export_stmt(&mut self, node: &ExportStmtNode) {
+        if let Some(expr) = node.expr() {
+            self.visit_expr(expr);
+            self.declare(node.identifier());
+        }
+    }
+}
+
+fn line_starts(text: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(text.match_indices('\n').map(|(offset, _)| offset + 1));
+
+    starts
+}
+
+fn interface_method<'n>(
+    identifier: &SyntaxToken,
+    arguments: impl Iterator<Item = &'n SyntaxToken>,
+) -> InterfaceMethod {
+    let arguments: Vec<_> = arguments.collect();
+    let is_static = arguments
+        .first()
+        .is_none_or(|receiver| receiver.lexeme() != "self");
+
+    InterfaceMethod::new(identifier.lexeme(), arguments.len(), is_static)
+}
@@ -0,0 +1,79 @@
+use crate::featurez::syntax::{NewtModule, NewtStaticError, StaticErrorKind, TextRange};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+pub struct ModuleLoader {
+    source_root: PathBuf,
+    modules: HashMap<String, Rc<NewtModule>>,
+    loading: Vec<String>,
+}
+
+impl ModuleLoader {
+    pub fn new(source_root: PathBuf) -> ModuleLoader {
+        ModuleLoader {
+            source_root,
+            modules: HashMap::new(),
+            loading: Vec::new(),
+        }
+    }
+
+    pub fn set_source_root(&mut self, source_root: PathBuf) {
+        self.source_root = source_root;
+    }
+
+    pub fn module(&self, name: &str) -> Option<&Rc<NewtModule>> {
+        self.modules.get(name)
+    }
+
+    /// Every call returning a source must be followed by `finish_loading` or `abandon_loading`.
+    pub fn begin_loading(
+        &mut self,
+        name: &str,
+        range: TextRange,
+    ) -> Result<Option<String>, NewtStaticError> {
+        if self.modules.contains_key(name) {
+            return Ok(None);
+        }
+
+        if self.loading.iter().any(|loading| loading == name) {
+            let mut cycle = self.loading.clone();
+            cycle.push(name.to_string());
+
+            return Err(NewtStaticError::new(
+                StaticErrorKind::ImportCycle,
+                &format!("import cycle: {}", cycle.join(" -> ")),
+                range,
+            ));
+        }
+
+        let path = self.path(name);
+        let source = std::fs::read_to_string(&path).map_err(|_| {
+            NewtStaticError::new(
+                StaticErrorKind::UnresolvedImport,
+                &format!("cannot find module {} at {}", name, path.display()),
+                range,
+            )
+        })?;
+
+        self.loading.push(name.to_string());
+        Ok(Some(source))
+    }
+
+    pub fn finish_loading(&mut self, module: Rc<NewtModule>) {
+        self.loading.pop();
+        self.modules.insert(module.name().to_string(), module);
+    }
+
+    pub fn abandon_loading(&mut self) {
+        self.loading.pop();
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        let mut path = self.source_root.clone();
+        path.extend(name.split('.'));
+        path.set_extension("newt");
+
+        path
+    }
+}
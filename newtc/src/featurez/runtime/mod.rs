@@ -1,5 +1,8 @@
+mod builtins;
 mod callable;
-mod scope;
+mod chunk;
+mod compiler;
+mod loader;
 mod virtual_machine;
 
 pub use self::callable::Callable;
@@ -1,11 +1,14 @@
 use crate::featurez::grammar::root_expr;
 use crate::featurez::parse::Parser;
-use crate::featurez::runtime::scope::Environment;
 use crate::featurez::syntax::{
-    NewtResult, NewtRuntimeError, NewtString, NewtValue, SyntaxKind, SyntaxTree,
+    NewtResult, NewtRuntimeError, NewtString, NewtValue, RuntimeErrorKind, StackFrame,
+    StaticErrorKind, SyntaxKind, SyntaxTree, TextRange,
 };
 use crate::featurez::tokenize;
-use crate::featurez::{StrTokenSource, VirtualMachine};
+use crate::featurez::{NewtError, StrTokenSource, VirtualMachine};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
 
 #[test]
 fn return_statement_returns_value() {
@@ -192,6 +195,130 @@ fn while_statement_uses_truthy_semantics() {
     );
 }
 
+#[test]
+fn for_statement_iterates_over_ranges() {
+    let mut vm = VirtualMachine::new();
+
+    vm.interpret(
+        r#"
+	let sum = 0;
+	for x in range(5) {
+		sum = sum + x;
+	}"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(10)), vm.interpret("sum"));
+}
+
+#[test]
+fn for_statement_iterates_over_arrays() {
+    let mut vm = VirtualMachine::new();
+
+    vm.interpret(
+        r#"
+	let reversed = [];
+	let xs = [1, 2, 3];
+	for x in xs {
+		reversed = [x, reversed];
+	}"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(3)), vm.interpret("reversed[0]"));
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("reversed[1][1][0]"));
+}
+
+#[test]
+fn for_statement_iterates_over_string_glyphs() {
+    let mut vm = VirtualMachine::new();
+
+    vm.interpret(
+        r#"
+	let glyphs = [];
+	for glyph in "abc" {
+		glyphs.push(glyph);
+	}"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(3)), vm.interpret("glyphs.length"));
+    assert_eq!(Ok(NewtValue::Glyph('a')), vm.interpret("glyphs[0]"));
+    assert_eq!(Ok(NewtValue::Glyph('c')), vm.interpret("glyphs[2]"));
+}
+
+#[test]
+fn for_statement_iterates_over_object_keys() {
+    let mut vm = VirtualMachine::new();
+
+    vm.interpret(
+        r#"
+	let map = { b: 2, a: 1, c: 3 };
+	let found = [];
+	let total = 0;
+	for key in map {
+		found.push(key);
+	}
+	for value in values(map) {
+		total = total + value;
+	}"#,
+    );
+
+    assert_eq!(
+        Ok(NewtValue::String(NewtString::new("a"))),
+        vm.interpret("found[0]")
+    );
+    assert_eq!(
+        Ok(NewtValue::String(NewtString::new("c"))),
+        vm.interpret("found[2]")
+    );
+    assert_eq!(Ok(NewtValue::Int(6)), vm.interpret("total"));
+    assert_eq!(Ok(NewtValue::Int(3)), vm.interpret("keys(map).length"));
+}
+
+#[test]
+fn for_statement_does_not_leak_loop_variable() {
+    let mut vm = VirtualMachine::new();
+
+    vm.interpret("for x in range(3) {}");
+
+    assert_eq!(
+        vec![StaticErrorKind::UndeclaredVariable],
+        static_error_kinds(vm.interpret("x"))
+    );
+}
+
+#[test]
+fn for_statement_returns_from_enclosing_function() {
+    let mut vm = VirtualMachine::new();
+
+    vm.interpret(
+        r#"
+	fn first_over(xs, limit) {
+		for x in xs {
+			if (x > limit) {
+				return x;
+			}
+		}
+
+		return 0;
+	}"#,
+    );
+
+    assert_eq!(
+        Ok(NewtValue::Int(5)),
+        vm.interpret("first_over([1, 5, 7], 3)")
+    );
+    assert_eq!(Ok(NewtValue::Int(0)), vm.interpret("first_over([], 3)"));
+}
+
+#[test]
+fn for_statement_fails_with_type_error_for_non_iterables() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("for x in 42 {}"))
+    );
+}
+
 #[test]
 fn newt_value_truthy_semantics_for_truthy_values() {
     let mut vm = VirtualMachine::new();
@@ -237,24 +364,24 @@ fn newt_value_truthy_semantics_for_untruthy_values() {
     );
 
     assert_eq!(
-        Err(NewtRuntimeError::TypeError),
-        vm.interpret("truthiness(1.0)")
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("truthiness(1.0)"))
     );
     assert_eq!(
-        Err(NewtRuntimeError::TypeError),
-        vm.interpret("truthiness(\"foo\")")
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("truthiness(\"foo\")"))
     );
     assert_eq!(
-        Err(NewtRuntimeError::TypeError),
-        vm.interpret("truthiness('c')")
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("truthiness('c')"))
     );
     assert_eq!(
-        Err(NewtRuntimeError::TypeError),
-        vm.interpret("truthiness(truthiness)")
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("truthiness(truthiness)"))
     );
     assert_eq!(
-        Err(NewtRuntimeError::TypeError),
-        vm.interpret("truthiness(null_value())")
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("truthiness(null_value())"))
     );
 }
 
@@ -294,8 +421,8 @@ fn function_declaration_statement_adds_function_to_scope() {
         vm.interpret("function_declaration()")
     );
     assert_eq!(
-        Err(NewtRuntimeError::UndefinedVariable),
-        vm.interpret("foo()")
+        vec![StaticErrorKind::UndeclaredVariable],
+        static_error_kinds(vm.interpret("foo()"))
     );
 }
 
@@ -348,6 +475,278 @@ fn function_declaration_statement_can_capture_closure() {
     assert_eq!(Ok(NewtValue::Int(3)), vm.interpret("count_to_3()"));
 }
 
+#[test]
+fn native_functions_can_be_called_from_scripts() {
+    let mut vm = VirtualMachine::new();
+    vm.define_native("add", 2, |_, arguments| {
+        arguments[0].clone() + arguments[1].clone()
+    });
+
+    assert_eq!(Ok(NewtValue::Int(42)), vm.interpret("add(40, 2)"));
+    assert_eq!(Ok(NewtValue::Float(1.5)), vm.interpret("add(1.0, 0.5)"));
+}
+
+#[test]
+fn native_functions_can_observe_script_values() {
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let mut vm = VirtualMachine::new();
+    let sink = output.clone();
+    vm.define_native("print", 1, move |_, arguments| {
+        sink.borrow_mut().push(arguments[0].to_string());
+        Ok(NewtValue::Null)
+    });
+
+    vm.interpret(
+        r#"
+	for x in range(3) {
+		print(x);
+	}
+	print([1, "two", 3.0]);
+	print({ b: true, a: 'c' });"#,
+    );
+
+    assert_eq!(
+        vec!["0", "1", "2", "[1, two, 3.0]", "{a: c, b: true}"],
+        *output.borrow()
+    );
+}
+
+#[test]
+fn native_functions_check_arity_before_calling() {
+    let called = Rc::new(RefCell::new(false));
+    let mut vm = VirtualMachine::new();
+    let witness = called.clone();
+    vm.define_native("nullary", 0, move |_, _| {
+        *witness.borrow_mut() = true;
+        Ok(NewtValue::Null)
+    });
+
+    assert_eq!(
+        Err(RuntimeErrorKind::ArityMismatch),
+        error_kind(vm.interpret("nullary(1)"))
+    );
+    assert_eq!(false, *called.borrow());
+}
+
+#[test]
+fn native_functions_can_fail_with_runtime_errors() {
+    let mut vm = VirtualMachine::new();
+    vm.define_native("fails", 0, |_, _| {
+        Err(NewtRuntimeError::type_error("always fails"))
+    });
+
+    assert_eq!(
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("fails()"))
+    );
+}
+
+#[test]
+fn native_functions_can_shadow_builtins_and_be_redefined() {
+    let mut vm = VirtualMachine::new();
+    vm.define_native("len", 1, |_, _| Ok(NewtValue::Int(1)));
+    vm.define_native("len", 1, |_, _| Ok(NewtValue::Int(2)));
+
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("len([])"));
+}
+
+#[test]
+fn builtin_str_converts_values_to_strings() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(
+        Ok(NewtValue::String(NewtString::new("42"))),
+        vm.interpret("str(42)")
+    );
+    assert_eq!(
+        Ok(NewtValue::String(NewtString::new("[true, c]"))),
+        vm.interpret("str([true, 'c'])")
+    );
+}
+
+#[test]
+fn builtin_len_counts_elements_glyphs_and_keys() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("let object = { a: 1, b: 2 };");
+
+    assert_eq!(Ok(NewtValue::Int(3)), vm.interpret("len([1, 2, 3])"));
+    assert_eq!(Ok(NewtValue::Int(5)), vm.interpret("len(\"hello\")"));
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("len(object)"));
+    assert_eq!(
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("len(42)"))
+    );
+}
+
+#[test]
+fn strings_are_concatenated_with_plus() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(
+        Ok(NewtValue::from("newts")),
+        vm.interpret(r#""newt" + "s""#)
+    );
+    assert_eq!(Ok(NewtValue::from("")), vm.interpret(r#""" + """#));
+    assert_eq!(
+        vec![StaticErrorKind::TypeMismatch],
+        static_error_kinds(vm.interpret(r#""newt" + 1"#))
+    );
+}
+
+#[test]
+fn interpolated_strings_format_their_expressions() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(r#"let name = "newt"; let scores = [1, 2.5];"#);
+
+    assert_eq!(
+        Ok(NewtValue::from("hello newt, you scored 3.5!")),
+        vm.interpret(r#""hello ${name}, you scored ${scores[0] + scores[1]}!""#)
+    );
+    assert_eq!(
+        Ok(NewtValue::from("[1, 2.5] and NEWT")),
+        vm.interpret(r#""${scores} and ${"${name}".upper()}""#)
+    );
+    assert_eq!(Ok(NewtValue::from("newt")), vm.interpret(r#""${name}""#));
+}
+
+#[test]
+fn string_literals_replace_escape_sequences() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(
+        Ok(NewtValue::from("a\tb\n\"c\" \\ ${d} \u{e9}")),
+        vm.interpret(r#""a\tb\n\"c\" \\ \${d} \u{e9}""#)
+    );
+    assert_eq!(Ok(NewtValue::Glyph('\'')), vm.interpret(r"'\''"));
+    assert_eq!(Ok(NewtValue::Glyph('\n')), vm.interpret(r"'\n'"));
+    assert_eq!(Ok(NewtValue::Glyph('\u{e9}')), vm.interpret("'\u{e9}'"));
+    assert_eq!(
+        Ok(NewtValue::Glyph('\u{1F600}')),
+        vm.interpret(r"'\u{1F600}'")
+    );
+    assert_eq!(
+        vec![
+            StaticErrorKind::InvalidEscapeSequence,
+            StaticErrorKind::InvalidEscapeSequence,
+        ],
+        static_error_kinds(vm.interpret(r#"print("\q"); print("${1} \u{110000}");"#))
+    );
+}
+
+#[test]
+fn invalid_escape_sequences_are_reported_whole() {
+    let mut vm = VirtualMachine::new();
+    let messages = |result: Result<NewtValue, NewtError>| match result {
+        Err(NewtError::Static(errors)) => errors
+            .iter()
+            .map(|error| error.message().to_string())
+            .collect::<Vec<_>>(),
+        other => panic!("Expected static errors, found {:?}", other),
+    };
+
+    assert_eq!(
+        vec![r"invalid escape sequence '\u{D800}'"],
+        messages(vm.interpret(r#""\u{D800} x""#))
+    );
+    assert_eq!(
+        vec![r"invalid escape sequence '\u'"],
+        messages(vm.interpret(r#""\u0041}""#))
+    );
+    assert_eq!(
+        vec![r"invalid escape sequence '\u{41'"],
+        messages(vm.interpret(r#""\u{41""#))
+    );
+}
+
+#[test]
+fn unicode_text_is_read_from_source() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("let caf\u{e9} = \"cr\u{e8}me br\u{fb}l\u{e9}e \u{1F36E}\";");
+
+    assert_eq!(Ok(NewtValue::Int(14)), vm.interpret("caf\u{e9}.length"));
+    assert_eq!(
+        Ok(NewtValue::Glyph('\u{1F36E}')),
+        vm.interpret("caf\u{e9}[13]")
+    );
+    assert_eq!(
+        Ok(NewtValue::from("\u{e9}: cr\u{e8}me")),
+        vm.interpret("\"\u{e9}: ${caf\u{e9}.slice(0, 5)}\"")
+    );
+    assert!(vm.interpret("let x = 'ab';").is_err());
+}
+
+#[test]
+fn strings_have_methods() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(r#"let text = "Newts, frogs, toads";"#);
+
+    assert_eq!(Ok(NewtValue::Int(19)), vm.interpret("text.length"));
+    assert_eq!(
+        Ok(NewtValue::from("frogs")),
+        vm.interpret("text.slice(7, 12)")
+    );
+    assert_eq!(Ok(NewtValue::Int(7)), vm.interpret(r#"text.find("frogs")"#));
+    assert_eq!(
+        Ok(NewtValue::Int(-1)),
+        vm.interpret(r#"text.find("salamanders")"#)
+    );
+    assert_eq!(
+        Ok(NewtValue::from("Newts and frogs and toads")),
+        vm.interpret(r#"text.replace(", ", " and ")"#)
+    );
+    assert_eq!(
+        Ok(NewtValue::from("NEWTS, FROGS, TOADS")),
+        vm.interpret("text.upper()")
+    );
+    assert_eq!(
+        Ok(NewtValue::from("newts, frogs, toads")),
+        vm.interpret("text.lower()")
+    );
+    assert_eq!(
+        Ok(NewtValue::Int(3)),
+        vm.interpret(r#"text.split(", ").length"#)
+    );
+    assert_eq!(
+        Ok(NewtValue::from("toads")),
+        vm.interpret(r#"text.split(", ")[2]"#)
+    );
+    assert_eq!(Ok(NewtValue::Glyph('N')), vm.interpret("text[0]"));
+}
+
+#[test]
+fn string_methods_check_their_arguments() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(r#"let text = "newt"; let anything = fn (value) { return value; };"#);
+
+    assert_eq!(
+        Err(RuntimeErrorKind::IndexOutOfBounds),
+        error_kind(vm.interpret("text.slice(2, 5)"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::IndexOutOfBounds),
+        error_kind(vm.interpret("text[4]"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("text.find(anything(1))"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::ArityMismatch),
+        error_kind(vm.interpret("text.slice(1)"))
+    );
+}
+
+#[test]
+fn script_functions_check_arity_before_calling() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("fn unary(x) { return x; }");
+
+    assert_eq!(
+        Err(RuntimeErrorKind::ArityMismatch),
+        error_kind(vm.interpret("unary(1, 2)"))
+    );
+}
+
 #[test]
 fn variable_declaration_statement_adds_variable_to_top_scope() {
     let mut vm = VirtualMachine::new();
@@ -367,7 +766,10 @@ fn variable_declaration_statement_does_not_effect_scope_prior_to_declaration() {
 	"#,
     );
 
-    assert_eq!(Err(NewtRuntimeError::UndefinedVariable), result);
+    assert_eq!(
+        vec![StaticErrorKind::UndeclaredVariable],
+        static_error_kinds(result)
+    );
 }
 
 #[test]
@@ -477,8 +879,8 @@ fn object_property_get_fails_with_type_error_for_invalid_property() {
     );
 
     assert_eq!(
-        Err(NewtRuntimeError::UndefinedVariable),
-        vm.interpret("instance.foo")
+        Err(RuntimeErrorKind::UndefinedVariable),
+        error_kind(vm.interpret("instance.foo"))
     );
 }
 
@@ -519,54 +921,398 @@ fn object_property_set_saves_expected_value_in_expected_property() {
 }
 
 #[test]
-fn virtual_machine_correctly_computes_fibonacci_5() {
+fn array_literals_are_correctly_evaluated() {
     let mut vm = VirtualMachine::new();
-    vm.interpret(
-        r#"
-		fn fibonacci(x) {
-			if (x == 2) {
-				return 1;
-			}
-			if (x == 1) {
-				return 1;
-			}
-			if (x == 0) {
-				return 0;
-			}
+    vm.interpret("let xs = [1, 2.5, \"three\", [4]];");
 
-			return fibonacci(x-2) + fibonacci(x-1);
-		}"#,
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("xs[0]"));
+    assert_eq!(Ok(NewtValue::Float(2.5)), vm.interpret("xs[1]"));
+    assert_eq!(
+        Ok(NewtValue::String(NewtString::new("three"))),
+        vm.interpret("xs[2]")
     );
-
-    assert_eq!(Ok(NewtValue::Int(8)), vm.interpret("fibonacci(6)"));
+    assert_eq!(Ok(NewtValue::Int(4)), vm.interpret("xs[3][0]"));
 }
 
 #[test]
-fn virtual_machine_precedence_calculates_higher_precedence_expressions_first() {
+fn array_index_fails_with_index_out_of_bounds() {
     let mut vm = VirtualMachine::new();
+    vm.interpret("let xs = [1, 2, 3];");
 
-    assert_eq!(Ok(NewtValue::Int(7)), vm.interpret("1+3*4/2"));
-    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("2 + 2 == 4"));
+    assert_eq!(
+        Err(RuntimeErrorKind::IndexOutOfBounds),
+        error_kind(vm.interpret("xs[3]"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::IndexOutOfBounds),
+        error_kind(vm.interpret("xs[-1]"))
+    );
 }
 
 #[test]
-fn virtual_machine_precedence_computes_prefix_unary_operators() {
+fn array_index_fails_with_type_error_for_non_integer_index() {
     let mut vm = VirtualMachine::new();
+    vm.interpret("let xs = [1, 2, 3];");
 
-    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("-1+2"));
-    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("2+-1"));
-    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("--2"));
+    assert_eq!(
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("xs[1.0]"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("42[0]"))
+    );
 }
 
 #[test]
-fn virtual_machine_precedence_computes_suffix_unary_operators() {
+fn array_index_set_saves_expected_value_in_expected_element() {
     let mut vm = VirtualMachine::new();
     vm.interpret(
         r#"
-		fn fibonacci(x) {
-			if (x == 2) {
-				return 1;
-			}
+	let xs = [1, 2, 3];
+	xs[1] = 42;"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("xs[0]"));
+    assert_eq!(Ok(NewtValue::Int(42)), vm.interpret("xs[1]"));
+    assert_eq!(
+        Err(RuntimeErrorKind::IndexOutOfBounds),
+        error_kind(vm.interpret("xs[3] = 4;"))
+    );
+}
+
+#[test]
+fn array_length_push_and_pop_modify_the_array() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+	let xs = [];
+	xs.push(1);
+	xs.push(2);
+	xs.push(3);
+	let popped = xs.pop();"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(3)), vm.interpret("popped"));
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("xs.length"));
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("xs[1]"));
+}
+
+#[test]
+fn array_values_are_shared_between_references() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+	fn append(list, value) {
+		list.push(value);
+	}
+	let xs = [];
+	append(xs, 42);"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("xs.length"));
+    assert_eq!(Ok(NewtValue::Int(42)), vm.interpret("xs[0]"));
+}
+
+#[test]
+fn virtual_machine_correctly_computes_fibonacci_5() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn fibonacci(x) {
+			if (x == 2) {
+				return 1;
+			}
+			if (x == 1) {
+				return 1;
+			}
+			if (x == 0) {
+				return 0;
+			}
+
+			return fibonacci(x-2) + fibonacci(x-1);
+		}"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(8)), vm.interpret("fibonacci(6)"));
+}
+
+#[test]
+fn virtual_machine_precedence_calculates_higher_precedence_expressions_first() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(Ok(NewtValue::Int(7)), vm.interpret("1+3*4/2"));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("2 + 2 == 4"));
+}
+
+#[test]
+fn virtual_machine_precedence_computes_prefix_unary_operators() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("-1+2"));
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("2+-1"));
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("--2"));
+}
+
+#[test]
+fn virtual_machine_precedence_orders_numeric_and_bitwise_operators() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(Ok(NewtValue::Int(-4)), vm.interpret("-2 ** 2"));
+    assert_eq!(Ok(NewtValue::Int(512)), vm.interpret("2 ** 3 ** 2"));
+    assert_eq!(
+        Ok(NewtValue::Int(19)),
+        vm.interpret("3 * 2 ** 3 - 10 % 4 - 3")
+    );
+    assert_eq!(Ok(NewtValue::Int(16)), vm.interpret("1 + 1 << 3"));
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("1 | 2 ^ 3 & 6"));
+}
+
+#[test]
+fn logical_operators_short_circuit() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+	let calls = 0;
+	fn touch(result) {
+		calls = calls + 1;
+		return result;
+	}"#,
+    );
+
+    assert_eq!(
+        Ok(NewtValue::Bool(false)),
+        vm.interpret("false && touch(true)")
+    );
+    assert_eq!(
+        Ok(NewtValue::Bool(true)),
+        vm.interpret("true || touch(false)")
+    );
+    assert_eq!(Ok(NewtValue::Int(0)), vm.interpret("calls"));
+
+    assert_eq!(
+        Ok(NewtValue::Bool(true)),
+        vm.interpret("true && touch(true)")
+    );
+    assert_eq!(
+        Ok(NewtValue::Bool(false)),
+        vm.interpret("false || touch(false)")
+    );
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("calls"));
+}
+
+#[test]
+fn logical_operators_bind_looser_than_comparisons() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("1 < 2 && 2 < 3"));
+    assert_eq!(
+        Ok(NewtValue::Bool(true)),
+        vm.interpret("true || false && false")
+    );
+    assert_eq!(
+        Ok(NewtValue::Bool(false)),
+        vm.interpret("(true || false) && false")
+    );
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("!false && 1 != 2"));
+    assert_eq!(Ok(NewtValue::Bool(false)), vm.interpret("1 && 0"));
+}
+
+#[test]
+fn not_negates_bools_and_ints() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(Ok(NewtValue::Bool(false)), vm.interpret("!true"));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("!!true"));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("!0"));
+    assert_eq!(
+        vec![StaticErrorKind::TypeMismatch],
+        static_error_kinds(vm.interpret("!\"newt\""))
+    );
+}
+
+#[test]
+fn values_are_equal_by_value_or_identity() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+	struct Point { x: i64, y: i64 }
+	fn f() { return 1; }
+	let a = [1, 2];
+	let o = { x: 1 };
+	let p = Point { x: 1, y: 2 };
+	let g = f;
+	let other_o = { x: 1 };
+	let other_p = Point { x: 1, y: 2 };
+	let other_f = fn () { return 1; };"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("1 == 1.0"));
+    assert_eq!(
+        Ok(NewtValue::Bool(true)),
+        vm.interpret("\"newt\" == \"newt\"")
+    );
+    assert_eq!(
+        Ok(NewtValue::Bool(true)),
+        vm.interpret("\"newt\" != \"newts\"")
+    );
+    assert_eq!(Ok(NewtValue::Bool(false)), vm.interpret("1 == \"1\""));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("a == a"));
+    assert_eq!(Ok(NewtValue::Bool(false)), vm.interpret("a == [1, 2]"));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("o == o"));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("o != other_o"));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("p == p"));
+    assert_eq!(Ok(NewtValue::Bool(false)), vm.interpret("p == other_p"));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("f == g"));
+    assert_eq!(Ok(NewtValue::Bool(false)), vm.interpret("f == other_f"));
+}
+
+#[test]
+fn ints_are_promoted_to_floats_when_mixed_with_them() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(Ok(NewtValue::Float(3.5)), vm.interpret("1 + 2.5"));
+    assert_eq!(Ok(NewtValue::Float(7.5)), vm.interpret("2.5 * 3"));
+    assert_eq!(Ok(NewtValue::Float(0.5)), vm.interpret("1 / 2.0"));
+    assert_eq!(Ok(NewtValue::Int(0)), vm.interpret("1 / 2"));
+    assert_eq!(Ok(NewtValue::Float(2.0)), vm.interpret("4 ** 0.5"));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("2 < 2.5"));
+}
+
+#[test]
+fn remainders_and_powers_of_ints() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("7 % 3"));
+    assert_eq!(Ok(NewtValue::Int(-1)), vm.interpret("-7 % 3"));
+    assert_eq!(Ok(NewtValue::Float(1.5)), vm.interpret("7.5 % 2"));
+    assert_eq!(Ok(NewtValue::Int(1024)), vm.interpret("2 ** 10"));
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("7 ** 0"));
+    assert_eq!(Ok(NewtValue::Int(0)), vm.interpret("2 ** -1"));
+    assert_eq!(Ok(NewtValue::Int(-1)), vm.interpret("(-1) ** -3"));
+}
+
+#[test]
+fn bitwise_operators_work_on_ints_and_bools() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("6 & 3"));
+    assert_eq!(Ok(NewtValue::Int(7)), vm.interpret("6 | 3"));
+    assert_eq!(Ok(NewtValue::Int(5)), vm.interpret("6 ^ 3"));
+    assert_eq!(Ok(NewtValue::Int(40)), vm.interpret("5 << 3"));
+    assert_eq!(Ok(NewtValue::Int(-4)), vm.interpret("-16 >> 2"));
+    assert_eq!(Ok(NewtValue::Bool(false)), vm.interpret("true & false"));
+    assert_eq!(Ok(NewtValue::Bool(true)), vm.interpret("true ^ false"));
+    assert_eq!(
+        vec![StaticErrorKind::TypeMismatch],
+        static_error_kinds(vm.interpret("1.5 & 1"))
+    );
+}
+
+#[test]
+fn integer_literals_must_fit_in_an_i64() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(
+        Ok(NewtValue::Int(i64::MIN)),
+        vm.interpret("-9223372036854775808")
+    );
+    assert_eq!(
+        Ok(NewtValue::Bool(true)),
+        evaluate(
+            &mut vm,
+            "match -9223372036854775808 { -9223372036854775808 => true, _ => false }"
+        )
+    );
+    assert_eq!(
+        vec![
+            StaticErrorKind::IntegerLiteralOutOfRange,
+            StaticErrorKind::IntegerLiteralOutOfRange,
+        ],
+        static_error_kinds(
+            vm.interpret("let a = 9223372036854775808; let b = -9223372036854775809;")
+        )
+    );
+
+    match vm.interpret("1 + 99999999999999999999") {
+        Err(NewtError::Static(errors)) => {
+            assert_eq!(TextRange::new(4, 24), errors[0].range());
+            assert_eq!(
+                "integer literal '99999999999999999999' does not fit in an i64",
+                errors[0].message()
+            );
+        }
+        other => panic!("Expected a static error, found {:?}", other),
+    }
+}
+
+#[test]
+fn integer_overflow_is_a_runtime_error() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("let max = 9223372036854775807; let min = -max - 1;");
+
+    assert_eq!(
+        Err(RuntimeErrorKind::Overflow),
+        error_kind(vm.interpret("max + 1"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::Overflow),
+        error_kind(vm.interpret("min - 1"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::Overflow),
+        error_kind(vm.interpret("max * 2"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::Overflow),
+        error_kind(vm.interpret("min / -1"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::Overflow),
+        error_kind(vm.interpret("-min"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::Overflow),
+        error_kind(vm.interpret("2 ** 64"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::Overflow),
+        error_kind(vm.interpret("1 << 64"))
+    );
+    assert_eq!(
+        Ok(NewtValue::Float(9223372036854775808.0)),
+        vm.interpret("max + 1.0")
+    );
+}
+
+#[test]
+fn integer_division_by_zero_is_a_runtime_error() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(
+        Err(RuntimeErrorKind::DivisionByZero),
+        error_kind(vm.interpret("1 / 0"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::DivisionByZero),
+        error_kind(vm.interpret("1 % 0"))
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::DivisionByZero),
+        error_kind(vm.interpret("0 ** -1"))
+    );
+    assert_eq!(Ok(NewtValue::Float(f64::INFINITY)), vm.interpret("1.0 / 0"));
+}
+
+#[test]
+fn virtual_machine_precedence_computes_suffix_unary_operators() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn fibonacci(x) {
+			if (x == 2) {
+				return 1;
+			}
 			if (x == 1) {
 				return 1;
 			}
@@ -588,12 +1334,12 @@ fn virtual_machine_short_circuits_incorrect_syntax() {
     let mut vm = VirtualMachine::new();
 
     assert_eq!(
-        Err(NewtRuntimeError::InvalidSyntaxTree),
-        vm.interpret("2++2")
+        Err(RuntimeErrorKind::InvalidSyntaxTree),
+        error_kind(vm.interpret("2++2"))
     );
 }
 
-fn evaluate(vm: &mut VirtualMachine, source: &str) -> NewtResult {
+fn evaluate(vm: &mut VirtualMachine, source: &str) -> Result<NewtValue, NewtError> {
     let tokens = tokenize(source);
     let token_source = StrTokenSource::new(tokens);
     let mut parser = Parser::new(token_source);
@@ -607,3 +1353,1068 @@ fn evaluate(vm: &mut VirtualMachine, source: &str) -> NewtResult {
 fn assert_eq_newt_values(a: NewtValue, b: NewtValue) {
     assert_eq!(a, b);
 }
+
+#[test]
+fn type_errors_name_the_operand_types_and_point_at_the_expression() {
+    let mut vm = VirtualMachine::new();
+
+    let error = runtime_error(vm.interpret("fn f(a) { return 1 + (2 * a); } f(\"a\");"));
+
+    assert_eq!(&RuntimeErrorKind::TypeError, error.kind());
+    assert_eq!("cannot apply '*' to int and string", error.message());
+    assert_eq!(Some(TextRange::new(22, 27)), error.range());
+}
+
+#[test]
+fn undefined_variable_errors_name_the_variable() {
+    let mut vm = VirtualMachine::new();
+
+    let error = runtime_error(vm.interpret("fn f() { return later; } f(); let later = 1;"));
+
+    assert_eq!("'later' is not defined", error.message());
+    assert_eq!(Some(TextRange::new(16, 21)), error.range());
+}
+
+#[test]
+fn runtime_errors_collect_a_stack_trace_while_unwinding() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("fn inner(a) { return 1 + a; }\nfn outer() { return inner(\"a\"); }");
+
+    let error = runtime_error(vm.interpret("outer()"));
+    let frames: Vec<&str> = error.stack().iter().map(|f| f.symbol()).collect();
+
+    assert_eq!(vec!["inner", "outer"], frames);
+    assert_eq!(Some(TextRange::new(21, 26)), error.range());
+    assert_eq!(Some(TextRange::new(0, 7)), error.stack()[1].call_site());
+}
+
+#[test]
+fn native_function_errors_point_at_their_call_site() {
+    let mut vm = VirtualMachine::new();
+
+    let error = runtime_error(vm.interpret("let n = 1 + len(42);"));
+
+    assert_eq!("len does not accept int", error.message());
+    assert_eq!(Some(TextRange::new(12, 19)), error.range());
+    assert_eq!(
+        &[StackFrame::new("len", Some(TextRange::new(12, 19)))],
+        error.stack()
+    );
+}
+
+#[test]
+fn runtime_errors_render_the_source_and_stack_trace() {
+    let mut vm = VirtualMachine::new();
+    let source = "fn half(x) { return x / 2; }\nhalf(\"four\");";
+
+    let rendered = runtime_error(vm.interpret(source)).render(source);
+
+    assert!(rendered.contains("cannot apply '/' to string and int"));
+    assert!(rendered.contains("1 | fn half(x) { return x / 2; }"));
+    assert!(rendered.contains("^^^^^"));
+    assert!(rendered.contains("in half called at line 2"));
+}
+
+#[test]
+fn programs_with_static_errors_are_not_run() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("let ran = false;");
+
+    let result = vm.interpret("ran = true; { let x = 1; let x = 2; }");
+
+    assert_eq!(
+        vec![StaticErrorKind::DuplicateVariableDeclaration],
+        static_error_kinds(result)
+    );
+    assert_eq!(Ok(NewtValue::Bool(false)), vm.interpret("ran"));
+}
+
+#[test]
+fn globals_can_be_redeclared_by_later_programs() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("let x = 1;");
+    vm.interpret("let boom = 1 / 0;");
+
+    assert_eq!(Ok(NewtValue::Null), vm.interpret("let x = 2;"));
+    assert_eq!(Ok(NewtValue::Null), vm.interpret("let boom = 3;"));
+    assert_eq!(Ok(NewtValue::Int(5)), vm.interpret("x + boom"));
+}
+
+#[test]
+fn failed_programs_do_not_declare_globals_they_never_defined() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("let before = 1; let boom = 1 / 0; let after = 2;");
+
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("before"));
+    assert_eq!(
+        vec![StaticErrorKind::UndeclaredVariable],
+        static_error_kinds(vm.interpret("after"))
+    );
+}
+
+#[test]
+fn errors_inside_blocks_do_not_leave_their_scopes_open() {
+    let mut vm = VirtualMachine::new();
+
+    vm.interpret("{ let x = 1 + true; }");
+    vm.interpret("let y = 2;");
+
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("y"));
+}
+
+#[test]
+fn closures_share_captured_variables_by_reference() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn counter() {
+			let count = 0;
+			fn increment() { count = count + 1; return count; }
+			fn read() { return count; }
+			return [increment, read];
+		}
+		let pair = counter();
+		let increment = pair[0];
+		let read = pair[1];
+		increment();
+		increment();
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("read()"));
+}
+
+#[test]
+fn captured_variables_reach_through_intermediate_functions() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn outer() {
+			let x = 1;
+			fn middle() {
+				fn inner() { x = x * 10; return x; }
+				return inner;
+			}
+			return middle();
+		}
+		let inner = outer();
+		inner();
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(100)), vm.interpret("inner()"));
+}
+
+#[test]
+fn each_loop_iteration_gets_a_fresh_captured_variable() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		let fns = [];
+		for i in range(3) {
+			fn f() { return i; }
+			fns.push(f);
+		}
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(0)), vm.interpret("fns[0]()"));
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("fns[2]()"));
+}
+
+#[test]
+fn closures_see_assignments_made_after_they_were_created() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn make() {
+			let greeting = "hello";
+			let read = fn () { return greeting; };
+			greeting = "goodbye";
+			return read;
+		}
+		let read = make();
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::from("goodbye")), vm.interpret("read()"));
+}
+
+#[test]
+fn accumulators_captured_by_several_functions_share_their_state() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn accumulator(start) {
+			let total = start;
+			return {
+				add: fn (amount) { total = total + amount; return total; },
+				reset: fn () { total = start; },
+				read: fn () { return total; }
+			};
+		}
+		let a = accumulator(10);
+		let b = accumulator(100);
+		a.add(5);
+		a.add(5);
+		b.add(1);
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(20)), vm.interpret("a.read()"));
+    assert_eq!(Ok(NewtValue::Int(101)), vm.interpret("b.read()"));
+
+    vm.interpret("a.reset(); a.add(3);");
+    assert_eq!(Ok(NewtValue::Int(13)), vm.interpret("a.read()"));
+    assert_eq!(Ok(NewtValue::Int(101)), vm.interpret("b.read()"));
+}
+
+#[test]
+fn anonymous_functions_are_values() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn apply_all(values, f) {
+			let results = [];
+			for value in values { results.push(f(value)); }
+			return results;
+		}
+		let factor = 3;
+		let tripled = apply_all([1, 2], fn (x) { return x * factor; });
+		let answer = fn (x, y) { return x + y; }(40, 2);
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(6)), vm.interpret("tripled[1]"));
+    assert_eq!(Ok(NewtValue::Int(42)), vm.interpret("answer"));
+
+    vm.interpret("let seven = 0; fn () { seven = 7; }();");
+    assert_eq!(Ok(NewtValue::Int(7)), vm.interpret("seven"));
+}
+
+#[test]
+fn anonymous_functions_capture_loop_variables_and_each_other() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn counters() {
+			let made = [];
+			for i in range(3) {
+				let count = i * 10;
+				made.push(fn () { count = count + 1; return count; });
+			}
+			return made;
+		}
+		let made = counters();
+		made[2]();
+		made[2]();
+		made[0]();
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(23)), vm.interpret("made[2]()"));
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("made[0]()"));
+}
+
+#[test]
+fn anonymous_functions_are_type_checked_like_declarations() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret("let half = fn (x: f64) -> f64 { return x / 2.0; }; half(\"a\");");
+
+    assert_eq!(
+        vec![StaticErrorKind::TypeMismatch],
+        static_error_kinds(result)
+    );
+    assert_eq!(
+        vec![StaticErrorKind::UndeclaredVariable],
+        static_error_kinds(vm.interpret("fn () { return missing; };"))
+    );
+}
+
+#[test]
+fn returning_from_inside_nested_loops_leaves_the_stack_balanced() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn find(target) {
+			for i in range(10) {
+				for j in range(10) {
+					let product = i * j;
+					if product == target { return [i, j]; }
+				}
+			}
+			return [0, 0];
+		}
+		let total = 0;
+		for n in [6, 12, 81] {
+			let pair = find(n);
+			total = total + pair[0] + pair[1];
+		}
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(33)), vm.interpret("total"));
+}
+
+#[test]
+fn struct_literals_construct_instances_with_declared_fields() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		struct Point { get x: i64, get y: i64 }
+		let p = Point { y: 2, x: 1 };
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("p.x"));
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("p.y"));
+    assert_eq!(
+        "Point { x: 1, y: 2 }",
+        vm.interpret("p").unwrap().to_string()
+    );
+}
+
+#[test]
+fn struct_literals_reject_unknown_and_missing_fields() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("struct Point { get x: i64, get y: i64 }");
+
+    let unknown = runtime_error(vm.interpret("let p = Point { x: 1, y: 2, z: 3 };"));
+    let missing = runtime_error(vm.interpret("let q = Point { x: 1 };"));
+
+    assert_eq!(&RuntimeErrorKind::UndefinedVariable, unknown.kind());
+    assert_eq!("Point has no field 'z'", unknown.message());
+    assert_eq!(&RuntimeErrorKind::TypeError, missing.kind());
+    assert_eq!("field 'y' of Point is not initialized", missing.message());
+}
+
+#[test]
+fn struct_fields_enforce_their_visibility_modifiers() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		struct Foo {
+				Bar: i64,
+			imm	Fizz: i64,
+			get	Buzz: i64,
+			set	Flop: i64,
+			get set	Zap: i64
+		}
+		let foo = Foo { Bar: 1, Fizz: 2, Buzz: 3, Flop: 4, Zap: 5 };
+		"#,
+    );
+
+    let inaccessible = |result| error_kind(result) == Err(RuntimeErrorKind::InaccessibleField);
+
+    assert!(inaccessible(vm.interpret("foo.Bar")));
+    assert!(inaccessible(vm.interpret("foo.Bar = 0;")));
+    assert!(inaccessible(vm.interpret("foo.Fizz = 0;")));
+    assert!(inaccessible(vm.interpret("foo.Buzz = 0;")));
+    assert!(inaccessible(vm.interpret("foo.Flop")));
+    assert_eq!(Ok(NewtValue::Int(3)), vm.interpret("foo.Buzz"));
+
+    vm.interpret("foo.Flop = 40; foo.Zap = 50;").unwrap();
+    assert_eq!(Ok(NewtValue::Int(50)), vm.interpret("foo.Zap"));
+}
+
+#[test]
+fn struct_instances_are_shared_by_reference() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		struct Counter { get set count: i64 }
+		fn bump(counter) { counter.count = counter.count + 1; }
+		let c = Counter { count: 0 };
+		bump(c);
+		bump(c);
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("c.count"));
+}
+
+#[test]
+fn methods_bind_self_to_their_receiver() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		struct Counter { count: i64 }
+		impl Counter {
+			fn new() { return Counter { count: 0 }; }
+			fn bump(self, by) { self.count = self.count + by; return self.count; }
+		}
+		let c = Counter.new();
+		c.bump(2);
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(5)), vm.interpret("c.bump(3)"));
+    assert_eq!(
+        Err(RuntimeErrorKind::InaccessibleField),
+        error_kind(vm.interpret("c.count"))
+    );
+}
+
+#[test]
+fn methods_live_on_the_type_and_are_shared_by_instances() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		struct Dog { get name: string }
+		let rex = Dog { name: "Rex" };
+		impl Dog { fn speak(self) { return self.name; } }
+		let speak = rex.speak;
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::from("Rex")), vm.interpret("speak()"));
+    vm.interpret(r#"let fido = Dog { name: "Fido" }.speak();"#);
+    assert_eq!(Ok(NewtValue::from("Fido")), vm.interpret("fido"));
+}
+
+#[test]
+fn method_errors_name_the_method_in_the_stack_trace() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		struct Dog;
+		impl Dog { fn speak(self, sound) { return 1 + sound; } fn new() { return Dog {}; } }
+		"#,
+    );
+
+    let error = runtime_error(vm.interpret("Dog {}.speak(\"woof\");"));
+    let symbols: Vec<&str> = error.stack().iter().map(|f| f.symbol()).collect();
+
+    assert_eq!(vec!["Dog.speak"], symbols);
+    assert_eq!(
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("Dog {}.new();"))
+    );
+}
+
+#[test]
+fn values_dispatch_through_the_interfaces_they_implement() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		interface Animal { fn make_sound(self); }
+		struct Dog;
+		struct Cat;
+		struct Rock;
+		impl Animal for Dog { fn make_sound(self) { return "Bark!"; } }
+		impl Animal for Cat { fn make_sound(self) { return "Meow!"; } }
+		let sounds = [];
+		for value in [Dog {}, Rock {}, Cat {}] {
+			if implements(value, Animal) {
+				sounds.push(value.make_sound());
+			}
+		}
+		"#,
+    );
+
+    assert_eq!(
+        "[Bark!, Meow!]",
+        vm.interpret("sounds").unwrap().to_string()
+    );
+    assert_eq!(
+        Ok(NewtValue::Bool(false)),
+        vm.interpret("implements(42, Animal)")
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::TypeError),
+        error_kind(vm.interpret("implements(Dog, 42)"))
+    );
+}
+
+#[test]
+fn impls_must_provide_every_method_their_interface_requires() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		interface Shape { fn area(self); fn name(self); }
+		struct Square { side: i64 }
+		"#,
+    );
+
+    let missing =
+        runtime_error(vm.interpret("impl Shape for Square { fn area(self) { return 1; } }"));
+    vm.interpret("let square = Square { side: 1 };").unwrap();
+    let undefined = runtime_error(vm.interpret("square.area()"));
+    let mismatched = runtime_error(vm.interpret(
+        "impl Shape for Square { fn area(self, scale) { return 1; } fn name(self) { return 1; } }",
+    ));
+
+    assert_eq!(
+        "Square does not implement 'name' required by Shape",
+        missing.message()
+    );
+    assert_eq!(
+        "Square.area does not match the signature required by Shape",
+        mismatched.message()
+    );
+    assert_eq!("Square has no field or method 'area'", undefined.message());
+    assert_eq!(
+        Ok(NewtValue::Bool(false)),
+        vm.interpret("implements(Square, Shape)")
+    );
+}
+
+#[test]
+fn annotations_naming_unknown_types_are_rejected() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret("struct Square { get side: i64 }").unwrap();
+
+    assert_eq!(
+        vec![StaticErrorKind::UnknownType],
+        static_error_kinds(vm.interpret("let side: int = 1;"))
+    );
+    vm.interpret("fn side(s: Square) -> i64 { return s.side; } let s = Square { side: 2 };")
+        .unwrap();
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("side(s)"));
+}
+
+#[test]
+fn generic_code_runs_with_its_type_parameters_erased() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(
+        r#"
+		interface Mammal { fn sound(self); }
+		struct Dog;
+		impl Mammal for Dog { fn sound(self) { return "Bark!"; } }
+		struct Cage[T: Mammal] { get Animal: T }
+		impl Cage[T: Mammal] {
+			fn listen[Z: Mammal](self, other: Z) {
+				return [self.Animal.sound(), other.sound()];
+			}
+		}
+		fn first[T](values: Array[T]) { return values[0]; }
+		let sounds = Cage { Animal: Dog {} }.listen(Dog {});
+		"#,
+    );
+
+    assert!(result.is_ok());
+    assert_eq!(
+        "[Bark!, Bark!]",
+        vm.interpret("sounds").unwrap().to_string()
+    );
+    assert_eq!(Ok(NewtValue::Int(1)), vm.interpret("first([1, 2])"));
+}
+
+#[test]
+fn unsatisfied_constraints_are_reported_before_the_program_runs() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret(
+        r#"
+		interface Mammal { fn sound(self); }
+		struct Rock;
+		fn listen[Z: Mammal](value: Z) { return value.sound(); }
+		let ran = true;
+		listen(Rock {});
+		"#,
+    );
+
+    assert_eq!(
+        vec![StaticErrorKind::UnsatisfiedConstraint],
+        static_error_kinds(result)
+    );
+    assert_eq!(
+        vec![StaticErrorKind::UndeclaredVariable],
+        static_error_kinds(vm.interpret("ran"))
+    );
+}
+
+#[test]
+fn annotated_programs_run_like_unannotated_ones() {
+    let mut vm = VirtualMachine::new();
+
+    assert_eq!(
+        Ok(NewtValue::Float(1.5)),
+        vm.interpret(
+            r#"
+		fn half(value: f64) -> f64 { return value / 2.0; }
+		let x: f64 = half(3.0);
+		"#
+        )
+        .and_then(|_| vm.interpret("x"))
+    );
+}
+
+#[test]
+fn type_mismatches_are_reported_before_the_program_runs() {
+    let mut vm = VirtualMachine::new();
+    let result = vm.interpret("let ran = true; let x: i64 = \"four\";");
+
+    assert_eq!(
+        vec![StaticErrorKind::TypeMismatch],
+        static_error_kinds(result)
+    );
+    assert_eq!(
+        vec![StaticErrorKind::UndeclaredVariable],
+        static_error_kinds(vm.interpret("ran"))
+    );
+}
+
+#[test]
+fn modules_keep_their_globals_to_themselves() {
+    let mut vm = VirtualMachine::new();
+    vm.set_source_root(source_root(
+        "namespaces",
+        &[(
+            "Counter.newt",
+            r#"
+		let count = 0;
+		fn increment() { count = count + 1; return count; }
+		export increment;
+		"#,
+        )],
+    ));
+
+    assert_eq!(
+        Ok(NewtValue::Int(12)),
+        vm.interpret(
+            r#"
+		import Counter;
+		let count = 10;
+		Counter.increment();
+		return count + Counter.increment();
+		"#
+        )
+    );
+}
+
+#[test]
+fn modules_only_expose_their_exports() {
+    let mut vm = VirtualMachine::new();
+    vm.set_source_root(source_root(
+        "exports",
+        &[(
+            "Notepad/Store.newt",
+            "let secret = 1; export Reducers = { size: 2 };",
+        )],
+    ));
+
+    assert_eq!(
+        Ok(NewtValue::Int(2)),
+        vm.interpret("import Notepad.Store; return Store.Reducers.size;")
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::UndefinedVariable),
+        error_kind(vm.interpret("return Store.secret;"))
+    );
+}
+
+#[test]
+fn the_notepad_sample_runs() {
+    let lang = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../lang");
+    let app = std::fs::read_to_string(lang.join("Notepad/App.newt")).unwrap();
+    let mut vm = VirtualMachine::new();
+    vm.set_source_root(lang);
+
+    assert_eq!(Ok(NewtValue::Null), vm.interpret(app.as_str()));
+    assert_eq!(
+        Ok(NewtValue::from("h")),
+        vm.interpret("store.state.gapBuffer.prefix")
+    );
+    assert_eq!(
+        Ok(NewtValue::from("i")),
+        vm.interpret("store.state.gapBuffer.suffix")
+    );
+}
+
+#[test]
+fn modules_run_once_however_often_they_are_imported() {
+    let runs = Rc::new(RefCell::new(0));
+    let counter = runs.clone();
+    let mut vm = VirtualMachine::new();
+    vm.define_native("ran", 0, move |_, _| {
+        *counter.borrow_mut() += 1;
+        Ok(NewtValue::Null)
+    });
+    vm.set_source_root(source_root(
+        "once",
+        &[
+            ("Shared.newt", "ran(); export x = 1;"),
+            ("User.newt", "import Shared; export y = Shared.x + 1;"),
+        ],
+    ));
+
+    assert_eq!(
+        Ok(NewtValue::Int(3)),
+        vm.interpret("import User; import Shared; return Shared.x + User.y;")
+    );
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("User.y"));
+    assert_eq!(1, *runs.borrow());
+}
+
+#[test]
+fn missing_modules_are_reported_before_the_program_runs() {
+    let mut vm = VirtualMachine::new();
+    vm.set_source_root(source_root("missing", &[]));
+
+    assert_eq!(
+        vec![StaticErrorKind::UnresolvedImport],
+        static_error_kinds(vm.interpret("let ran = true; import Nowhere;"))
+    );
+    assert_eq!(
+        vec![StaticErrorKind::UndeclaredVariable],
+        static_error_kinds(vm.interpret("ran"))
+    );
+}
+
+#[test]
+fn import_cycles_are_reported_in_the_module_that_closes_them() {
+    let mut vm = VirtualMachine::new();
+    vm.set_source_root(source_root(
+        "cycle",
+        &[
+            ("A.newt", "import B; export a = 1;"),
+            ("B.newt", "import A; export b = 2;"),
+        ],
+    ));
+
+    match vm.interpret("import A;") {
+        Err(NewtError::Module { name, error, .. }) => {
+            assert_eq!("B", name);
+            assert_eq!(
+                vec![StaticErrorKind::ImportCycle],
+                static_error_kinds(Err(*error))
+            );
+        }
+        other => panic!("Expected an error in module B, found {:?}", other),
+    }
+}
+
+#[test]
+fn thrown_values_are_caught_by_the_nearest_catch_clause() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn check(n) { if n > 2 { throw {code: n}; } return n; }
+		let caught = [];
+		for i in [1, 2, 3] {
+			try { caught.push(check(i)); } catch (e) { caught.push(e.code * 10); }
+		}
+		let outer = 0;
+		try {
+			try { throw "inner"; } catch (e) { throw e + " again"; }
+		} catch (e) {
+			outer = e;
+		}
+		"#,
+    );
+
+    assert_eq!(
+        Ok(NewtValue::from("[1, 2, 30]")),
+        vm.interpret("\"${caught}\"")
+    );
+    assert_eq!(Ok(NewtValue::from("inner again")), vm.interpret("outer"));
+}
+
+#[test]
+fn runtime_errors_are_caught_as_objects() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn divide(n, d) {
+			return n / d;
+		}
+		let error = 0;
+		try { divide(1, 0); } catch (e) { error = e; }
+		"#,
+    );
+
+    assert_eq!(
+        Ok(NewtValue::from("DivisionByZero")),
+        vm.interpret("error.kind")
+    );
+    assert_eq!(
+        Ok(NewtValue::from("division by zero")),
+        vm.interpret("error.message")
+    );
+    assert_eq!(Ok(NewtValue::Int(3)), vm.interpret("error.line"));
+}
+
+#[test]
+fn runaway_recursion_is_a_catchable_stack_overflow() {
+    // Debug builds need more native stack for the deepest calls than test threads start with
+    let test = std::thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(|| {
+            let mut vm = VirtualMachine::new();
+            vm.interpret(
+                r#"
+				fn deep(n) {
+					if n == 0 { return 0; }
+					return deep(n - 1) + 1;
+				}
+				let error = 0;
+				try { deep(100000); } catch (e) { error = e.kind; }
+				"#,
+            );
+
+            assert_eq!(Ok(NewtValue::Int(300)), vm.interpret("deep(300)"));
+            assert_eq!(Ok(NewtValue::from("StackOverflow")), vm.interpret("error"));
+            assert_eq!(
+                Err(RuntimeErrorKind::StackOverflow),
+                error_kind(vm.interpret("deep(100000)"))
+            );
+        })
+        .expect("Could not start the test thread");
+
+    test.join().expect("The test thread panicked");
+}
+
+#[test]
+fn finally_blocks_run_however_their_try_statements_end() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		let log = [];
+		fn early(n) {
+			let kept = n;
+			try {
+				let inner = 1;
+				try { return kept + inner; } finally { log.push("inner"); }
+			} finally {
+				log.push("outer ${kept}");
+			}
+		}
+		fn overridden() { try { throw "lost"; } finally { return "kept"; } }
+		let result = early(41);
+		try { log.push("body"); } finally { log.push("done"); }
+		try { throw 1; } catch (e) { log.push("caught"); } finally { log.push("after"); }
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(42)), vm.interpret("result"));
+    assert_eq!(Ok(NewtValue::from("kept")), vm.interpret("overridden()"));
+    assert_eq!(
+        Ok(NewtValue::from(
+            "[inner, outer 41, body, done, caught, after]"
+        )),
+        vm.interpret("\"${log}\"")
+    );
+    assert_eq!(
+        Err(RuntimeErrorKind::IndexOutOfBounds),
+        error_kind(vm.interpret("try { log[10]; } finally { log.push(\"unwound\"); }"))
+    );
+    assert_eq!(Ok(NewtValue::from("unwound")), vm.interpret("log.pop()"));
+}
+
+#[test]
+fn handlers_restore_the_stack_they_were_pushed_with() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn fail() { throw "no"; }
+		let total = 0;
+		for i in [1, 2, 3] {
+			let before = i;
+			try {
+				for j in [4, 5] { let sum = before + j + fail(); }
+			} catch (e) {
+				let after = 10;
+				total = total + before + after;
+			}
+		}
+		"#,
+    );
+
+    assert_eq!(Ok(NewtValue::Int(36)), vm.interpret("total"));
+}
+
+#[test]
+fn uncaught_throws_end_the_program() {
+    let mut vm = VirtualMachine::new();
+    let error = runtime_error(vm.interpret("fn fail() {\n throw [1, 2];\n}\nfail();"));
+
+    assert_eq!(&RuntimeErrorKind::Thrown, error.kind());
+    assert_eq!("[1, 2]", error.message());
+    assert_eq!(Some(2), error.line());
+    assert_eq!(
+        vec![StaticErrorKind::UndeclaredVariable],
+        static_error_kinds(vm.interpret("try { throw 1; } catch (e) {} print(e);"))
+    );
+}
+
+#[test]
+fn break_and_continue_jump_to_the_innermost_or_labeled_loop() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		let odd = [];
+		let n = 0;
+		while true {
+			n = n + 1;
+			if n % 2 == 0 { continue; }
+			if n > 7 { break; }
+			odd.push(n);
+		}
+		let pairs = [];
+		rows: for i in range(5) {
+			let row = i * 10;
+			for j in range(5) {
+				let column = j;
+				if j == 2 { continue rows; }
+				if i == 3 { break rows; }
+				pairs.push(row + column);
+			}
+		}
+		"#,
+    );
+
+    assert_eq!(
+        Ok(NewtValue::from("[1, 3, 5, 7] [0, 1, 10, 11, 20, 21]")),
+        vm.interpret("\"${odd} ${pairs}\"")
+    );
+}
+
+#[test]
+fn match_takes_the_first_arm_whose_pattern_and_guard_match() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		struct Point { get x: i64, get y: i64 }
+		fn describe(value) {
+			return match value {
+				0 => "zero",
+				-1.5 => "negative",
+				"hi" => "greeting",
+				[] => "empty",
+				[first, ..] if first == 1 => "one and ${value.length - 1} more",
+				[a, b] => "pair of ${a} and ${b}",
+				Point { x: 0, y } => "on the y axis at ${y}",
+				Point { x, y } => "at ${x}, ${y}",
+				{ name, age: 42 } => "${name} is 42",
+				{ name } => "just ${name}",
+				n if n > 100 => "big",
+				_ => "other",
+			};
+		}
+		"#,
+    );
+
+    let cases = [
+        ("0", "zero"),
+        ("-1.5", "negative"),
+        ("\"hi\"", "greeting"),
+        ("[]", "empty"),
+        ("[1, 2, 3]", "one and 2 more"),
+        ("[2, 3]", "pair of 2 and 3"),
+        ("[2, 3, 4]", "other"),
+        ("Point { x: 0, y: 5 }", "on the y axis at 5"),
+        ("Point { x: 3, y: 4 }", "at 3, 4"),
+        ("{ name: \"ann\", age: 42 }", "ann is 42"),
+        ("{ name: \"bob\", age: 7 }", "just bob"),
+        ("500", "big"),
+        ("true", "other"),
+    ];
+
+    for (value, expected) in cases.iter() {
+        assert_eq!(
+            Ok(NewtValue::from(*expected)),
+            evaluate(&mut vm, &format!("describe({})", value))
+        );
+    }
+}
+
+#[test]
+fn match_is_an_expression_whose_bindings_can_be_captured() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		fn adder(pair) {
+			return match pair { [a, b] => fn () { return a + b; } };
+		}
+		"#,
+    );
+
+    assert_eq!(
+        Ok(NewtValue::Int(13)),
+        evaluate(&mut vm, "10 + match 2 { 1 => 0, n => adder([n, 1])() }")
+    );
+
+    let error = runtime_error(evaluate(&mut vm, "match [1] { [] => 0, [a, b] => a }"));
+
+    assert_eq!(&RuntimeErrorKind::NoMatchingArm, error.kind());
+    assert_eq!("no arm matches [1]", error.message());
+}
+
+#[test]
+fn jumps_out_of_try_statements_run_their_finally_blocks() {
+    let mut vm = VirtualMachine::new();
+    vm.interpret(
+        r#"
+		let log = [];
+		search: while true {
+			try {
+				for k in range(3) {
+					try {
+						if k == 1 { break search; }
+					} finally {
+						log.push("inner ${k}");
+					}
+				}
+			} finally {
+				log.push("outer");
+			}
+		}
+		fn last() {
+			let k = 0;
+			while true {
+				try { return k; } finally { k = k + 1; if k < 3 { continue; } }
+			}
+		}
+		let total = 0;
+		for i in range(4) {
+			try {
+				if i == 1 { continue; }
+				if i == 3 { throw "stop"; }
+				total = total + i;
+			} catch (e) {
+				break;
+			} finally {
+				total = total + 10;
+			}
+		}
+		"#,
+    );
+
+    assert_eq!(
+        Ok(NewtValue::from("[inner 0, inner 1, outer]")),
+        vm.interpret("\"${log}\"")
+    );
+    assert_eq!(Ok(NewtValue::Int(2)), vm.interpret("last()"));
+    assert_eq!(Ok(NewtValue::Int(42)), vm.interpret("total"));
+}
+
+fn error_kind(result: Result<NewtValue, NewtError>) -> Result<NewtValue, RuntimeErrorKind> {
+    result.map_err(|error| runtime_error(Err(error)).kind().clone())
+}
+
+fn runtime_error(result: Result<NewtValue, NewtError>) -> NewtRuntimeError {
+    match result {
+        Err(NewtError::Runtime(error)) => error,
+        other => panic!("Expected a runtime error, found {:?}", other),
+    }
+}
+
+fn static_error_kinds(result: Result<NewtValue, NewtError>) -> Vec<StaticErrorKind> {
+    match result {
+        Err(NewtError::Static(errors)) => errors.iter().map(|error| error.kind()).collect(),
+        other => panic!("Expected static errors, found {:?}", other),
+    }
+}
+
+fn source_root(test: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("newt-modules-{}-{}", test, std::process::id()));
+
+    for (path, source) in files {
+        let path = root.join(path);
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source).unwrap();
+    }
+
+    root
+}
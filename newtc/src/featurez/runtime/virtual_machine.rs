@@ -1,295 +1,937 @@
-use crate::featurez::newtypes::TransparentNewType;
-use crate::featurez::runtime::callable::NewtCallable;
-use crate::featurez::runtime::scope::{Environment, ScopeNode};
+use crate::featurez::analysis::{Globals, Resolver};
+use crate::featurez::runtime::builtins::define_builtins;
+use crate::featurez::runtime::callable::{BoundMethod, Closure, NativeCallable};
+use crate::featurez::runtime::chunk::{Capture, Instruction};
+use crate::featurez::runtime::compiler::Compiler;
+use crate::featurez::runtime::loader::ModuleLoader;
 use crate::featurez::runtime::Callable;
 use crate::featurez::syntax::*;
-use crate::featurez::TokenKind;
+use crate::featurez::NewtError;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
 
-#[derive(Debug)]
+// Each call runs on the native stack, so calls nested deeper than this are an error rather
+// than a crash.  Debug builds use several kilobytes of stack per call.
+const MAX_FRAMES: usize = 512;
+
+struct Handler {
+    target: usize,
+    catches: bool,
+    stack: usize,
+    iterators: usize,
+    unwinding: usize,
+}
+
 pub struct VirtualMachine {
-    scope: Environment,
+    natives: HashMap<String, NewtValue>,
+    native_globals: Globals,
+    main: Rc<NewtModule>,
+    declared_globals: Globals,
+    loader: ModuleLoader,
+    stack: Vec<NewtValue>,
+    iterators: Vec<NewtIterator>,
+    frames: usize,
+}
+
+impl Default for VirtualMachine {
+    fn default() -> VirtualMachine {
+        VirtualMachine::new()
+    }
 }
 
 impl VirtualMachine {
     pub fn new() -> VirtualMachine {
-        VirtualMachine {
-            scope: Environment::new(),
-        }
+        let mut vm = VirtualMachine {
+            natives: HashMap::new(),
+            native_globals: Globals::new(),
+            main: Rc::new(NewtModule::new("main", Vec::new())),
+            declared_globals: Globals::new(),
+            loader: ModuleLoader::new(PathBuf::from(".")),
+            stack: Vec::new(),
+            iterators: Vec::new(),
+            frames: 0,
+        };
+
+        define_builtins(&mut vm);
+
+        vm
     }
 
-    pub fn new_with_scope(scope: &Environment) -> VirtualMachine {
-        VirtualMachine {
-            scope: scope.clone(),
-        }
+    /// Callers are checked against `arity` before `function` runs.
+    pub fn define_native<F>(&mut self, symbol: &str, arity: usize, function: F)
+    where
+        F: Fn(&mut VirtualMachine, &[NewtValue]) -> NewtResult + 'static,
+    {
+        let callable = NativeCallable::new(symbol, arity, function);
+        let value = NewtValue::Callable(Rc::new(callable));
+
+        self.native_globals.define_native(symbol);
+        self.declared_globals.define_native(symbol);
+        self.main.set_global(symbol, value.clone());
+        self.natives.insert(symbol.to_string(), value);
     }
 
-    pub fn interpret<S: Into<SyntaxTree>>(&mut self, source: S) -> NewtResult {
+    pub fn set_source_root(&mut self, source_root: PathBuf) {
+        self.loader.set_source_root(source_root);
+    }
+
+    pub fn interpret<S: Into<SyntaxTree>>(&mut self, source: S) -> Result<NewtValue, NewtError> {
         let tree: SyntaxTree = source.into();
 
         if tree.errors().count() != 0 {
-            return Err(NewtRuntimeError::InvalidSyntaxTree);
+            return Err(invalid_syntax_tree().into());
         }
 
-        let node = tree
-            .root()
-            .as_node()
-            .ok_or(NewtRuntimeError::InvalidSyntaxTree)?;
-
-        let result = if let Some(expr) = ExprNode::cast(node) {
-            self.visit_expr(expr)
-        } else if let Some(stmt) = StmtNode::cast(node) {
-            self.visit_stmt(stmt).map(|f| NewtValue::Null)
-        } else {
-            panic!("All nodes should be either an Expression or Statement!");
-        };
+        let node = tree.root().as_node().ok_or_else(invalid_syntax_tree)?;
+        let mut declared_globals = self.declared_globals.clone();
+        let resolution =
+            Resolver::resolve(node, &mut declared_globals).map_err(NewtError::Static)?;
 
-        match result {
-            Ok(value) => Ok(value),
-            Err(NewtRuntimeError::ReturnedValue(value)) => Ok(value),
-            Err(error) => Err(error),
-        }
+        // A program whose imports fail to load never runs, so declares nothing.
+        self.load_imports(node)?;
+
+        let script = Compiler::compile(node, &resolution);
+        let closure = Closure::new(Rc::new(script), Vec::new(), self.main.clone());
+        let result = self.call_closure(&closure, &[]);
+
+        // Globals whose definition never ran, because the program failed first, stay free to
+        // be declared again
+        let main = self.main.clone();
+        declared_globals.retain_declared(|name| main.global(name).is_some());
+        self.declared_globals = declared_globals;
+
+        Ok(result?)
     }
-}
 
-impl ExprVisitor<NewtResult> for VirtualMachine {
-    fn visit_expr(&mut self, node: &ExprNode) -> NewtResult {
-        match node.kind() {
-            ExprKind::BinaryExpr(node) => self.visit_binary_expr(node),
-            ExprKind::UnaryExpr(node) => self.visit_unary_expr(node),
-            ExprKind::PrimitiveLiteralExpr(node) => self.visit_primitive_literal_expr(node),
-            ExprKind::ObjectLiteralExpr(node) => self.visit_object_literal_expr(node),
-            ExprKind::ObjectPropertyExpr(node) => self.visit_object_property_expr(node),
-            ExprKind::GroupingExpr(node) => self.visit_grouping_expr(node),
-            ExprKind::VariableExpr(node) => self.visit_variable_expr(node),
-            ExprKind::FunctionCallExpr(node) => self.visit_function_call_expr(node),
+    fn load_imports(&mut self, node: &SyntaxNode) -> Result<(), NewtError> {
+        for stmt in top_level_stmts(node) {
+            if let StmtKind::ImportStmt(import) = stmt.kind() {
+                self.load_module(&import.module_name(), import.range())?;
+            }
         }
+
+        Ok(())
     }
 
-    fn visit_binary_expr(&mut self, node: &BinaryExprNode) -> NewtResult {
-        let lhs = self.visit_expr(node.lhs())?;
-        let rhs = self.visit_expr(node.rhs())?;
-
-        match node.operator() {
-            TokenKind::Plus => lhs + rhs,
-            TokenKind::Minus => lhs - rhs,
-            TokenKind::Star => lhs * rhs,
-            TokenKind::Slash => lhs / rhs,
-            TokenKind::Greater => Ok(NewtValue::Bool(lhs > rhs)),
-            TokenKind::GreaterEquals => Ok(NewtValue::Bool(lhs >= rhs)),
-            TokenKind::Less => Ok(NewtValue::Bool(lhs < rhs)),
-            TokenKind::LessEquals => Ok(NewtValue::Bool(lhs <= rhs)),
-            TokenKind::EqualsEquals => Ok(NewtValue::Bool(lhs == rhs)),
-            kind => unreachable!("TokenKind {:?} is not a binary", kind),
+    fn load_module(&mut self, name: &str, range: TextRange) -> Result<(), NewtError> {
+        let source = match self.loader.begin_loading(name, range) {
+            Ok(Some(source)) => source,
+            Ok(None) => return Ok(()),
+            Err(error) => return Err(NewtError::Static(vec![error])),
+        };
+
+        match self.run_module(name, &source) {
+            Ok(module) => {
+                self.loader.finish_loading(module);
+                Ok(())
+            }
+            Err(error) => {
+                self.loader.abandon_loading();
+                Err(error.in_module(name, &source))
+            }
         }
     }
 
-    //noinspection RsTypeCheck -- faulty on the match statement
-    fn visit_unary_expr(&mut self, node: &UnaryExprNode) -> NewtResult {
-        let rhs = self.visit_expr(node.rhs())?;
+    fn run_module(&mut self, name: &str, source: &str) -> Result<Rc<NewtModule>, NewtError> {
+        let tree = SyntaxTree::from_stmts(source);
 
-        match node.operator() {
-            TokenKind::Bang => !rhs,
-            TokenKind::Minus => -rhs,
-            _ => unreachable!("not a unary"),
+        if tree.errors().count() != 0 {
+            return Err(NewtError::Syntax(tree.errors().cloned().collect()));
         }
+
+        let node = tree.root().as_node().ok_or_else(invalid_syntax_tree)?;
+        let resolution =
+            Resolver::resolve(node, &mut self.native_globals.clone()).map_err(NewtError::Static)?;
+        self.load_imports(node)?;
+
+        let exports = top_level_stmts(node)
+            .filter_map(|stmt| match stmt.kind() {
+                StmtKind::ExportStmt(export) => Some(export.identifier().lexeme().to_string()),
+                _ => None,
+            })
+            .collect();
+        let module = Rc::new(NewtModule::new(name, exports));
+        let script = Compiler::compile(node, &resolution);
+
+        self.call_closure(
+            &Closure::new(Rc::new(script), Vec::new(), module.clone()),
+            &[],
+        )?;
+        Ok(module)
     }
 
-    fn visit_primitive_literal_expr(&mut self, node: &PrimitiveLiteralExprNode) -> NewtResult {
-        let literal = node.literal();
-        let value = NewtValue::from_primitive_literal_node(node);
+    pub(crate) fn call_closure(
+        &mut self,
+        closure: &Closure,
+        arguments: &[NewtValue],
+    ) -> NewtResult {
+        if self.frames == MAX_FRAMES {
+            return Err(NewtRuntimeError::new(
+                RuntimeErrorKind::StackOverflow,
+                &format!("calls are nested more than {} deep", MAX_FRAMES),
+            ));
+        }
 
-        Ok(value)
-    }
+        let base = self.stack.len();
+        let iterator_base = self.iterators.len();
 
-    fn visit_grouping_expr(&mut self, node: &GroupingExprNode) -> NewtResult {
-        let expr = node.expr();
+        self.stack.extend_from_slice(arguments);
+        self.frames += 1;
+        let result = self.run(closure, base);
+        self.frames -= 1;
 
-        self.visit_expr(expr)
-    }
+        // Returning or failing part way through a block leaves its locals and iterators behind.
+        self.stack.truncate(base);
+        self.iterators.truncate(iterator_base);
 
-    fn visit_variable_expr(&mut self, node: &VariableExprNode) -> NewtResult {
-        self.scope
-            .resolve(node.identifier().lexeme())
-            .map(|value| value.clone())
+        result
     }
 
-    fn visit_function_call_expr(&mut self, node: &FunctionCallExprNode) -> NewtResult {
-        let callable = match self.visit_expr(node.callee())? {
-            NewtValue::Callable(callable) => callable,
-            _ => {
-                return Err(NewtRuntimeError::TypeError);
-            }
-        };
+    fn run(&mut self, closure: &Closure, base: usize) -> NewtResult {
+        let function = closure.function();
+        let chunk = &function.chunk;
+        let owner = function.owner.as_deref();
+        let mut cells: Vec<Rc<RefCell<NewtValue>>> = (0..function.cell_count)
+            .map(|_| Rc::new(RefCell::new(NewtValue::Null)))
+            .collect();
+        let mut handlers: Vec<Handler> = Vec::new();
+        // The errors held by finally handlers, to be raised again once their blocks have run
+        let mut unwinding: Vec<NewtRuntimeError> = Vec::new();
+        let mut ip = 0;
 
-        if callable.arity() != node.arguments().count() {
-            return Err(NewtRuntimeError::TypeError);
-        }
+        loop {
+            let index = ip;
+            ip += 1;
 
-        let mut arguments: Vec<NewtValue> = Vec::new();
-        for argument in node.arguments() {
-            arguments.push(self.visit_expr(argument)?);
-        }
+            let outcome = match chunk.code[index] {
+                Instruction::Constant(constant) => {
+                    self.push(chunk.constants[constant].clone());
+                    Ok(())
+                }
+                Instruction::Null => {
+                    self.push(NewtValue::Null);
+                    Ok(())
+                }
+                Instruction::Pop => {
+                    self.pop();
+                    Ok(())
+                }
+                Instruction::PopN(count) => {
+                    let length = self.stack.len();
+                    self.stack.truncate(length - count);
+                    Ok(())
+                }
+                Instruction::GetLocal(slot) => {
+                    self.push(self.stack[base + slot].clone());
+                    Ok(())
+                }
+                Instruction::SetLocal(slot) => {
+                    self.stack[base + slot] = self.pop();
+                    Ok(())
+                }
+                Instruction::DefineCell(cell) => {
+                    cells[cell] = Rc::new(RefCell::new(self.pop()));
+                    Ok(())
+                }
+                Instruction::GetCell(cell) => {
+                    self.push(cells[cell].borrow().clone());
+                    Ok(())
+                }
+                Instruction::SetCell(cell) => {
+                    *cells[cell].borrow_mut() = self.pop();
+                    Ok(())
+                }
+                Instruction::GetUpvalue(upvalue) => {
+                    self.push(closure.upvalue(upvalue).borrow().clone());
+                    Ok(())
+                }
+                Instruction::SetUpvalue(upvalue) => {
+                    *closure.upvalue(upvalue).borrow_mut() = self.pop();
+                    Ok(())
+                }
+                Instruction::DefineGlobal(name) => {
+                    let value = self.pop();
+                    closure.module().define_global(&chunk.names[name], value);
+                    Ok(())
+                }
+                Instruction::GetGlobal(name) => {
+                    self.get_global(closure.module(), &chunk.names[name])
+                }
+                Instruction::SetGlobal(name) => {
+                    self.set_global(closure.module(), &chunk.names[name])
+                }
+                Instruction::Import(name) => {
+                    let module = self
+                        .loader
+                        .module(&chunk.names[name])
+                        .expect("Imports are loaded before the importing module runs")
+                        .clone();
+
+                    self.push(NewtValue::Module(module));
+                    Ok(())
+                }
+                Instruction::GetProperty(name) => {
+                    let source = self.pop();
+                    get_property(source, &chunk.names[name], owner).map(|value| self.push(value))
+                }
+                Instruction::SetProperty(name) => {
+                    let destination = self.pop();
+                    let value = self.pop();
+                    set_property(destination, &chunk.names[name], &value, owner)
+                }
+                Instruction::GetIndex => {
+                    let index = self.pop();
+                    let source = self.pop();
+                    get_index(source, index).map(|value| self.push(value))
+                }
+                Instruction::SetIndex => {
+                    let index = self.pop();
+                    let destination = self.pop();
+                    let value = self.pop();
+                    set_index(destination, index, &value)
+                }
+                Instruction::Array(count) => {
+                    let elements = self.stack.split_off(self.stack.len() - count);
+                    self.push(NewtArray::from(elements).into());
+                    Ok(())
+                }
+                Instruction::Interpolate(count) => {
+                    let parts = self.stack.split_off(self.stack.len() - count);
+                    let text: String = parts.iter().map(NewtValue::to_string).collect();
+
+                    self.push(NewtValue::String(NewtString::new(&text)));
+                    Ok(())
+                }
+                Instruction::Object(count) => {
+                    let fields = self.stack.split_off(self.stack.len() - count * 2);
+                    let mut object = NewtObject::new();
+
+                    for field in fields.chunks(2) {
+                        object.set(&field[0].to_string(), &field[1]);
+                    }
+
+                    self.push(object.into());
+                    Ok(())
+                }
+                Instruction::Struct(count) => {
+                    let fields = self.stack.split_off(self.stack.len() - count * 2);
+                    let struct_type = self.pop();
 
-        callable.call(self, &arguments)
+                    instantiate(struct_type, fields).map(|value| self.push(value))
+                }
+                Instruction::DefineMethod { name, is_static } => {
+                    let method = self.pop();
+                    let implemented = self.stack.last().expect("impl blocks push their type");
+
+                    define_method(implemented, &chunk.names[name], method, is_static)
+                }
+                Instruction::Implement => {
+                    let interface = self.pop();
+                    let provided = self.pop();
+                    let implemented = self.stack.last().expect("impl blocks push their type");
+
+                    implement(implemented, &provided, interface)
+                }
+                Instruction::Add => self.binary(|lhs, rhs| lhs + rhs),
+                Instruction::Subtract => self.binary(|lhs, rhs| lhs - rhs),
+                Instruction::Multiply => self.binary(|lhs, rhs| lhs * rhs),
+                Instruction::Divide => self.binary(|lhs, rhs| lhs / rhs),
+                Instruction::Modulo => self.binary(|lhs, rhs| lhs % rhs),
+                Instruction::Power => self.binary(NewtValue::pow),
+                Instruction::BitAnd => self.binary(|lhs, rhs| lhs & rhs),
+                Instruction::BitOr => self.binary(|lhs, rhs| lhs | rhs),
+                Instruction::BitXor => self.binary(|lhs, rhs| lhs ^ rhs),
+                Instruction::ShiftLeft => self.binary(|lhs, rhs| lhs << rhs),
+                Instruction::ShiftRight => self.binary(|lhs, rhs| lhs >> rhs),
+                Instruction::Equal => self.binary(|lhs, rhs| Ok(NewtValue::Bool(lhs == rhs))),
+                Instruction::Greater => self.binary(|lhs, rhs| Ok(NewtValue::Bool(lhs > rhs))),
+                Instruction::GreaterEqual => {
+                    self.binary(|lhs, rhs| Ok(NewtValue::Bool(lhs >= rhs)))
+                }
+                Instruction::Less => self.binary(|lhs, rhs| Ok(NewtValue::Bool(lhs < rhs))),
+                Instruction::LessEqual => self.binary(|lhs, rhs| Ok(NewtValue::Bool(lhs <= rhs))),
+                Instruction::Negate => {
+                    let value = self.pop();
+                    (-value).map(|value| self.push(value))
+                }
+                Instruction::Not => {
+                    let value = self.pop();
+                    (!value).map(|value| self.push(value))
+                }
+                Instruction::Jump(target) => {
+                    ip = target;
+                    Ok(())
+                }
+                Instruction::JumpIfFalse(target) => {
+                    let condition = self.pop();
+
+                    condition
+                        .as_truthy()
+                        .ok_or_else(|| condition_type_error(&condition))
+                        .map(|truthy| {
+                            if !truthy {
+                                ip = target;
+                            }
+                        })
+                }
+                Instruction::PushIterator => {
+                    let iterable = self.pop();
+                    iterable
+                        .iter()
+                        .map(|iterator| self.iterators.push(iterator))
+                }
+                Instruction::IterateNext(exit) => {
+                    let iterator = self.iterators.last_mut().expect("No iterator to advance");
+
+                    match iterator.next() {
+                        Some(value) => self.push(value),
+                        None => ip = exit,
+                    }
+
+                    Ok(())
+                }
+                Instruction::PopIterator => {
+                    self.iterators.pop();
+                    Ok(())
+                }
+                Instruction::PushCatch(target) => {
+                    handlers.push(self.handler(target, true, unwinding.len()));
+                    Ok(())
+                }
+                Instruction::PushFinally(target) => {
+                    handlers.push(self.handler(target, false, unwinding.len()));
+                    Ok(())
+                }
+                Instruction::PopHandler => {
+                    handlers.pop();
+                    Ok(())
+                }
+                Instruction::MatchArray { length, rest } => {
+                    let matches = match self.pop() {
+                        NewtValue::Array(array) if rest => array.len() >= length,
+                        NewtValue::Array(array) => array.len() == length,
+                        _ => false,
+                    };
+
+                    self.push(NewtValue::Bool(matches));
+                    Ok(())
+                }
+                Instruction::HasProperty(name) => {
+                    let name = &chunk.names[name];
+                    let matches = match self.pop() {
+                        NewtValue::Object(object) => object.get(name).is_some(),
+                        NewtValue::Struct(instance) => instance.struct_type().has_field(name),
+                        _ => false,
+                    };
+
+                    self.push(NewtValue::Bool(matches));
+                    Ok(())
+                }
+                Instruction::MatchStruct => {
+                    let struct_type = self.pop();
+                    let value = self.pop();
+
+                    match_struct(value, struct_type).map(|matches| self.push(matches))
+                }
+                Instruction::NoMatch => {
+                    let value = self.pop();
+
+                    Err(NewtRuntimeError::new(
+                        RuntimeErrorKind::NoMatchingArm,
+                        &format!("no arm matches {}", value),
+                    ))
+                }
+                Instruction::Throw => {
+                    let value = self.pop();
+                    Err(NewtRuntimeError::thrown(value))
+                }
+                Instruction::Rethrow => Err(unwinding
+                    .pop()
+                    .expect("Finally blocks only end in a rethrow when unwinding")),
+                Instruction::Closure(index) => {
+                    let function = chunk.functions[index].clone();
+                    let upvalues = function
+                        .captures
+                        .iter()
+                        .map(|capture| match capture {
+                            Capture::Cell(cell) => cells[*cell].clone(),
+                            Capture::Upvalue(upvalue) => closure.upvalue(*upvalue).clone(),
+                        })
+                        .collect();
+
+                    self.push(NewtValue::Callable(Rc::new(Closure::new(
+                        function,
+                        upvalues,
+                        closure.module().clone(),
+                    ))));
+                    Ok(())
+                }
+                Instruction::Call(argument_count) => {
+                    let arguments = self.stack.split_off(self.stack.len() - argument_count);
+                    let callee = self.pop();
+
+                    self.call(callee, &arguments, chunk.spans[index])
+                        .map(|value| self.push(value))
+                }
+                Instruction::Return => return Ok(self.pop()),
+            };
+
+            if let Err(error) = outcome {
+                let error = error
+                    .with_range(chunk.spans[index])
+                    .with_line(chunk.lines[index]);
+                let handler = match handlers.pop() {
+                    Some(handler) => handler,
+                    None => return Err(error),
+                };
+
+                self.stack.truncate(handler.stack);
+                self.iterators.truncate(handler.iterators);
+                unwinding.truncate(handler.unwinding);
+
+                if handler.catches {
+                    self.push(error.value());
+                } else {
+                    unwinding.push(error);
+                }
+                ip = handler.target;
+            }
+        }
     }
 
-    fn visit_object_literal_expr(
+    fn call(
         &mut self,
-        node: &ObjectLiteralExprNode,
-    ) -> Result<NewtValue, NewtRuntimeError> {
-        let mut object = NewtObject::new();
+        callee: NewtValue,
+        arguments: &[NewtValue],
+        call_site: TextRange,
+    ) -> NewtResult {
+        let callable = match callee {
+            NewtValue::Callable(callable) => callable,
+            value => {
+                return Err(NewtRuntimeError::type_error(&format!(
+                    "cannot call {}",
+                    value.type_name()
+                )));
+            }
+        };
 
-        for pair in node.fields().iter() {
-            object.set(pair.0, &self.visit_expr(pair.1)?);
+        if callable.arity() != arguments.len() {
+            return Err(NewtRuntimeError::new(
+                RuntimeErrorKind::ArityMismatch,
+                &format!(
+                    "{} expects {} argument(s) but was given {}",
+                    callable.symbol(),
+                    callable.arity(),
+                    arguments.len()
+                ),
+            ));
         }
 
-        Ok(object.into())
+        callable.call(self, arguments).map_err(|error| {
+            error
+                .with_range(call_site)
+                .with_frame(StackFrame::new(callable.symbol(), Some(call_site)))
+        })
     }
 
-    fn visit_object_property_expr(
-        &mut self,
-        node: &ObjectPropertyExprNode,
-    ) -> Result<NewtValue, NewtRuntimeError> {
-        match self.visit_expr(node.source_expr())? {
-            NewtValue::Object(object) => object
-                .get(node.identifier().lexeme())
-                .map(|reference| reference.clone())
-                .ok_or(NewtRuntimeError::UndefinedVariable),
-            _ => Err(NewtRuntimeError::TypeError),
+    fn handler(&self, target: usize, catches: bool, unwinding: usize) -> Handler {
+        Handler {
+            target,
+            catches,
+            stack: self.stack.len(),
+            iterators: self.iterators.len(),
+            unwinding,
         }
     }
-}
 
-impl StmtVisitor<Result<(), NewtRuntimeError>> for VirtualMachine {
-    fn visit_stmt(&mut self, node: &StmtNode) -> Result<(), NewtRuntimeError> {
-        match node.kind() {
-            StmtKind::VariableDeclarationStmt(node) => {
-                self.visit_variable_declaration_stmt(node)?
-            }
-            StmtKind::AssignmentStmt(node) => self.visit_assignment_stmt(node)?,
-            StmtKind::StmtListStmt(node) => self.visit_stmt_list_stmt(node)?,
-            StmtKind::ExprStmt(node) => self.visit_expr_stmt(node)?,
-            StmtKind::IfStmt(node) => self.visit_if_stmt(node)?,
-            StmtKind::WhileStmt(node) => self.visit_while_stmt(node)?,
-            StmtKind::FunctionDeclarationStmt(node) => {
-                self.visit_function_declaration_stmt(node)?
-            }
-            StmtKind::ReturnStmt(node) => self.visit_return_stmt(node)?,
-        };
+    fn get_global(&mut self, module: &NewtModule, name: &str) -> Result<(), NewtRuntimeError> {
+        let value = module
+            .global(name)
+            .or_else(|| self.natives.get(name).cloned())
+            .ok_or_else(|| NewtRuntimeError::undefined_variable(name))?;
 
+        self.push(value);
         Ok(())
     }
 
-    fn visit_variable_declaration_stmt(
-        &mut self,
-        node: &VariableDeclarationStmtNode,
-    ) -> Result<(), NewtRuntimeError> {
-        let result = self.visit_expr(node.expr())?;
-        let identifier = node.identifier().lexeme();
-        let value = self.visit_expr(node.expr())?;
+    fn set_global(&mut self, module: &NewtModule, name: &str) -> Result<(), NewtRuntimeError> {
+        let value = self.pop();
 
-        self.scope.bind(&identifier, value)?;
+        if module.set_global(name, value.clone()) {
+            return Ok(());
+        }
 
+        // Assigning to a native shadows it within the assigning module.
+        if !self.natives.contains_key(name) {
+            return Err(NewtRuntimeError::undefined_variable(name));
+        }
+
+        module.define_global(name, value);
         Ok(())
     }
 
-    fn visit_assignment_stmt(&mut self, node: &AssignmentStmtNode) -> Result<(), NewtRuntimeError> {
-        let value = self.visit_expr(node.expr())?;
+    fn binary<F>(&mut self, operation: F) -> Result<(), NewtRuntimeError>
+    where
+        F: FnOnce(NewtValue, NewtValue) -> NewtResult,
+    {
+        let rhs = self.pop();
+        let lhs = self.pop();
+
+        self.push(operation(lhs, rhs)?);
+        Ok(())
+    }
 
-        match node.rval().kind() {
-            RValKind::VariableRVal(variable) => {
-                self.scope.assign(variable.identifier().lexeme(), value)
+    fn push(&mut self, value: NewtValue) {
+        self.stack.push(value);
+    }
+
+    fn pop(&mut self) -> NewtValue {
+        self.stack
+            .pop()
+            .expect("The compiler keeps the stack balanced")
+    }
+}
+
+fn get_property(source: NewtValue, property: &str, owner: Option<&str>) -> NewtResult {
+    match source {
+        NewtValue::Object(object) => object.get(property).ok_or_else(|| {
+            NewtRuntimeError::new(
+                RuntimeErrorKind::UndefinedVariable,
+                &format!("object has no property '{}'", property),
+            )
+        }),
+        NewtValue::Array(array) => array_property(array, property),
+        NewtValue::String(string) => string_property(string, property),
+        NewtValue::Module(module) => module.export(property),
+        NewtValue::Struct(instance) => {
+            if instance.struct_type().has_field(property) {
+                return instance.get(property, owner);
             }
-            RValKind::ObjectPropertyRVal(property) => {
-                let mut destination = self.visit_expr(property.source_expr())?;
-                match destination {
-                    NewtValue::Object(mut object) => {
-                        object.set(property.identifier().lexeme(), &value);
-                        Ok(())
-                    }
-                    _ => Err(NewtRuntimeError::TypeError),
+
+            match instance.struct_type().method(property) {
+                Some(ref method) if method.is_static() => {
+                    Err(NewtRuntimeError::type_error(&format!(
+                        "'{}' is a static method of {}",
+                        property,
+                        instance.struct_type().name()
+                    )))
+                }
+                Some(method) => {
+                    let receiver = NewtValue::Struct(instance.clone());
+                    let bound = BoundMethod::new(receiver, method.function().clone());
+
+                    Ok(NewtValue::Callable(Rc::new(bound)))
                 }
+                None => Err(NewtRuntimeError::new(
+                    RuntimeErrorKind::UndefinedVariable,
+                    &format!(
+                        "{} has no field or method '{}'",
+                        instance.struct_type().name(),
+                        property
+                    ),
+                )),
             }
         }
+        NewtValue::StructType(struct_type) => struct_type
+            .method(property)
+            .map(|method| NewtValue::Callable(method.function().clone()))
+            .ok_or_else(|| {
+                NewtRuntimeError::new(
+                    RuntimeErrorKind::UndefinedVariable,
+                    &format!("{} has no method '{}'", struct_type.name(), property),
+                )
+            }),
+        value => Err(NewtRuntimeError::type_error(&format!(
+            "cannot read property '{}' of {}",
+            property,
+            value.type_name()
+        ))),
     }
+}
 
-    fn visit_stmt_list_stmt(&mut self, node: &StmtListStmtNode) -> Result<(), NewtRuntimeError> {
-        if node.has_braces() {
-            self.scope.push_scope();
+fn set_property(
+    destination: NewtValue,
+    property: &str,
+    value: &NewtValue,
+    owner: Option<&str>,
+) -> Result<(), NewtRuntimeError> {
+    match destination {
+        NewtValue::Object(mut object) => {
+            object.set(property, value);
+            Ok(())
         }
+        NewtValue::Struct(instance) => instance.set(property, value, owner),
+        value => Err(NewtRuntimeError::type_error(&format!(
+            "cannot set property '{}' of {}",
+            property,
+            value.type_name()
+        ))),
+    }
+}
 
-        for stmt in node.stmts() {
-            self.visit_stmt(stmt)?;
+fn define_method(
+    implemented: &NewtValue,
+    name: &str,
+    method: NewtValue,
+    is_static: bool,
+) -> Result<(), NewtRuntimeError> {
+    match (implemented, method) {
+        (NewtValue::StructType(struct_type), NewtValue::Callable(function)) => {
+            struct_type.define_method(name, StructMethod::new(function, is_static));
+            Ok(())
         }
+        (value, _) => Err(NewtRuntimeError::type_error(&format!(
+            "cannot implement methods for {}",
+            value.type_name()
+        ))),
+    }
+}
 
-        if node.has_braces() {
-            self.scope.pop_scope();
+fn implement(
+    implemented: &NewtValue,
+    provided: &NewtValue,
+    interface: NewtValue,
+) -> Result<(), NewtRuntimeError> {
+    match (implemented, provided, interface) {
+        (
+            NewtValue::StructType(struct_type),
+            NewtValue::Interface(provided),
+            NewtValue::Interface(interface),
+        ) => struct_type.implement(interface, provided),
+        (NewtValue::StructType(_), _, value) => Err(NewtRuntimeError::type_error(&format!(
+            "cannot implement {} as an interface",
+            value.type_name()
+        ))),
+        (value, _, _) => Err(NewtRuntimeError::type_error(&format!(
+            "cannot implement methods for {}",
+            value.type_name()
+        ))),
+    }
+}
+
+fn match_struct(value: NewtValue, struct_type: NewtValue) -> NewtResult {
+    match (value, struct_type) {
+        (NewtValue::Struct(instance), NewtValue::StructType(struct_type)) => Ok(NewtValue::Bool(
+            Rc::ptr_eq(instance.struct_type(), &struct_type),
+        )),
+        (_, NewtValue::StructType(_)) => Ok(NewtValue::Bool(false)),
+        (_, value) => Err(NewtRuntimeError::type_error(&format!(
+            "cannot match against {}",
+            value.type_name()
+        ))),
+    }
+}
+
+fn instantiate(struct_type: NewtValue, fields: Vec<NewtValue>) -> NewtResult {
+    let struct_type = match struct_type {
+        NewtValue::StructType(struct_type) => struct_type,
+        value => {
+            return Err(NewtRuntimeError::type_error(&format!(
+                "cannot construct {}",
+                value.type_name()
+            )))
         }
+    };
+    let initializers = fields
+        .chunks(2)
+        .map(|field| (field[0].to_string(), field[1].clone()))
+        .collect();
 
-        Ok(())
+    NewtStruct::new(struct_type, initializers).map(NewtValue::Struct)
+}
+
+fn get_index(source: NewtValue, index: NewtValue) -> NewtResult {
+    match source {
+        NewtValue::Array(array) => {
+            let index = checked_index(index, array.len(), "an array")?;
+            Ok(array.get(index).expect("checked_index checks bounds"))
+        }
+        NewtValue::String(string) => {
+            let index = checked_index(index, string.len(), "a string")?;
+            Ok(NewtValue::Glyph(
+                string.char_at(index).expect("checked_index checks bounds"),
+            ))
+        }
+        value => Err(index_type_error(&value)),
     }
+}
 
-    fn visit_expr_stmt(&mut self, node: &ExprStmtNode) -> Result<(), NewtRuntimeError> {
-        self.visit_expr(node.expr())?;
-        Ok(())
+fn set_index(
+    destination: NewtValue,
+    index: NewtValue,
+    value: &NewtValue,
+) -> Result<(), NewtRuntimeError> {
+    let mut array = match destination {
+        NewtValue::Array(array) => array,
+        value => return Err(index_type_error(&value)),
+    };
+    let index = checked_index(index, array.len(), "an array")?;
+
+    array.set(index, value);
+    Ok(())
+}
+
+fn top_level_stmts(node: &SyntaxNode) -> impl Iterator<Item = &StmtNode> {
+    node.children()
+        .iter()
+        .filter_map(|n| n.as_node())
+        .filter_map(StmtNode::cast)
+}
+
+fn invalid_syntax_tree() -> NewtRuntimeError {
+    NewtRuntimeError::new(
+        RuntimeErrorKind::InvalidSyntaxTree,
+        "cannot run source that failed to parse",
+    )
+}
+
+fn checked_index(index: NewtValue, length: usize, source: &str) -> Result<usize, NewtRuntimeError> {
+    match index {
+        NewtValue::Int(i) if i >= 0 && (i as usize) < length => Ok(i as usize),
+        NewtValue::Int(i) => Err(NewtRuntimeError::new(
+            RuntimeErrorKind::IndexOutOfBounds,
+            &format!(
+                "index {} is out of bounds for {} of length {}",
+                i, source, length
+            ),
+        )),
+        value => Err(NewtRuntimeError::type_error(&format!(
+            "index into {} must be int, found {}",
+            source,
+            value.type_name()
+        ))),
     }
+}
 
-    fn visit_if_stmt(&mut self, node: &IfStmtNode) -> Result<(), NewtRuntimeError> {
-        let result = self.visit_expr(node.condition())?;
-        let truthiness = result.as_truthy();
+fn index_type_error(value: &NewtValue) -> NewtRuntimeError {
+    NewtRuntimeError::type_error(&format!("cannot index into {}", value.type_name()))
+}
 
-        match truthiness {
-            Some(conditional) => {
-                if conditional {
-                    self.visit_stmt_list_stmt(node.when_true())?;
-                } else {
-                    if let Some(else_path) = node.when_false() {
-                        self.visit_stmt_list_stmt(else_path)?;
-                    }
-                }
-            }
-            None => Err(NewtRuntimeError::TypeError)?,
+fn condition_type_error(value: &NewtValue) -> NewtRuntimeError {
+    NewtRuntimeError::type_error(&format!(
+        "condition must be bool or int, found {}",
+        value.type_name()
+    ))
+}
+
+fn array_property(array: NewtArray, property: &str) -> NewtResult {
+    let method = match property {
+        "length" => return Ok(NewtValue::Int(array.len() as i64)),
+        "push" => NativeCallable::new("push", 1, move |_, arguments| {
+            array.clone().push(&arguments[0]);
+            Ok(NewtValue::Null)
+        }),
+        "pop" => NativeCallable::new("pop", 0, move |_, _| {
+            array.clone().pop().ok_or_else(|| {
+                NewtRuntimeError::new(
+                    RuntimeErrorKind::IndexOutOfBounds,
+                    "cannot pop from an empty array",
+                )
+            })
+        }),
+        _ => {
+            return Err(NewtRuntimeError::new(
+                RuntimeErrorKind::UndefinedVariable,
+                &format!("array has no property '{}'", property),
+            ))
         }
+    };
 
-        Ok(())
-    }
+    Ok(NewtValue::Callable(Rc::new(method)))
+}
 
-    fn visit_while_stmt(&mut self, node: &WhileStmtNode) -> Result<(), NewtRuntimeError> {
-        loop {
-            let conditional = self.visit_expr(node.condition())?;
-            let truthy_conditional = conditional.as_truthy().ok_or(NewtRuntimeError::TypeError)?;
+fn string_property(string: NewtString, property: &str) -> NewtResult {
+    let method = match property {
+        "length" => return Ok(NewtValue::Int(string.len() as i64)),
+        "slice" => NativeCallable::new("slice", 2, move |_, arguments| {
+            let start = int_argument("slice", &arguments[0])?;
+            let end = int_argument("slice", &arguments[1])?;
 
-            if !truthy_conditional {
-                break;
+            let slice = if start < 0 || end < 0 {
+                None
             } else {
-                self.visit_stmt_list_stmt(node.stmts())?;
-            }
+                string.slice(start as usize, end as usize)
+            };
+
+            slice.map(NewtValue::String).ok_or_else(|| {
+                NewtRuntimeError::new(
+                    RuntimeErrorKind::IndexOutOfBounds,
+                    &format!(
+                        "cannot slice {}..{} from a string of length {}",
+                        start,
+                        end,
+                        string.len()
+                    ),
+                )
+            })
+        }),
+        "split" => NativeCallable::new("split", 1, move |_, arguments| {
+            let separator = string_argument("split", &arguments[0])?;
+            let pieces: Vec<NewtValue> = string
+                .split(separator.as_str())
+                .into_iter()
+                .map(NewtValue::String)
+                .collect();
+
+            Ok(NewtArray::from(pieces).into())
+        }),
+        "find" => NativeCallable::new("find", 1, move |_, arguments| {
+            let needle = string_argument("find", &arguments[0])?;
+            let index = string
+                .find(needle.as_str())
+                .map_or(-1, |index| index as i64);
+
+            Ok(NewtValue::Int(index))
+        }),
+        "replace" => NativeCallable::new("replace", 2, move |_, arguments| {
+            let from = string_argument("replace", &arguments[0])?;
+            let to = string_argument("replace", &arguments[1])?;
+            let replaced = string.as_str().replace(from.as_str(), to.as_str());
+
+            Ok(NewtValue::String(NewtString::new(&replaced)))
+        }),
+        "upper" => NativeCallable::new("upper", 0, move |_, _| {
+            Ok(NewtValue::String(NewtString::new(
+                &string.as_str().to_uppercase(),
+            )))
+        }),
+        "lower" => NativeCallable::new("lower", 0, move |_, _| {
+            Ok(NewtValue::String(NewtString::new(
+                &string.as_str().to_lowercase(),
+            )))
+        }),
+        _ => {
+            return Err(NewtRuntimeError::new(
+                RuntimeErrorKind::UndefinedVariable,
+                &format!("string has no property '{}'", property),
+            ))
         }
+    };
 
-        Ok(())
-    }
-
-    fn visit_function_declaration_stmt(
-        &mut self,
-        node: &FunctionDeclarationStmtNode,
-    ) -> Result<(), NewtRuntimeError> {
-        let callable = NewtCallable::new(node, &self.scope);
-        self.scope.bind(
-            node.identifier().lexeme(),
-            NewtValue::Callable(Rc::new(callable)),
-        )?;
+    Ok(NewtValue::Callable(Rc::new(method)))
+}
 
-        Ok(())
+fn int_argument(method: &str, value: &NewtValue) -> Result<i64, NewtRuntimeError> {
+    match value {
+        NewtValue::Int(i) => Ok(*i),
+        value => Err(method_argument_error(method, "int", value)),
     }
+}
 
-    fn visit_return_stmt(&mut self, node: &ReturnStmtNode) -> Result<(), NewtRuntimeError> {
-        match node.result() {
-            Some(expr) => {
-                let ok_is_err = self.visit_expr(expr)?;
-                Err(NewtRuntimeError::ReturnedValue(ok_is_err))
-            }
-            None => Err(NewtRuntimeError::NullValueEncountered),
-        }
+fn string_argument(method: &str, value: &NewtValue) -> Result<NewtString, NewtRuntimeError> {
+    match value {
+        NewtValue::String(string) => Ok(string.clone()),
+        value => Err(method_argument_error(method, "string", value)),
     }
 }
+
+fn method_argument_error(method: &str, expected: &str, value: &NewtValue) -> NewtRuntimeError {
+    NewtRuntimeError::type_error(&format!(
+        "{} expects {} but found {}",
+        method,
+        expected,
+        value.type_name()
+    ))
+}
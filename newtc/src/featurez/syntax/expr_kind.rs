@@ -6,7 +6,13 @@ pub enum ExprKind<'a> {
     PrimitiveLiteralExpr(&'a PrimitiveLiteralExprNode),
     ObjectLiteralExpr(&'a ObjectLiteralExprNode),
     ObjectPropertyExpr(&'a ObjectPropertyExprNode),
+    ArrayLiteralExpr(&'a ArrayLiteralExprNode),
+    IndexExpr(&'a IndexExprNode),
     GroupingExpr(&'a GroupingExprNode),
     VariableExpr(&'a VariableExprNode),
     FunctionCallExpr(&'a FunctionCallExprNode),
+    StructLiteralExpr(&'a StructLiteralExprNode),
+    FunctionExpr(&'a FunctionExprNode),
+    InterpolatedStringExpr(&'a InterpolatedStringExprNode),
+    MatchExpr(&'a MatchExprNode),
 }
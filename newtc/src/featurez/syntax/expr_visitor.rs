@@ -10,4 +10,10 @@ pub trait ExprVisitor<R> {
     fn visit_function_call_expr(&mut self, node: &FunctionCallExprNode) -> R;
     fn visit_object_literal_expr(&mut self, node: &ObjectLiteralExprNode) -> R;
     fn visit_object_property_expr(&mut self, node: &ObjectPropertyExprNode) -> R;
+    fn visit_array_literal_expr(&mut self, node: &ArrayLiteralExprNode) -> R;
+    fn visit_index_expr(&mut self, node: &IndexExprNode) -> R;
+    fn visit_struct_literal_expr(&mut self, node: &StructLiteralExprNode) -> R;
+    fn visit_function_expr(&mut self, node: &FunctionExprNode) -> R;
+    fn visit_interpolated_string_expr(&mut self, node: &InterpolatedStringExprNode) -> R;
+    fn visit_match_expr(&mut self, node: &MatchExprNode) -> R;
 }
@@ -1,12 +1,18 @@
 mod ast_node;
 mod expr_kind;
 mod expr_visitor;
+mod newt_array;
+mod newt_interface;
+mod newt_iterator;
+mod newt_module;
 mod newt_object;
 mod newt_runtime_error;
 mod newt_static_error;
 mod newt_string;
+mod newt_struct;
 mod newt_value;
 mod nodes;
+mod pattern_kind;
 mod rval_kind;
 mod stmt_kind;
 mod stmt_visitor;
@@ -16,6 +22,8 @@ mod syntax_node;
 mod syntax_token;
 mod syntax_tree;
 mod tests;
+mod text_edit;
+mod text_range;
 mod text_tree_sink;
 mod token_source;
 mod tree_sink;
@@ -23,12 +31,18 @@ mod tree_sink;
 pub use self::ast_node::AstNode;
 pub use self::expr_kind::ExprKind;
 pub use self::expr_visitor::ExprVisitor;
+pub use self::newt_array::NewtArray;
+pub use self::newt_interface::{Interface, InterfaceMethod};
+pub use self::newt_iterator::NewtIterator;
+pub use self::newt_module::NewtModule;
 pub use self::newt_object::NewtObject;
-pub use self::newt_runtime_error::NewtRuntimeError;
-pub use self::newt_static_error::NewtStaticError;
-pub use self::newt_string::NewtString;
+pub use self::newt_runtime_error::{NewtRuntimeError, RuntimeErrorKind, StackFrame};
+pub use self::newt_static_error::{NewtStaticError, StaticErrorKind};
+pub use self::newt_string::{literal_text, unescape, NewtString};
+pub use self::newt_struct::{NewtStruct, StructField, StructMethod, StructType};
 pub use self::newt_value::NewtValue;
 pub use self::nodes::*;
+pub use self::pattern_kind::PatternKind;
 pub use self::rval_kind::RValKind;
 pub use self::stmt_kind::StmtKind;
 pub use self::stmt_visitor::StmtVisitor;
@@ -36,7 +50,9 @@ pub use self::syntax_element::SyntaxElement;
 pub use self::syntax_kind::SyntaxKind;
 pub use self::syntax_node::SyntaxNode;
 pub use self::syntax_token::SyntaxToken;
-pub use self::syntax_tree::SyntaxTree;
+pub use self::syntax_tree::{ErrorReport, SyntaxTree};
+pub use self::text_edit::TextEdit;
+pub use self::text_range::TextRange;
 pub use self::text_tree_sink::TextTreeSink;
 pub use self::token_source::TokenSource;
 pub use self::tree_sink::TreeSink;
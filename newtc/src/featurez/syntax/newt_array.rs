@@ -0,0 +1,78 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::featurez::syntax::NewtValue;
+use std::fmt::{Debug, Display, Error, Formatter};
+
+type InternalArray = Rc<RefCell<Vec<NewtValue>>>;
+
+#[derive(Clone)]
+pub struct NewtArray(InternalArray);
+
+impl NewtArray {
+    pub fn new() -> NewtArray {
+        NewtArray(Rc::new(RefCell::new(Vec::new())))
+    }
+
+    pub fn ptr_eq(&self, other: &NewtArray) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    pub fn get(&self, index: usize) -> Option<NewtValue> {
+        self.0.borrow().get(index).map(|v| v.clone())
+    }
+
+    pub fn set(&mut self, index: usize, value: &NewtValue) -> bool {
+        match self.0.borrow_mut().get_mut(index) {
+            Some(slot) => {
+                *slot = value.clone();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn push(&mut self, value: &NewtValue) -> &mut Self {
+        self.0.borrow_mut().push(value.clone());
+
+        self
+    }
+
+    pub fn pop(&mut self) -> Option<NewtValue> {
+        self.0.borrow_mut().pop()
+    }
+
+    pub fn elements(&self) -> Vec<NewtValue> {
+        self.0.borrow().clone()
+    }
+}
+
+impl From<Vec<NewtValue>> for NewtArray {
+    fn from(elements: Vec<NewtValue>) -> Self {
+        NewtArray(Rc::new(RefCell::new(elements)))
+    }
+}
+
+impl Display for NewtArray {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "[")?;
+        for (index, element) in self.0.borrow().iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}", element)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl Debug for NewtArray {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{:?}", self.0.borrow())
+    }
+}
@@ -0,0 +1,52 @@
+/// A method an interface requires.  Its arity counts `self`, as a method's own arity does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceMethod {
+    name: String,
+    arity: usize,
+    is_static: bool,
+}
+
+impl InterfaceMethod {
+    pub fn new(name: &str, arity: usize, is_static: bool) -> InterfaceMethod {
+        InterfaceMethod {
+            name: name.to_string(),
+            arity,
+            is_static,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Interface {
+    name: String,
+    methods: Vec<InterfaceMethod>,
+}
+
+impl Interface {
+    pub fn new(name: &str, methods: Vec<InterfaceMethod>) -> Interface {
+        Interface {
+            name: name.to_string(),
+            methods,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn methods(&self) -> &[InterfaceMethod] {
+        &self.methods
+    }
+}
@@ -0,0 +1,38 @@
+use crate::featurez::syntax::{NewtRuntimeError, NewtString, NewtValue};
+
+pub struct NewtIterator(Box<dyn Iterator<Item = NewtValue>>);
+
+impl NewtIterator {
+    pub fn new(iterable: &NewtValue) -> Result<NewtIterator, NewtRuntimeError> {
+        let iterator: Box<dyn Iterator<Item = NewtValue>> = match iterable {
+            NewtValue::Array(array) => Box::new(array.elements().into_iter()),
+            NewtValue::String(string) => {
+                let glyphs: Vec<char> = string.as_str().chars().collect();
+                Box::new(glyphs.into_iter().map(NewtValue::Glyph))
+            }
+            NewtValue::Object(object) => Box::new(
+                object
+                    .keys()
+                    .into_iter()
+                    .map(|key| NewtValue::String(NewtString::new(&key))),
+            ),
+            NewtValue::Range(start, end) => Box::new((*start..*end).map(NewtValue::Int)),
+            value => {
+                return Err(NewtRuntimeError::type_error(&format!(
+                    "cannot iterate over {}",
+                    value.type_name()
+                )))
+            }
+        };
+
+        Ok(NewtIterator(iterator))
+    }
+}
+
+impl Iterator for NewtIterator {
+    type Item = NewtValue;
+
+    fn next(&mut self) -> Option<NewtValue> {
+        self.0.next()
+    }
+}
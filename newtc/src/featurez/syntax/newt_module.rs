@@ -0,0 +1,61 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::featurez::syntax::{NewtRuntimeError, NewtValue, RuntimeErrorKind};
+
+/// The globals of one module.  Functions declared in a module keep reading its globals
+/// wherever they are called from, and other modules only see the names it exports.
+#[derive(Debug)]
+pub struct NewtModule {
+    name: String,
+    exports: Vec<String>,
+    globals: RefCell<HashMap<String, NewtValue>>,
+}
+
+impl NewtModule {
+    pub fn new(name: &str, exports: Vec<String>) -> NewtModule {
+        NewtModule {
+            name: name.to_string(),
+            exports,
+            globals: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn exports(&self) -> &[String] {
+        &self.exports
+    }
+
+    pub fn global(&self, name: &str) -> Option<NewtValue> {
+        self.globals.borrow().get(name).cloned()
+    }
+
+    pub fn define_global(&self, name: &str, value: NewtValue) {
+        self.globals.borrow_mut().insert(name.to_string(), value);
+    }
+
+    pub fn set_global(&self, name: &str, value: NewtValue) -> bool {
+        match self.globals.borrow_mut().get_mut(name) {
+            Some(slot) => {
+                *slot = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn export(&self, name: &str) -> Result<NewtValue, NewtRuntimeError> {
+        if !self.exports.iter().any(|export| export == name) {
+            return Err(NewtRuntimeError::new(
+                RuntimeErrorKind::UndefinedVariable,
+                &format!("module {} does not export '{}'", self.name, name),
+            ));
+        }
+
+        self.global(name)
+            .ok_or_else(|| NewtRuntimeError::undefined_variable(name))
+    }
+}
@@ -26,14 +26,29 @@ impl NewtObject {
         self
     }
 
+    pub fn ptr_eq(&self, other: &NewtObject) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
     pub fn keys(&self) -> Vec<String> {
-        self.0.borrow().keys().map(|k| k.clone()).collect()
+        let mut keys: Vec<String> = self.0.borrow().keys().map(|k| k.clone()).collect();
+        keys.sort();
+
+        keys
     }
 }
 
 impl Display for NewtObject {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "{:#?}", self.0.borrow())
+        write!(f, "{{")?;
+        for (index, key) in self.keys().iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}: {}", key, self.0.borrow()[key])?;
+        }
+        write!(f, "}}")
     }
 }
 
@@ -1,12 +1,172 @@
-use crate::featurez::syntax::NewtValue;
+use crate::featurez::syntax::text_range::{line_column, render_snippet};
+use crate::featurez::syntax::{NewtObject, NewtValue, TextRange};
+use ansi_term::Color::Red;
+use std::fmt::{Display, Error, Formatter};
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum NewtRuntimeError {
+pub enum RuntimeErrorKind {
     TypeError,
+    ArityMismatch,
     UndefinedVariable,
-    DuplicateDeclaration,
-    // We hit this when a function doesn't return anything, but we try to assign it to a variable
-    NullValueEncountered,
-    ReturnedValue(NewtValue),
+    IndexOutOfBounds,
+    InaccessibleField,
+    // Integer arithmetic whose result doesn't fit in 64 bits, or a shift by more than 63
+    Overflow,
+    DivisionByZero,
+    // Calls nested too deeply, usually by recursion that never ends
+    StackOverflow,
     InvalidSyntaxTree,
+    // A value thrown by a script's `throw` statement that nothing caught
+    Thrown,
+    // A value that none of a match expression's arms matched
+    NoMatchingArm,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StackFrame {
+    symbol: String,
+    call_site: Option<TextRange>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewtRuntimeError {
+    kind: RuntimeErrorKind,
+    message: String,
+    range: Option<TextRange>,
+    line: Option<usize>,
+    stack: Vec<StackFrame>,
+    thrown: Option<NewtValue>,
+}
+
+impl StackFrame {
+    pub fn new(symbol: &str, call_site: Option<TextRange>) -> StackFrame {
+        StackFrame {
+            symbol: symbol.to_string(),
+            call_site,
+        }
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    pub fn call_site(&self) -> Option<TextRange> {
+        self.call_site
+    }
+}
+
+impl NewtRuntimeError {
+    pub fn new(kind: RuntimeErrorKind, message: &str) -> NewtRuntimeError {
+        NewtRuntimeError {
+            kind,
+            message: message.to_string(),
+            range: None,
+            line: None,
+            stack: Vec::new(),
+            thrown: None,
+        }
+    }
+
+    pub fn thrown(value: NewtValue) -> NewtRuntimeError {
+        NewtRuntimeError {
+            thrown: Some(value.clone()),
+            ..NewtRuntimeError::new(RuntimeErrorKind::Thrown, &value.to_string())
+        }
+    }
+
+    pub fn type_error(message: &str) -> NewtRuntimeError {
+        NewtRuntimeError::new(RuntimeErrorKind::TypeError, message)
+    }
+
+    pub fn undefined_variable(identifier: &str) -> NewtRuntimeError {
+        NewtRuntimeError::new(
+            RuntimeErrorKind::UndefinedVariable,
+            &format!("'{}' is not defined", identifier),
+        )
+    }
+
+    pub fn kind(&self) -> &RuntimeErrorKind {
+        &self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn range(&self) -> Option<TextRange> {
+        self.range
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn stack(&self) -> &[StackFrame] {
+        &self.stack
+    }
+
+    /// Attaches `range` unless a more precise span has already been recorded.
+    pub fn with_range(mut self, range: TextRange) -> NewtRuntimeError {
+        self.range = self.range.or(Some(range));
+        self
+    }
+
+    pub fn with_line(mut self, line: usize) -> NewtRuntimeError {
+        self.line = self.line.or(Some(line));
+        self
+    }
+
+    /// The value a script's `catch` clause receives for this error: whatever was thrown, or
+    /// an object describing an error raised by the runtime itself.
+    pub fn value(&self) -> NewtValue {
+        if let Some(value) = &self.thrown {
+            return value.clone();
+        }
+
+        let line = self
+            .line
+            .map_or(NewtValue::Null, |line| NewtValue::from(line as i64));
+        let mut object = NewtObject::new();
+        object
+            .set(
+                "kind",
+                &NewtValue::from(format!("{:?}", self.kind).as_str()),
+            )
+            .set("message", &NewtValue::from(self.message.as_str()))
+            .set("line", &line);
+
+        object.into()
+    }
+
+    pub fn with_frame(mut self, frame: StackFrame) -> NewtRuntimeError {
+        self.stack.push(frame);
+        self
+    }
+
+    pub fn render(&self, source: &str) -> String {
+        let mut rendered = format!("{}\n", Red.bold().paint(format!("error: {}", self)));
+
+        if let Some(range) = self.range.filter(|r| r.end() <= source.len()) {
+            rendered.push_str(&render_snippet(source, range));
+        }
+
+        for frame in self.stack.iter() {
+            match frame.call_site.filter(|r| r.end() <= source.len()) {
+                Some(range) => rendered.push_str(&format!(
+                    "  in {} called at line {}\n",
+                    frame.symbol,
+                    line_column(source, range.start()).0
+                )),
+                None => rendered.push_str(&format!("  in {}\n", frame.symbol)),
+            }
+        }
+
+        rendered
+    }
+}
+
+impl Display for NewtRuntimeError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
 }
@@ -1,6 +1,67 @@
+use crate::featurez::syntax::text_range::render_snippet;
+use crate::featurez::syntax::TextRange;
+use ansi_term::Color::Red;
+use std::fmt::{Display, Error, Formatter};
+
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub enum NewtStaticError {
+pub enum StaticErrorKind {
     ShadowedVariableDeclaration,
     DuplicateVariableDeclaration,
     UndeclaredVariable,
+    DuplicateFieldDeclaration,
+    DuplicateMethodDeclaration,
+    UnsatisfiedConstraint,
+    TypeMismatch,
+    UnknownType,
+    InvalidEscapeSequence,
+    IntegerLiteralOutOfRange,
+    MisplacedModuleStmt,
+    MisplacedJumpStmt,
+    UndeclaredLabel,
+    NonExhaustiveMatch,
+    UnresolvedImport,
+    ImportCycle,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewtStaticError {
+    kind: StaticErrorKind,
+    message: String,
+    range: TextRange,
+}
+
+impl NewtStaticError {
+    pub fn new(kind: StaticErrorKind, message: &str, range: TextRange) -> NewtStaticError {
+        NewtStaticError {
+            kind,
+            message: message.to_string(),
+            range,
+        }
+    }
+
+    pub fn kind(&self) -> StaticErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn range(&self) -> TextRange {
+        self.range
+    }
+
+    pub fn render(&self, source: &str) -> String {
+        format!(
+            "{}\n{}",
+            Red.bold().paint(format!("error: {}", self)),
+            render_snippet(source, self.range)
+        )
+    }
+}
+
+impl Display for NewtStaticError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{:?}: {}", self.kind, self.message)
+    }
 }
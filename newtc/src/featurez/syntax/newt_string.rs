@@ -8,6 +8,110 @@ impl NewtString {
     pub fn new(s: &str) -> NewtString {
         NewtString(Rc::new(s.to_string()))
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The number of characters in the string, which is what its indices count.
+    pub fn len(&self) -> usize {
+        self.0.chars().count()
+    }
+
+    pub fn slice(&self, start: usize, end: usize) -> Option<NewtString> {
+        if start > end || end > self.len() {
+            return None;
+        }
+
+        let text: String = self.0.chars().skip(start).take(end - start).collect();
+        Some(NewtString::new(&text))
+    }
+
+    pub fn char_at(&self, index: usize) -> Option<char> {
+        self.0.chars().nth(index)
+    }
+
+    pub fn find(&self, needle: &str) -> Option<usize> {
+        self.0
+            .find(needle)
+            .map(|byte_index| self.0[..byte_index].chars().count())
+    }
+
+    pub fn split(&self, separator: &str) -> Vec<NewtString> {
+        if separator.is_empty() {
+            return self
+                .0
+                .chars()
+                .map(|c| NewtString::new(&c.to_string()))
+                .collect();
+        }
+
+        self.0.split(separator).map(NewtString::new).collect()
+    }
+
+    pub fn concat(&self, other: &NewtString) -> NewtString {
+        NewtString::new(&format!("{}{}", self.0, other.0))
+    }
+}
+
+pub fn unescape(text: &str) -> Result<String, String> {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        let escaped = match chars.next() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('0') => '\0',
+            Some('\\') => '\\',
+            Some('"') => '"',
+            Some('\'') => '\'',
+            Some('$') => '$',
+            Some('u') => unescape_unicode(&mut chars)?,
+            Some(other) => return Err(format!("\\{}", other)),
+            None => return Err("\\".to_string()),
+        };
+
+        unescaped.push(escaped);
+    }
+
+    Ok(unescaped)
+}
+
+/// The text of a literal with its escape sequences replaced.  Invalid sequences are reported
+/// by the type checker, and kept as they are here.
+pub fn literal_text(text: &str) -> String {
+    unescape(text).unwrap_or_else(|_| text.to_string())
+}
+
+fn unescape_unicode(chars: &mut std::str::Chars) -> Result<char, String> {
+    let rest = chars.as_str();
+    let digits = rest
+        .strip_prefix('{')
+        .and_then(|rest| rest.split('}').next().filter(|_| rest.contains('}')));
+
+    let code = digits
+        .filter(|digits| !digits.is_empty() && digits.len() <= 6)
+        .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+        .and_then(std::char::from_u32);
+
+    match (digits, code) {
+        (Some(digits), Some(code)) => {
+            chars.nth(digits.chars().count() + 1);
+            Ok(code)
+        }
+        _ if !rest.starts_with('{') => Err("\\u".to_string()),
+        _ => Err(format!(
+            "\\u{}",
+            rest.find('}').map_or(rest, |end| &rest[..=end])
+        )),
+    }
 }
 
 impl Debug for NewtString {
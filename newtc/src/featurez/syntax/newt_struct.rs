@@ -0,0 +1,309 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Error, Formatter};
+use std::rc::Rc;
+
+use crate::featurez::runtime::Callable;
+use crate::featurez::syntax::{Interface, NewtRuntimeError, NewtValue, RuntimeErrorKind};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructField {
+    name: String,
+    type_name: String,
+    immutable: bool,
+    readable: bool,
+    writable: bool,
+}
+
+impl StructField {
+    pub fn new(
+        name: &str,
+        type_name: &str,
+        immutable: bool,
+        readable: bool,
+        writable: bool,
+    ) -> StructField {
+        StructField {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            immutable,
+            readable,
+            writable,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    pub fn is_immutable(&self) -> bool {
+        self.immutable
+    }
+
+    pub fn is_readable(&self) -> bool {
+        self.readable
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.writable
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StructMethod {
+    function: Rc<dyn Callable>,
+    is_static: bool,
+}
+
+impl StructMethod {
+    pub fn new(function: Rc<dyn Callable>, is_static: bool) -> StructMethod {
+        StructMethod {
+            function,
+            is_static,
+        }
+    }
+
+    pub fn function(&self) -> &Rc<dyn Callable> {
+        &self.function
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+}
+
+#[derive(Debug)]
+pub struct StructType {
+    name: String,
+    fields: Vec<StructField>,
+    methods: RefCell<HashMap<String, StructMethod>>,
+    interfaces: RefCell<Vec<Rc<Interface>>>,
+}
+
+impl StructType {
+    pub fn new(name: &str, fields: Vec<StructField>) -> StructType {
+        StructType {
+            name: name.to_string(),
+            fields,
+            methods: RefCell::new(HashMap::new()),
+            interfaces: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[StructField] {
+        &self.fields
+    }
+
+    pub fn has_field(&self, name: &str) -> bool {
+        self.fields.iter().any(|field| field.name == name)
+    }
+
+    pub fn method(&self, name: &str) -> Option<StructMethod> {
+        self.methods.borrow().get(name).cloned()
+    }
+
+    pub fn define_method(&self, name: &str, method: StructMethod) {
+        self.methods.borrow_mut().insert(name.to_string(), method);
+    }
+
+    /// Records that the type implements `interface`, provided each method it requires is one of
+    /// the `provided` methods an impl is about to define, or else one the type already has.
+    pub fn implement(
+        &self,
+        interface: Rc<Interface>,
+        provided: &Interface,
+    ) -> Result<(), NewtRuntimeError> {
+        for required in interface.methods() {
+            let signature = provided
+                .methods()
+                .iter()
+                .find(|method| method.name() == required.name())
+                .map(|method| (method.is_static(), method.arity()))
+                .or_else(|| {
+                    self.method(required.name())
+                        .map(|method| (method.is_static(), method.function().arity()))
+                })
+                .ok_or_else(|| {
+                    NewtRuntimeError::type_error(&format!(
+                        "{} does not implement '{}' required by {}",
+                        self.name,
+                        required.name(),
+                        interface.name()
+                    ))
+                })?;
+
+            if signature != (required.is_static(), required.arity()) {
+                return Err(NewtRuntimeError::type_error(&format!(
+                    "{}.{} does not match the signature required by {}",
+                    self.name,
+                    required.name(),
+                    interface.name()
+                )));
+            }
+        }
+
+        if !self.implements(&interface) {
+            self.interfaces.borrow_mut().push(interface);
+        }
+
+        Ok(())
+    }
+
+    pub fn implements(&self, interface: &Rc<Interface>) -> bool {
+        self.interfaces
+            .borrow()
+            .iter()
+            .any(|implemented| Rc::ptr_eq(implemented, interface))
+    }
+
+    fn field(&self, name: &str) -> Result<(usize, &StructField), NewtRuntimeError> {
+        self.fields
+            .iter()
+            .enumerate()
+            .find(|(_, field)| field.name == name)
+            .ok_or_else(|| {
+                NewtRuntimeError::new(
+                    RuntimeErrorKind::UndefinedVariable,
+                    &format!("{} has no field '{}'", self.name, name),
+                )
+            })
+    }
+}
+
+struct StructInstance {
+    struct_type: Rc<StructType>,
+    values: RefCell<Vec<NewtValue>>,
+}
+
+#[derive(Clone)]
+pub struct NewtStruct(Rc<StructInstance>);
+
+impl NewtStruct {
+    pub fn new(
+        struct_type: Rc<StructType>,
+        initializers: Vec<(String, NewtValue)>,
+    ) -> Result<NewtStruct, NewtRuntimeError> {
+        let mut values: Vec<Option<NewtValue>> = vec![None; struct_type.fields.len()];
+
+        for (name, value) in initializers {
+            let (index, _) = struct_type.field(&name)?;
+
+            if values[index].replace(value).is_some() {
+                return Err(NewtRuntimeError::type_error(&format!(
+                    "field '{}' of {} is initialized more than once",
+                    name, struct_type.name
+                )));
+            }
+        }
+
+        let mut initialized = Vec::with_capacity(values.len());
+        for (field, value) in struct_type.fields.iter().zip(values) {
+            match value {
+                Some(value) => initialized.push(value),
+                None => {
+                    return Err(NewtRuntimeError::type_error(&format!(
+                        "field '{}' of {} is not initialized",
+                        field.name, struct_type.name
+                    )))
+                }
+            }
+        }
+
+        Ok(NewtStruct(Rc::new(StructInstance {
+            struct_type,
+            values: RefCell::new(initialized),
+        })))
+    }
+
+    pub fn ptr_eq(&self, other: &NewtStruct) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub fn struct_type(&self) -> &Rc<StructType> {
+        &self.0.struct_type
+    }
+
+    /// Reads a field on behalf of code belonging to the impl of the struct named `owner`.  Only
+    /// `get` fields may be read outside the struct's own impl.
+    pub fn get(&self, name: &str, owner: Option<&str>) -> Result<NewtValue, NewtRuntimeError> {
+        let (index, field) = self.0.struct_type.field(name)?;
+
+        if !field.readable && !self.is_owned_by(owner) {
+            return Err(self.access_error(field, "is not readable"));
+        }
+
+        Ok(self.0.values.borrow()[index].clone())
+    }
+
+    /// Assigns a field on behalf of code belonging to the impl of the struct named `owner`.  Only
+    /// `set` fields may be assigned outside the struct's own impl, and `imm` fields never are.
+    pub fn set(
+        &self,
+        name: &str,
+        value: &NewtValue,
+        owner: Option<&str>,
+    ) -> Result<(), NewtRuntimeError> {
+        let (index, field) = self.0.struct_type.field(name)?;
+
+        if field.immutable {
+            return Err(self.access_error(field, "is immutable"));
+        }
+
+        if !field.writable && !self.is_owned_by(owner) {
+            return Err(self.access_error(field, "is not writable"));
+        }
+
+        self.0.values.borrow_mut()[index] = value.clone();
+        Ok(())
+    }
+
+    fn is_owned_by(&self, owner: Option<&str>) -> bool {
+        owner == Some(self.0.struct_type.name.as_str())
+    }
+
+    fn access_error(&self, field: &StructField, problem: &str) -> NewtRuntimeError {
+        NewtRuntimeError::new(
+            RuntimeErrorKind::InaccessibleField,
+            &format!(
+                "field '{}' of {} {}",
+                field.name, self.0.struct_type.name, problem
+            ),
+        )
+    }
+}
+
+impl Display for NewtStruct {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        let struct_type = &self.0.struct_type;
+        let values = self.0.values.borrow();
+
+        write!(f, "{}", struct_type.name)?;
+        if struct_type.fields.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, " {{ ")?;
+        for (index, (field, value)) in struct_type.fields.iter().zip(values.iter()).enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}: {}", field.name, value)?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl Debug for NewtStruct {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}", self)
+    }
+}
@@ -1,11 +1,16 @@
 use std::cmp::{Ordering, PartialEq, PartialOrd};
-use std::ops::{Add, Div, Mul, Neg, Not, Sub};
+use std::fmt::{Display, Error, Formatter};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
 use std::str::FromStr;
 
 use super::NewtResult;
 use super::NewtRuntimeError;
+use super::RuntimeErrorKind;
 use crate::featurez::runtime::Callable;
-use crate::featurez::syntax::{NewtObject, NewtString};
+use crate::featurez::syntax::{
+    literal_text, Interface, NewtArray, NewtIterator, NewtModule, NewtObject, NewtString,
+    NewtStruct, StructType,
+};
 
 use crate::featurez::syntax::{
     AstNode, BinaryExprNode, ExprKind, ExprNode, GroupingExprNode, PrimitiveLiteralExprNode,
@@ -26,6 +31,12 @@ pub enum NewtValue {
     Bool(bool),
     Callable(Rc<dyn Callable>),
     Object(NewtObject),
+    Array(NewtArray),
+    Struct(NewtStruct),
+    StructType(Rc<StructType>),
+    Interface(Rc<Interface>),
+    Module(Rc<NewtModule>),
+    Range(i64, i64),
     Null,
 }
 
@@ -55,17 +66,43 @@ impl NewtValue {
                 NewtValue::Float(lexeme.parse().expect("unparsable literal token"))
             }
             TokenKind::StringLiteral => {
-                NewtValue::String(NewtString::new(&lexeme[1..lexeme.len() - 1]))
-            }
-            TokenKind::GlyphLiteral => {
-                NewtValue::Glyph(lexeme[1..2].parse().expect("unparsable literal token"))
+                NewtValue::String(NewtString::new(&literal_text(&lexeme[1..lexeme.len() - 1])))
             }
+            TokenKind::GlyphLiteral => NewtValue::Glyph(
+                literal_text(&lexeme[1..lexeme.len() - 1])
+                    .chars()
+                    .next()
+                    .expect("unparsable literal token"),
+            ),
             TokenKind::True => NewtValue::Bool(true),
             TokenKind::False => NewtValue::Bool(false),
             _ => panic!("Literal node has non-literal token"),
         }
     }
 
+    pub fn iter(&self) -> Result<NewtIterator, NewtRuntimeError> {
+        NewtIterator::new(self)
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            NewtValue::Int(_) => "int",
+            NewtValue::Float(_) => "float",
+            NewtValue::Glyph(_) => "glyph",
+            NewtValue::String(_) => "string",
+            NewtValue::Bool(_) => "bool",
+            NewtValue::Callable(_) => "function",
+            NewtValue::Object(_) => "object",
+            NewtValue::Array(_) => "array",
+            NewtValue::Struct(_) => "struct",
+            NewtValue::StructType(_) => "struct type",
+            NewtValue::Interface(_) => "interface",
+            NewtValue::Module(_) => "module",
+            NewtValue::Range(_, _) => "range",
+            NewtValue::Null => "null",
+        }
+    }
+
     pub fn as_truthy(&self) -> Option<bool> {
         match self {
             NewtValue::Bool(b) => Some(*b),
@@ -75,14 +112,59 @@ impl NewtValue {
     }
 }
 
+impl Display for NewtValue {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        match self {
+            NewtValue::Int(i) => write!(f, "{}", i),
+            NewtValue::Float(x) => write!(f, "{:?}", x),
+            NewtValue::Glyph(c) => write!(f, "{}", c),
+            NewtValue::String(s) => write!(f, "{}", s),
+            NewtValue::Bool(b) => write!(f, "{}", b),
+            NewtValue::Callable(c) => write!(f, "{:?}", c),
+            NewtValue::Object(o) => write!(f, "{}", o),
+            NewtValue::Array(a) => write!(f, "{}", a),
+            NewtValue::Struct(s) => write!(f, "{}", s),
+            NewtValue::StructType(t) => write!(f, "<struct {}>", t.name()),
+            NewtValue::Interface(i) => write!(f, "<interface {}>", i.name()),
+            NewtValue::Module(m) => write!(f, "<module {}>", m.name()),
+            NewtValue::Range(start, end) => write!(f, "range({}, {})", start, end),
+            NewtValue::Null => write!(f, "null"),
+        }
+    }
+}
+
+enum Operands {
+    Int(i64, i64),
+    Float(f64, f64),
+}
+
+impl Operands {
+    fn promote(
+        operator: &str,
+        lhs: NewtValue,
+        rhs: NewtValue,
+    ) -> Result<Operands, NewtRuntimeError> {
+        match (lhs, rhs) {
+            (NewtValue::Int(l), NewtValue::Int(r)) => Ok(Operands::Int(l, r)),
+            (NewtValue::Float(l), NewtValue::Float(r)) => Ok(Operands::Float(l, r)),
+            (NewtValue::Int(l), NewtValue::Float(r)) => Ok(Operands::Float(l as f64, r)),
+            (NewtValue::Float(l), NewtValue::Int(r)) => Ok(Operands::Float(l, r as f64)),
+            (lhs, rhs) => Err(binary_type_error(operator, &lhs, &rhs)),
+        }
+    }
+}
+
 impl Add for NewtValue {
     type Output = NewtResult;
 
     fn add(self, rhs: Self) -> <Self as Add<Self>>::Output {
-        match (self, rhs) {
-            (NewtValue::Int(l), NewtValue::Int(r)) => Ok(NewtValue::Int(l + r)),
-            (NewtValue::Float(l), NewtValue::Float(r)) => Ok(NewtValue::Float(l + r)),
-            _ => Err(NewtRuntimeError::TypeError),
+        if let (NewtValue::String(l), NewtValue::String(r)) = (&self, &rhs) {
+            return Ok(NewtValue::String(l.concat(r)));
+        }
+
+        match Operands::promote("+", self, rhs)? {
+            Operands::Int(l, r) => checked("+", l, r, l.checked_add(r)),
+            Operands::Float(l, r) => Ok(NewtValue::Float(l + r)),
         }
     }
 }
@@ -91,10 +173,9 @@ impl Sub for NewtValue {
     type Output = NewtResult;
 
     fn sub(self, rhs: Self) -> <Self as Add<Self>>::Output {
-        match (self, rhs) {
-            (NewtValue::Int(l), NewtValue::Int(r)) => Ok(NewtValue::Int(l - r)),
-            (NewtValue::Float(l), NewtValue::Float(r)) => Ok(NewtValue::Float(l - r)),
-            _ => Err(NewtRuntimeError::TypeError),
+        match Operands::promote("-", self, rhs)? {
+            Operands::Int(l, r) => checked("-", l, r, l.checked_sub(r)),
+            Operands::Float(l, r) => Ok(NewtValue::Float(l - r)),
         }
     }
 }
@@ -103,10 +184,9 @@ impl Mul for NewtValue {
     type Output = NewtResult;
 
     fn mul(self, rhs: Self) -> <Self as Add<Self>>::Output {
-        match (self, rhs) {
-            (NewtValue::Int(l), NewtValue::Int(r)) => Ok(NewtValue::Int(l * r)),
-            (NewtValue::Float(l), NewtValue::Float(r)) => Ok(NewtValue::Float(l - r)),
-            _ => Err(NewtRuntimeError::TypeError),
+        match Operands::promote("*", self, rhs)? {
+            Operands::Int(l, r) => checked("*", l, r, l.checked_mul(r)),
+            Operands::Float(l, r) => Ok(NewtValue::Float(l * r)),
         }
     }
 }
@@ -115,10 +195,95 @@ impl Div for NewtValue {
     type Output = NewtResult;
 
     fn div(self, rhs: Self) -> <Self as Add<Self>>::Output {
+        match Operands::promote("/", self, rhs)? {
+            Operands::Int(_, 0) => Err(division_by_zero()),
+            Operands::Int(l, r) => checked("/", l, r, l.checked_div(r)),
+            Operands::Float(l, r) => Ok(NewtValue::Float(l / r)),
+        }
+    }
+}
+
+impl Rem for NewtValue {
+    type Output = NewtResult;
+
+    fn rem(self, rhs: Self) -> <Self as Rem<Self>>::Output {
+        match Operands::promote("%", self, rhs)? {
+            Operands::Int(_, 0) => Err(division_by_zero()),
+            Operands::Int(l, r) => checked("%", l, r, l.checked_rem(r)),
+            Operands::Float(l, r) => Ok(NewtValue::Float(l % r)),
+        }
+    }
+}
+
+impl BitAnd for NewtValue {
+    type Output = NewtResult;
+
+    fn bitand(self, rhs: Self) -> <Self as BitAnd<Self>>::Output {
+        match (self, rhs) {
+            (NewtValue::Int(l), NewtValue::Int(r)) => Ok(NewtValue::Int(l & r)),
+            (NewtValue::Bool(l), NewtValue::Bool(r)) => Ok(NewtValue::Bool(l & r)),
+            (lhs, rhs) => Err(binary_type_error("&", &lhs, &rhs)),
+        }
+    }
+}
+
+impl BitOr for NewtValue {
+    type Output = NewtResult;
+
+    fn bitor(self, rhs: Self) -> <Self as BitOr<Self>>::Output {
+        match (self, rhs) {
+            (NewtValue::Int(l), NewtValue::Int(r)) => Ok(NewtValue::Int(l | r)),
+            (NewtValue::Bool(l), NewtValue::Bool(r)) => Ok(NewtValue::Bool(l | r)),
+            (lhs, rhs) => Err(binary_type_error("|", &lhs, &rhs)),
+        }
+    }
+}
+
+impl BitXor for NewtValue {
+    type Output = NewtResult;
+
+    fn bitxor(self, rhs: Self) -> <Self as BitXor<Self>>::Output {
+        match (self, rhs) {
+            (NewtValue::Int(l), NewtValue::Int(r)) => Ok(NewtValue::Int(l ^ r)),
+            (NewtValue::Bool(l), NewtValue::Bool(r)) => Ok(NewtValue::Bool(l ^ r)),
+            (lhs, rhs) => Err(binary_type_error("^", &lhs, &rhs)),
+        }
+    }
+}
+
+impl Shl for NewtValue {
+    type Output = NewtResult;
+
+    fn shl(self, rhs: Self) -> <Self as Shl<Self>>::Output {
+        match (self, rhs) {
+            (NewtValue::Int(l), NewtValue::Int(r)) => shift(l, r).map(|r| NewtValue::Int(l << r)),
+            (lhs, rhs) => Err(binary_type_error("<<", &lhs, &rhs)),
+        }
+    }
+}
+
+impl Shr for NewtValue {
+    type Output = NewtResult;
+
+    fn shr(self, rhs: Self) -> <Self as Shr<Self>>::Output {
         match (self, rhs) {
-            (NewtValue::Int(l), NewtValue::Int(r)) => Ok(NewtValue::Int(l / r)),
-            (NewtValue::Float(l), NewtValue::Float(r)) => Ok(NewtValue::Float(l / r)),
-            _ => Err(NewtRuntimeError::TypeError),
+            (NewtValue::Int(l), NewtValue::Int(r)) => shift(l, r).map(|r| NewtValue::Int(l >> r)),
+            (lhs, rhs) => Err(binary_type_error(">>", &lhs, &rhs)),
+        }
+    }
+}
+
+impl NewtValue {
+    /// Raises `self` to the power `rhs`.  An int raised to a negative int is the truncated
+    /// reciprocal of the positive power, the same as dividing one by it.
+    pub fn pow(self, rhs: Self) -> NewtResult {
+        match Operands::promote("**", self, rhs)? {
+            Operands::Int(l, r) if r >= 0 => checked("**", l, r, checked_pow(l, r as u64)),
+            Operands::Int(0, _) => Err(division_by_zero()),
+            Operands::Int(1, _) => Ok(NewtValue::Int(1)),
+            Operands::Int(-1, r) => Ok(NewtValue::Int(if r % 2 == 0 { 1 } else { -1 })),
+            Operands::Int(_, _) => Ok(NewtValue::Int(0)),
+            Operands::Float(l, r) => Ok(NewtValue::Float(l.powf(r))),
         }
     }
 }
@@ -127,8 +292,9 @@ impl Not for NewtValue {
     type Output = NewtResult;
 
     fn not(self) -> <Self as Not>::Output {
-        match self {
-            _ => Err(NewtRuntimeError::TypeError),
+        match self.as_truthy() {
+            Some(truthy) => Ok(NewtValue::Bool(!truthy)),
+            None => Err(unary_type_error("!", &self)),
         }
     }
 }
@@ -138,48 +304,114 @@ impl Neg for NewtValue {
 
     fn neg(self) -> <Self as Neg>::Output {
         match self {
-            NewtValue::Int(l) => Ok(NewtValue::Int(-l)),
+            NewtValue::Int(l) => l.checked_neg().map(NewtValue::Int).ok_or_else(|| {
+                NewtRuntimeError::new(
+                    RuntimeErrorKind::Overflow,
+                    &format!("integer overflow computing -({})", l),
+                )
+            }),
             NewtValue::Float(l) => Ok(NewtValue::Float(-l)),
-            _ => Err(NewtRuntimeError::TypeError),
+            value => Err(unary_type_error("-", &value)),
+        }
+    }
+}
+
+fn binary_type_error(operator: &str, lhs: &NewtValue, rhs: &NewtValue) -> NewtRuntimeError {
+    NewtRuntimeError::type_error(&format!(
+        "cannot apply '{}' to {} and {}",
+        operator,
+        lhs.type_name(),
+        rhs.type_name()
+    ))
+}
+
+fn checked(operator: &str, lhs: i64, rhs: i64, result: Option<i64>) -> NewtResult {
+    result.map(NewtValue::Int).ok_or_else(|| {
+        NewtRuntimeError::new(
+            RuntimeErrorKind::Overflow,
+            &format!("integer overflow computing {} {} {}", lhs, operator, rhs),
+        )
+    })
+}
+
+fn checked_pow(mut base: i64, mut exponent: u64) -> Option<i64> {
+    let mut result: i64 = 1;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result.checked_mul(base)?;
+        }
+
+        exponent >>= 1;
+        if exponent > 0 {
+            base = base.checked_mul(base)?;
         }
     }
+
+    Some(result)
+}
+
+fn shift(value: i64, distance: i64) -> Result<i64, NewtRuntimeError> {
+    if (0..64).contains(&distance) {
+        Ok(distance)
+    } else {
+        Err(NewtRuntimeError::new(
+            RuntimeErrorKind::Overflow,
+            &format!("cannot shift {} by {} bits", value, distance),
+        ))
+    }
+}
+
+fn division_by_zero() -> NewtRuntimeError {
+    NewtRuntimeError::new(RuntimeErrorKind::DivisionByZero, "division by zero")
+}
+
+fn unary_type_error(operator: &str, value: &NewtValue) -> NewtRuntimeError {
+    NewtRuntimeError::type_error(&format!(
+        "cannot apply '{}' to {}",
+        operator,
+        value.type_name()
+    ))
 }
 
 impl PartialOrd for NewtValue {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self, other) {
-            (NewtValue::Int(a), NewtValue::Int(b)) => {
-                if a < b {
-                    Some(Ordering::Less)
-                } else if a == b {
-                    Some(Ordering::Equal)
-                } else {
-                    Some(Ordering::Greater)
-                }
-            }
-            (NewtValue::Float(a), NewtValue::Float(b)) => {
-                if a < b {
-                    Some(Ordering::Less)
-                } else if a == b {
-                    Some(Ordering::Equal)
-                } else {
-                    Some(Ordering::Greater)
-                }
-            }
+            (NewtValue::Int(a), NewtValue::Int(b)) => a.partial_cmp(b),
+            (NewtValue::Float(a), NewtValue::Float(b)) => a.partial_cmp(b),
+            (NewtValue::Int(a), NewtValue::Float(b)) => (*a as f64).partial_cmp(b),
+            (NewtValue::Float(a), NewtValue::Int(b)) => a.partial_cmp(&(*b as f64)),
             _ => None,
         }
     }
 }
 
+/// Numbers, bools, glyphs, strings, ranges and null are equal when their values are, with ints
+/// promoted to floats to compare with them.  Everything else is equal only to itself, so two
+/// arrays with the same elements are different arrays.
 impl PartialEq for NewtValue {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (NewtValue::Int(a), NewtValue::Int(b)) => a == b,
             (NewtValue::Float(a), NewtValue::Float(b)) => a == b,
+            (NewtValue::Int(a), NewtValue::Float(b)) => *a as f64 == *b,
+            (NewtValue::Float(a), NewtValue::Int(b)) => *a == *b as f64,
             (NewtValue::Bool(a), NewtValue::Bool(b)) => a == b,
             (NewtValue::String(a), NewtValue::String(b)) => a == b,
             (NewtValue::Glyph(a), NewtValue::Glyph(b)) => a == b,
+            (NewtValue::Range(a_start, a_end), NewtValue::Range(b_start, b_end)) => {
+                a_start == b_start && a_end == b_end
+            }
             (NewtValue::Null, NewtValue::Null) => true,
+            (NewtValue::Callable(a), NewtValue::Callable(b)) => {
+                Rc::as_ptr(a) as *const () == Rc::as_ptr(b) as *const ()
+            }
+            (NewtValue::Object(a), NewtValue::Object(b)) => a.ptr_eq(b),
+            (NewtValue::Array(a), NewtValue::Array(b)) => a.ptr_eq(b),
+            (NewtValue::Struct(a), NewtValue::Struct(b)) => a.ptr_eq(b),
+            (NewtValue::StructType(a), NewtValue::StructType(b)) => Rc::ptr_eq(a, b),
+            (NewtValue::Interface(a), NewtValue::Interface(b)) => Rc::ptr_eq(a, b),
+            (NewtValue::Module(a), NewtValue::Module(b)) => Rc::ptr_eq(a, b),
             _ => false,
         }
     }
@@ -239,6 +471,18 @@ impl From<NewtObject> for NewtValue {
     }
 }
 
+impl From<NewtStruct> for NewtValue {
+    fn from(s: NewtStruct) -> Self {
+        NewtValue::Struct(s)
+    }
+}
+
+impl From<NewtArray> for NewtValue {
+    fn from(a: NewtArray) -> Self {
+        NewtValue::Array(a)
+    }
+}
+
 impl From<NewtString> for NewtValue {
     fn from(s: NewtString) -> Self {
         NewtValue::String(s)
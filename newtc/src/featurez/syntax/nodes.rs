@@ -1,6 +1,7 @@
 use crate::featurez::newtypes::TransparentNewType;
 use crate::featurez::syntax::{
-    AstNode, ExprKind, RValKind, StmtKind, SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken,
+    AstNode, ExprKind, PatternKind, RValKind, StmtKind, SyntaxElement, SyntaxKind, SyntaxNode,
+    SyntaxToken, TextRange,
 };
 use crate::featurez::tokens::{Token, TokenKind};
 
@@ -27,8 +28,18 @@ impl AstNode for StmtNode {
             | SyntaxKind::IfStmt
             | SyntaxKind::StmtListStmt
             | SyntaxKind::WhileStmt
+            | SyntaxKind::ForStmt
             | SyntaxKind::FunctionDeclarationStmt
-            | SyntaxKind::ReturnStmt => Some(StmtNode::from_inner(node)),
+            | SyntaxKind::ReturnStmt
+            | SyntaxKind::ThrowStmt
+            | SyntaxKind::TryStmt
+            | SyntaxKind::BreakStmt
+            | SyntaxKind::ContinueStmt
+            | SyntaxKind::StructDeclarationStmt
+            | SyntaxKind::ImplStmt
+            | SyntaxKind::InterfaceDeclarationStmt
+            | SyntaxKind::ImportStmt
+            | SyntaxKind::ExportStmt => Some(StmtNode::from_inner(node)),
             _ => None,
         }
     }
@@ -50,15 +61,35 @@ impl StmtNode {
             SyntaxKind::ExprStmt => StmtKind::ExprStmt(ExprStmtNode::from_inner(self.syntax())),
             SyntaxKind::IfStmt => StmtKind::IfStmt(IfStmtNode::from_inner(self.syntax())),
             SyntaxKind::WhileStmt => StmtKind::WhileStmt(WhileStmtNode::from_inner(self.syntax())),
+            SyntaxKind::ForStmt => StmtKind::ForStmt(ForStmtNode::from_inner(self.syntax())),
             SyntaxKind::FunctionDeclarationStmt => StmtKind::FunctionDeclarationStmt(
                 FunctionDeclarationStmtNode::from_inner(self.syntax()),
             ),
             SyntaxKind::ReturnStmt => {
                 StmtKind::ReturnStmt(ReturnStmtNode::from_inner(self.syntax()))
             }
+            SyntaxKind::ThrowStmt => StmtKind::ThrowStmt(ThrowStmtNode::from_inner(self.syntax())),
+            SyntaxKind::TryStmt => StmtKind::TryStmt(TryStmtNode::from_inner(self.syntax())),
+            SyntaxKind::BreakStmt => StmtKind::BreakStmt(BreakStmtNode::from_inner(self.syntax())),
+            SyntaxKind::ContinueStmt => {
+                StmtKind::ContinueStmt(ContinueStmtNode::from_inner(self.syntax()))
+            }
             SyntaxKind::StmtListStmt => {
                 StmtKind::StmtListStmt(StmtListStmtNode::from_inner(self.syntax()))
             }
+            SyntaxKind::StructDeclarationStmt => StmtKind::StructDeclarationStmt(
+                StructDeclarationStmtNode::from_inner(self.syntax()),
+            ),
+            SyntaxKind::ImplStmt => StmtKind::ImplStmt(ImplStmtNode::from_inner(self.syntax())),
+            SyntaxKind::InterfaceDeclarationStmt => StmtKind::InterfaceDeclarationStmt(
+                InterfaceDeclarationStmtNode::from_inner(self.syntax()),
+            ),
+            SyntaxKind::ImportStmt => {
+                StmtKind::ImportStmt(ImportStmtNode::from_inner(self.syntax()))
+            }
+            SyntaxKind::ExportStmt => {
+                StmtKind::ExportStmt(ExportStmtNode::from_inner(self.syntax()))
+            }
             _ => unreachable!("StmtNode cannot be constructed from invalid SyntaxKind"),
         }
     }
@@ -78,6 +109,117 @@ impl ReturnStmtNode {
     }
 }
 
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct ThrowStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for ThrowStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl ThrowStmtNode {
+    pub fn expr(&self) -> &ExprNode {
+        ExprNode::cast(self.0.nth_node(0)).expect("Expected an expression node to throw")
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct TryStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for TryStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl TryStmtNode {
+    pub fn stmts(&self) -> &StmtListStmtNode {
+        StmtListStmtNode::from_inner(self.0.nth_node(0))
+    }
+
+    pub fn catch_clause(&self) -> Option<&CatchClauseNode> {
+        self.0
+            .nodes()
+            .find(|n| n.kind() == SyntaxKind::CatchClause)
+            .map(CatchClauseNode::from_inner)
+    }
+
+    pub fn finally_stmts(&self) -> Option<&StmtListStmtNode> {
+        self.0
+            .nodes()
+            .skip(1)
+            .find(|n| n.kind() == SyntaxKind::StmtListStmt)
+            .map(StmtListStmtNode::from_inner)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct BreakStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for BreakStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl BreakStmtNode {
+    pub fn keyword(&self) -> &SyntaxToken {
+        self.0.nth_token(0)
+    }
+
+    pub fn label(&self) -> Option<&SyntaxToken> {
+        jump_label(&self.0)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct ContinueStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for ContinueStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl ContinueStmtNode {
+    pub fn keyword(&self) -> &SyntaxToken {
+        self.0.nth_token(0)
+    }
+
+    pub fn label(&self) -> Option<&SyntaxToken> {
+        jump_label(&self.0)
+    }
+}
+
+fn loop_label(node: &SyntaxNode, keyword: TokenKind) -> Option<&SyntaxToken> {
+    node.tokens()
+        .take_while(|t| t.token_kind() != keyword)
+        .find(|t| t.token_kind() == TokenKind::Identifier)
+}
+
+fn jump_label(node: &SyntaxNode) -> Option<&SyntaxToken> {
+    node.tokens()
+        .find(|t| t.token_kind() == TokenKind::Identifier)
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct CatchClauseNode(SyntaxNode);
+
+unsafe impl TransparentNewType for CatchClauseNode {
+    type Inner = SyntaxNode;
+}
+
+impl CatchClauseNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0
+            .tokens()
+            .find(|t| t.token_kind() == TokenKind::Identifier)
+            .expect("Expected an identifier for the caught value")
+    }
+
+    pub fn stmts(&self) -> &StmtListStmtNode {
+        StmtListStmtNode::from_inner(self.0.nth_node(0))
+    }
+}
+
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct FunctionDeclarationStmtNode(SyntaxNode);
@@ -91,31 +233,399 @@ impl FunctionDeclarationStmtNode {
         self.0.nth_token(1)
     }
 
-    pub fn arguments(&self) -> impl Iterator<Item = &SyntaxToken> {
-        self.0
-            .tokens()
-            .filter(|t| t.token_kind() == TokenKind::Identifier)
-            .skip(1)
+    pub fn arguments(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.0
+            .tokens()
+            .filter(|t| t.token_kind() == TokenKind::Identifier)
+            .skip(1)
+    }
+
+    pub fn type_parameters(&self) -> impl Iterator<Item = &TypeParameterNode> {
+        type_parameters(&self.0)
+    }
+
+    pub fn parameters(&self) -> impl Iterator<Item = (&SyntaxToken, Option<&TypeRefNode>)> {
+        parameters(&self.0)
+    }
+
+    pub fn return_type(&self) -> Option<&TypeRefNode> {
+        return_type(&self.0)
+    }
+
+    pub fn stmts(&self) -> &StmtListStmtNode {
+        let node = self.0.nodes().last().expect("Expecting StmtListStmtNode");
+        StmtListStmtNode::from_inner(node)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct StructDeclarationStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for StructDeclarationStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl StructDeclarationStmtNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(1)
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = &StructFieldNode> {
+        self.0
+            .nodes()
+            .filter(|n| n.kind() == SyntaxKind::StructField)
+            .map(|n| StructFieldNode::from_inner(n))
+    }
+
+    pub fn type_parameters(&self) -> impl Iterator<Item = &TypeParameterNode> {
+        type_parameters(&self.0)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct StructFieldNode(SyntaxNode);
+
+unsafe impl TransparentNewType for StructFieldNode {
+    type Inner = SyntaxNode;
+}
+
+impl StructFieldNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.identifiers()
+            .nth(0)
+            .expect("Expected an identifier naming the field")
+    }
+
+    pub fn type_ref(&self) -> &TypeRefNode {
+        let node = self
+            .0
+            .nodes()
+            .find(|n| n.kind() == SyntaxKind::TypeRef)
+            .expect("Expected a node naming the field's type");
+        TypeRefNode::from_inner(node)
+    }
+
+    pub fn is_immutable(&self) -> bool {
+        self.has_modifier(TokenKind::Imm)
+    }
+
+    pub fn is_readable(&self) -> bool {
+        self.has_modifier(TokenKind::Get)
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.has_modifier(TokenKind::Set)
+    }
+
+    fn identifiers(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.0
+            .tokens()
+            .filter(|t| t.token_kind() == TokenKind::Identifier)
+    }
+
+    fn has_modifier(&self, modifier: TokenKind) -> bool {
+        self.0.tokens().any(|t| t.token_kind() == modifier)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct ImplStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for ImplStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl ImplStmtNode {
+    pub fn type_identifier(&self) -> &SyntaxToken {
+        self.identifiers()
+            .last()
+            .expect("Expected an identifier naming the implemented type")
+    }
+
+    pub fn interface_identifier(&self) -> Option<&SyntaxToken> {
+        if self.0.tokens().any(|t| t.token_kind() == TokenKind::For) {
+            self.identifiers().nth(0)
+        } else {
+            None
+        }
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = &FunctionDeclarationStmtNode> {
+        self.0
+            .nodes()
+            .filter(|n| n.kind() == SyntaxKind::FunctionDeclarationStmt)
+            .map(|n| FunctionDeclarationStmtNode::from_inner(n))
+    }
+
+    pub fn type_parameters(&self) -> impl Iterator<Item = &TypeParameterNode> {
+        type_parameters(&self.0)
+    }
+
+    fn identifiers(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.0
+            .tokens()
+            .filter(|t| t.token_kind() == TokenKind::Identifier)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct InterfaceDeclarationStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for InterfaceDeclarationStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl InterfaceDeclarationStmtNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(1)
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = &InterfaceMethodNode> {
+        self.0
+            .nodes()
+            .filter(|n| n.kind() == SyntaxKind::InterfaceMethod)
+            .map(|n| InterfaceMethodNode::from_inner(n))
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct InterfaceMethodNode(SyntaxNode);
+
+unsafe impl TransparentNewType for InterfaceMethodNode {
+    type Inner = SyntaxNode;
+}
+
+impl InterfaceMethodNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(1)
+    }
+
+    pub fn arguments(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.0
+            .tokens()
+            .filter(|t| t.token_kind() == TokenKind::Identifier)
+            .skip(1)
+    }
+
+    pub fn type_parameters(&self) -> impl Iterator<Item = &TypeParameterNode> {
+        type_parameters(&self.0)
+    }
+
+    pub fn parameters(&self) -> impl Iterator<Item = (&SyntaxToken, Option<&TypeRefNode>)> {
+        parameters(&self.0)
+    }
+
+    pub fn return_type(&self) -> Option<&TypeRefNode> {
+        return_type(&self.0)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct ImportStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for ImportStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl ImportStmtNode {
+    pub fn path(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.0
+            .tokens()
+            .filter(|t| t.token_kind() == TokenKind::Identifier)
+    }
+
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.path()
+            .last()
+            .expect("Expected an identifier naming the module")
+    }
+
+    pub fn module_name(&self) -> String {
+        self.path()
+            .map(|identifier| identifier.lexeme())
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    pub fn range(&self) -> TextRange {
+        self.0.range()
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct ExportStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for ExportStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl ExportStmtNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(1)
+    }
+
+    pub fn expr(&self) -> Option<&ExprNode> {
+        self.0.nodes().filter_map(|n| ExprNode::cast(n)).nth(0)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct TypeRefNode(SyntaxNode);
+
+unsafe impl TransparentNewType for TypeRefNode {
+    type Inner = SyntaxNode;
+}
+
+impl TypeRefNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(0)
+    }
+
+    pub fn arguments(&self) -> impl Iterator<Item = &TypeRefNode> {
+        self.0.nodes().map(|n| TypeRefNode::from_inner(n))
+    }
+
+    pub fn range(&self) -> TextRange {
+        self.0.range()
+    }
+}
+
+impl Display for TypeRefNode {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}", self.identifier().lexeme())?;
+
+        for (index, argument) in self.arguments().enumerate() {
+            write!(f, "{}{}", if index == 0 { "[" } else { ", " }, argument)?;
+        }
+
+        if self.arguments().next().is_some() {
+            write!(f, "]")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct TypeParameterNode(SyntaxNode);
+
+unsafe impl TransparentNewType for TypeParameterNode {
+    type Inner = SyntaxNode;
+}
+
+impl TypeParameterNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(0)
+    }
+
+    pub fn bounds(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.0
+            .tokens()
+            .filter(|t| t.token_kind() == TokenKind::Identifier)
+            .skip(1)
+    }
+}
+
+fn type_parameters(node: &SyntaxNode) -> impl Iterator<Item = &TypeParameterNode> {
+    node.nodes()
+        .filter(|n| n.kind() == SyntaxKind::TypeParameters)
+        .flat_map(|n| n.nodes())
+        .map(|n| TypeParameterNode::from_inner(n))
+}
+
+fn parameters(node: &SyntaxNode) -> impl Iterator<Item = (&SyntaxToken, Option<&TypeRefNode>)> {
+    let mut parameters = Vec::new();
+
+    let children = node
+        .children()
+        .iter()
+        .skip_while(|c| c.as_token().map(|t| t.token_kind()) != Some(TokenKind::LeftParenthesis));
+
+    for child in children {
+        match child {
+            SyntaxElement::Token(token) if token.token_kind() == TokenKind::Identifier => {
+                parameters.push((token, None))
+            }
+            SyntaxElement::Token(token) if token.token_kind() == TokenKind::RightParenthesis => {
+                break
+            }
+            SyntaxElement::Node(n) if n.kind() == SyntaxKind::TypeRef => {
+                if let Some(parameter) = parameters.last_mut() {
+                    parameter.1 = Some(TypeRefNode::from_inner(n));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    parameters.into_iter()
+}
+
+fn return_type(node: &SyntaxNode) -> Option<&TypeRefNode> {
+    node.children()
+        .iter()
+        .skip_while(|c| c.as_token().map(|t| t.token_kind()) != Some(TokenKind::Arrow))
+        .filter_map(|c| c.as_node())
+        .nth(0)
+        .map(|n| TypeRefNode::from_inner(n))
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct WhileStmtNode(SyntaxNode);
+
+unsafe impl TransparentNewType for WhileStmtNode {
+    type Inner = SyntaxNode;
+}
+
+impl WhileStmtNode {
+    pub fn label(&self) -> Option<&SyntaxToken> {
+        loop_label(&self.0, TokenKind::While)
+    }
+
+    pub fn condition(&self) -> &ExprNode {
+        ExprNode::cast(self.0.nth_node(0))
+            .expect("Expected an expression node for the while statement's condition")
     }
 
     pub fn stmts(&self) -> &StmtListStmtNode {
-        let node = self.0.nodes().last().expect("Expecting StmtListStmtNode");
-        StmtListStmtNode::from_inner(node)
+        StmtListStmtNode::from_inner(self.0.nth_node(1))
     }
 }
 
 #[repr(transparent)]
 #[derive(Clone)]
-pub struct WhileStmtNode(SyntaxNode);
+pub struct ForStmtNode(SyntaxNode);
 
-unsafe impl TransparentNewType for WhileStmtNode {
+unsafe impl TransparentNewType for ForStmtNode {
     type Inner = SyntaxNode;
 }
 
-impl WhileStmtNode {
-    pub fn condition(&self) -> &ExprNode {
+impl ForStmtNode {
+    pub fn label(&self) -> Option<&SyntaxToken> {
+        loop_label(&self.0, TokenKind::For)
+    }
+
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0
+            .tokens()
+            .skip_while(|t| t.token_kind() != TokenKind::For)
+            .find(|t| t.token_kind() == TokenKind::Identifier)
+            .expect("Expected an identifier for the for statement's variable")
+    }
+
+    pub fn iterable(&self) -> &ExprNode {
         ExprNode::cast(self.0.nth_node(0))
-            .expect("Expected an expression node for the while statement's condition")
+            .expect("Expected an expression node for the for statement's iterable")
     }
 
     pub fn stmts(&self) -> &StmtListStmtNode {
@@ -167,8 +677,18 @@ impl VariableDeclarationStmtNode {
             .unwrap()
     }
 
+    pub fn type_ref(&self) -> Option<&TypeRefNode> {
+        self.0
+            .nodes()
+            .find(|n| n.kind() == SyntaxKind::TypeRef)
+            .map(|n| TypeRefNode::from_inner(n))
+    }
+
     pub fn expr(&self) -> &ExprNode {
-        ExprNode::cast(self.0.nth_node(0))
+        self.0
+            .nodes()
+            .filter_map(|n| ExprNode::cast(n))
+            .nth(0)
             .expect("Expected an expression node in variable declaration statement")
     }
 }
@@ -260,7 +780,13 @@ impl AstNode for ExprNode {
             | SyntaxKind::VariableExpr
             | SyntaxKind::FunctionCallExpr
             | SyntaxKind::ObjectLiteralExpr
-            | SyntaxKind::ObjectPropertyExpr => Some(ExprNode::from_inner(node)),
+            | SyntaxKind::ObjectPropertyExpr
+            | SyntaxKind::ArrayLiteralExpr
+            | SyntaxKind::IndexExpr
+            | SyntaxKind::StructLiteralExpr
+            | SyntaxKind::FunctionExpr
+            | SyntaxKind::InterpolatedStringExpr
+            | SyntaxKind::MatchExpr => Some(ExprNode::from_inner(node)),
             _ => None,
         }
     }
@@ -288,6 +814,12 @@ impl ExprNode {
             SyntaxKind::ObjectPropertyExpr => {
                 ExprKind::ObjectPropertyExpr(ObjectPropertyExprNode::from_inner(self.to_inner()))
             }
+            SyntaxKind::ArrayLiteralExpr => {
+                ExprKind::ArrayLiteralExpr(ArrayLiteralExprNode::from_inner(self.to_inner()))
+            }
+            SyntaxKind::IndexExpr => {
+                ExprKind::IndexExpr(IndexExprNode::from_inner(self.to_inner()))
+            }
             SyntaxKind::GroupingExpr => {
                 ExprKind::GroupingExpr(GroupingExprNode::from_inner(self.to_inner()))
             }
@@ -297,6 +829,18 @@ impl ExprNode {
             SyntaxKind::FunctionCallExpr => {
                 ExprKind::FunctionCallExpr(FunctionCallExprNode::from_inner(self.to_inner()))
             }
+            SyntaxKind::StructLiteralExpr => {
+                ExprKind::StructLiteralExpr(StructLiteralExprNode::from_inner(self.to_inner()))
+            }
+            SyntaxKind::FunctionExpr => {
+                ExprKind::FunctionExpr(FunctionExprNode::from_inner(self.to_inner()))
+            }
+            SyntaxKind::InterpolatedStringExpr => ExprKind::InterpolatedStringExpr(
+                InterpolatedStringExprNode::from_inner(self.to_inner()),
+            ),
+            SyntaxKind::MatchExpr => {
+                ExprKind::MatchExpr(MatchExprNode::from_inner(self.to_inner()))
+            }
             _ => unreachable!("ExprNode cannot be constructed from invalid SyntaxKind"),
         }
     }
@@ -320,6 +864,274 @@ impl FunctionCallExprNode {
     }
 }
 
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct FunctionExprNode(SyntaxNode);
+
+unsafe impl TransparentNewType for FunctionExprNode {
+    type Inner = SyntaxNode;
+}
+
+impl FunctionExprNode {
+    pub fn arguments(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.0
+            .tokens()
+            .filter(|t| t.token_kind() == TokenKind::Identifier)
+    }
+
+    pub fn parameters(&self) -> impl Iterator<Item = (&SyntaxToken, Option<&TypeRefNode>)> {
+        parameters(&self.0)
+    }
+
+    pub fn return_type(&self) -> Option<&TypeRefNode> {
+        return_type(&self.0)
+    }
+
+    pub fn stmts(&self) -> &StmtListStmtNode {
+        let node = self.0.nodes().last().expect("Expecting StmtListStmtNode");
+        StmtListStmtNode::from_inner(node)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct InterpolatedStringExprNode(SyntaxNode);
+
+unsafe impl TransparentNewType for InterpolatedStringExprNode {
+    type Inner = SyntaxNode;
+}
+
+impl InterpolatedStringExprNode {
+    /// The tokens holding the string's text, one more than there are interpolations.
+    pub fn fragments(&self) -> impl Iterator<Item = &SyntaxToken> {
+        self.0.tokens().filter(|t| match t.token_kind() {
+            TokenKind::StringHead | TokenKind::StringMiddle | TokenKind::StringTail => true,
+            _ => false,
+        })
+    }
+
+    pub fn texts(&self) -> impl Iterator<Item = &str> {
+        self.fragments().map(|fragment| {
+            let lexeme = fragment.lexeme();
+            let end = match fragment.token_kind() {
+                TokenKind::StringTail => lexeme.len() - 1,
+                _ => lexeme.len() - 2,
+            };
+
+            &lexeme[1..end]
+        })
+    }
+
+    pub fn exprs(&self) -> impl Iterator<Item = &ExprNode> {
+        self.0.nodes().filter_map(ExprNode::cast)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct MatchExprNode(SyntaxNode);
+
+unsafe impl TransparentNewType for MatchExprNode {
+    type Inner = SyntaxNode;
+}
+
+impl MatchExprNode {
+    pub fn scrutinee(&self) -> &ExprNode {
+        ExprNode::cast(self.0.nth_node(0)).expect("Expected an expression node to match")
+    }
+
+    pub fn arms(&self) -> impl Iterator<Item = &MatchArmNode> {
+        self.0
+            .nodes()
+            .filter(|n| n.kind() == SyntaxKind::MatchArm)
+            .map(MatchArmNode::from_inner)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct MatchArmNode(SyntaxNode);
+
+unsafe impl TransparentNewType for MatchArmNode {
+    type Inner = SyntaxNode;
+}
+
+impl MatchArmNode {
+    pub fn pattern(&self) -> &PatternNode {
+        PatternNode::cast(self.0.nth_node(0)).expect("Expected a pattern node for the arm")
+    }
+
+    pub fn guard(&self) -> Option<&ExprNode> {
+        if self.0.tokens().any(|t| t.token_kind() == TokenKind::If) {
+            self.0.nodes().filter_map(ExprNode::cast).next()
+        } else {
+            None
+        }
+    }
+
+    pub fn expr(&self) -> &ExprNode {
+        self.0
+            .nodes()
+            .filter_map(ExprNode::cast)
+            .last()
+            .expect("Expected an expression node for the arm's value")
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct PatternNode(SyntaxNode);
+
+unsafe impl TransparentNewType for PatternNode {
+    type Inner = SyntaxNode;
+}
+
+impl AstNode for PatternNode {
+    fn cast(node: &SyntaxNode) -> Option<&Self> {
+        match node.kind() {
+            SyntaxKind::WildcardPattern
+            | SyntaxKind::BindingPattern
+            | SyntaxKind::LiteralPattern
+            | SyntaxKind::ArrayPattern
+            | SyntaxKind::ObjectPattern
+            | SyntaxKind::StructPattern => Some(PatternNode::from_inner(node)),
+            _ => None,
+        }
+    }
+
+    fn syntax(&self) -> &SyntaxNode {
+        self.to_inner()
+    }
+}
+
+impl PatternNode {
+    pub fn kind(&self) -> PatternKind {
+        match self.0.kind() {
+            SyntaxKind::WildcardPattern => PatternKind::WildcardPattern,
+            SyntaxKind::BindingPattern => {
+                PatternKind::BindingPattern(BindingPatternNode::from_inner(&self.0))
+            }
+            SyntaxKind::LiteralPattern => {
+                PatternKind::LiteralPattern(LiteralPatternNode::from_inner(&self.0))
+            }
+            SyntaxKind::ArrayPattern => {
+                PatternKind::ArrayPattern(ArrayPatternNode::from_inner(&self.0))
+            }
+            SyntaxKind::ObjectPattern => {
+                PatternKind::ObjectPattern(ObjectPatternNode::from_inner(&self.0))
+            }
+            SyntaxKind::StructPattern => {
+                PatternKind::StructPattern(StructPatternNode::from_inner(&self.0))
+            }
+            kind => unreachable!("A PatternNode should not contain an {:?} node", kind),
+        }
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct BindingPatternNode(SyntaxNode);
+
+unsafe impl TransparentNewType for BindingPatternNode {
+    type Inner = SyntaxNode;
+}
+
+impl BindingPatternNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(0)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct LiteralPatternNode(SyntaxNode);
+
+unsafe impl TransparentNewType for LiteralPatternNode {
+    type Inner = SyntaxNode;
+}
+
+impl LiteralPatternNode {
+    pub fn expr(&self) -> &ExprNode {
+        ExprNode::cast(self.0.nth_node(0)).expect("Expected a literal for the pattern")
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct ArrayPatternNode(SyntaxNode);
+
+unsafe impl TransparentNewType for ArrayPatternNode {
+    type Inner = SyntaxNode;
+}
+
+impl ArrayPatternNode {
+    pub fn elements(&self) -> impl Iterator<Item = &PatternNode> {
+        self.0.nodes().filter_map(PatternNode::cast)
+    }
+
+    pub fn has_rest(&self) -> bool {
+        self.0.tokens().any(|t| t.token_kind() == TokenKind::DotDot)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct ObjectPatternNode(SyntaxNode);
+
+unsafe impl TransparentNewType for ObjectPatternNode {
+    type Inner = SyntaxNode;
+}
+
+impl ObjectPatternNode {
+    pub fn fields(&self) -> impl Iterator<Item = &PatternFieldNode> {
+        pattern_fields(&self.0)
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct StructPatternNode(SyntaxNode);
+
+unsafe impl TransparentNewType for StructPatternNode {
+    type Inner = SyntaxNode;
+}
+
+impl StructPatternNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(0)
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = &PatternFieldNode> {
+        pattern_fields(&self.0)
+    }
+}
+
+fn pattern_fields(node: &SyntaxNode) -> impl Iterator<Item = &PatternFieldNode> {
+    node.nodes()
+        .filter(|n| n.kind() == SyntaxKind::PatternField)
+        .map(PatternFieldNode::from_inner)
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct PatternFieldNode(SyntaxNode);
+
+unsafe impl TransparentNewType for PatternFieldNode {
+    type Inner = SyntaxNode;
+}
+
+impl PatternFieldNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(0)
+    }
+
+    /// The pattern the field's value must match, or `None` for a field written on its own,
+    /// which binds a variable of the same name.
+    pub fn pattern(&self) -> Option<&PatternNode> {
+        self.0.try_nth_node(0).and_then(PatternNode::cast)
+    }
+}
+
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct PrimitiveLiteralExprNode(SyntaxNode);
@@ -398,6 +1210,31 @@ impl ObjectLiteralExprNode {
     }
 }
 
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct StructLiteralExprNode(SyntaxNode);
+
+unsafe impl TransparentNewType for StructLiteralExprNode {
+    type Inner = SyntaxNode;
+}
+
+impl StructLiteralExprNode {
+    pub fn identifier(&self) -> &SyntaxToken {
+        self.0.nth_token(0)
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = (&SyntaxToken, &ExprNode)> {
+        let names = self
+            .0
+            .tokens()
+            .filter(|t| t.token_kind() == TokenKind::Identifier)
+            .skip(1);
+        let values = self.0.nodes().filter_map(|n| ExprNode::cast(n));
+
+        names.zip(values)
+    }
+}
+
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct ObjectPropertyExprNode(SyntaxNode);
@@ -416,6 +1253,38 @@ impl ObjectPropertyExprNode {
     }
 }
 
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct ArrayLiteralExprNode(SyntaxNode);
+
+unsafe impl TransparentNewType for ArrayLiteralExprNode {
+    type Inner = SyntaxNode;
+}
+
+impl ArrayLiteralExprNode {
+    pub fn elements(&self) -> impl Iterator<Item = &ExprNode> {
+        self.0.nodes().filter_map(|n| ExprNode::cast(n))
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct IndexExprNode(SyntaxNode);
+
+unsafe impl TransparentNewType for IndexExprNode {
+    type Inner = SyntaxNode;
+}
+
+impl IndexExprNode {
+    pub fn source_expr(&self) -> &ExprNode {
+        ExprNode::cast(self.0.nth_node(0)).unwrap()
+    }
+
+    pub fn index(&self) -> &ExprNode {
+        ExprNode::cast(self.0.nth_node(1)).unwrap()
+    }
+}
+
 #[repr(transparent)]
 #[derive(Clone)]
 pub struct BinaryExprNode(SyntaxNode);
@@ -454,6 +1323,20 @@ impl UnaryExprNode {
     pub fn rhs(&self) -> &ExprNode {
         ExprNode::cast(self.0.nth_node(0)).unwrap()
     }
+
+    /// The literal this negates, if it is an integer literal, since `-9223372036854775808` is
+    /// only an i64 as a whole.
+    pub fn negated_integer_literal(&self) -> Option<&SyntaxToken> {
+        match self.rhs().kind() {
+            ExprKind::PrimitiveLiteralExpr(node)
+                if self.operator() == TokenKind::Minus
+                    && node.literal().token_kind() == TokenKind::IntegerLiteral =>
+            {
+                Some(node.literal())
+            }
+            _ => None,
+        }
+    }
 }
 
 #[repr(transparent)]
@@ -495,7 +1378,7 @@ unsafe impl TransparentNewType for RValNode {
 impl AstNode for RValNode {
     fn cast(node: &SyntaxNode) -> Option<&Self> {
         match node.kind() {
-            SyntaxKind::ObjectPropertyRVal | SyntaxKind::VariableRval => {
+            SyntaxKind::ObjectPropertyRVal | SyntaxKind::VariableRval | SyntaxKind::IndexRVal => {
                 Some(RValNode::from_inner(node))
             }
             _ => None,
@@ -516,6 +1399,7 @@ impl RValNode {
             SyntaxKind::ObjectPropertyRVal => {
                 RValKind::ObjectPropertyRVal(ObjectPropertyRValNode::from_inner(&self.0))
             }
+            SyntaxKind::IndexRVal => RValKind::IndexRVal(IndexRValNode::from_inner(&self.0)),
             kind => unreachable!("An RValNode should not contain an {:?} node", kind),
         }
     }
@@ -552,3 +1436,21 @@ impl ObjectPropertyRValNode {
         self.0.nth_token(1)
     }
 }
+
+#[repr(transparent)]
+#[derive(Clone)]
+pub struct IndexRValNode(SyntaxNode);
+
+unsafe impl TransparentNewType for IndexRValNode {
+    type Inner = SyntaxNode;
+}
+
+impl IndexRValNode {
+    pub fn source_expr(&self) -> &ExprNode {
+        ExprNode::cast(self.0.nth_node(0)).unwrap()
+    }
+
+    pub fn index(&self) -> &ExprNode {
+        ExprNode::cast(self.0.nth_node(1)).unwrap()
+    }
+}
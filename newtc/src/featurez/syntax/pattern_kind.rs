@@ -0,0 +1,12 @@
+use crate::featurez::syntax::{
+    ArrayPatternNode, BindingPatternNode, LiteralPatternNode, ObjectPatternNode, StructPatternNode,
+};
+
+pub enum PatternKind<'a> {
+    WildcardPattern,
+    BindingPattern(&'a BindingPatternNode),
+    LiteralPattern(&'a LiteralPatternNode),
+    ArrayPattern(&'a ArrayPatternNode),
+    ObjectPattern(&'a ObjectPatternNode),
+    StructPattern(&'a StructPatternNode),
+}
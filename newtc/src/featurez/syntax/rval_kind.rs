@@ -1,6 +1,7 @@
-use crate::featurez::syntax::{ObjectPropertyRValNode, VariableRValNode};
+use crate::featurez::syntax::{IndexRValNode, ObjectPropertyRValNode, VariableRValNode};
 
 pub enum RValKind<'a> {
     VariableRVal(&'a VariableRValNode),
     ObjectPropertyRVal(&'a ObjectPropertyRValNode),
+    IndexRVal(&'a IndexRValNode),
 }
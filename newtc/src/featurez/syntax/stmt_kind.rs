@@ -7,6 +7,16 @@ pub enum StmtKind<'a> {
     ExprStmt(&'a ExprStmtNode),
     IfStmt(&'a IfStmtNode),
     WhileStmt(&'a WhileStmtNode),
+    ForStmt(&'a ForStmtNode),
     FunctionDeclarationStmt(&'a FunctionDeclarationStmtNode),
     ReturnStmt(&'a ReturnStmtNode),
+    ThrowStmt(&'a ThrowStmtNode),
+    TryStmt(&'a TryStmtNode),
+    BreakStmt(&'a BreakStmtNode),
+    ContinueStmt(&'a ContinueStmtNode),
+    StructDeclarationStmt(&'a StructDeclarationStmtNode),
+    ImplStmt(&'a ImplStmtNode),
+    InterfaceDeclarationStmt(&'a InterfaceDeclarationStmtNode),
+    ImportStmt(&'a ImportStmtNode),
+    ExportStmt(&'a ExportStmtNode),
 }
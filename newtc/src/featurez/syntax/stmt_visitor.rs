@@ -8,6 +8,16 @@ pub trait StmtVisitor<T> {
     fn visit_expr_stmt(&mut self, node: &ExprStmtNode) -> T;
     fn visit_if_stmt(&mut self, node: &IfStmtNode) -> T;
     fn visit_while_stmt(&mut self, node: &WhileStmtNode) -> T;
+    fn visit_for_stmt(&mut self, node: &ForStmtNode) -> T;
     fn visit_function_declaration_stmt(&mut self, node: &FunctionDeclarationStmtNode) -> T;
     fn visit_return_stmt(&mut self, node: &ReturnStmtNode) -> T;
+    fn visit_throw_stmt(&mut self, node: &ThrowStmtNode) -> T;
+    fn visit_try_stmt(&mut self, node: &TryStmtNode) -> T;
+    fn visit_break_stmt(&mut self, node: &BreakStmtNode) -> T;
+    fn visit_continue_stmt(&mut self, node: &ContinueStmtNode) -> T;
+    fn visit_struct_declaration_stmt(&mut self, node: &StructDeclarationStmtNode) -> T;
+    fn visit_impl_stmt(&mut self, node: &ImplStmtNode) -> T;
+    fn visit_interface_declaration_stmt(&mut self, node: &InterfaceDeclarationStmtNode) -> T;
+    fn visit_import_stmt(&mut self, node: &ImportStmtNode) -> T;
+    fn visit_export_stmt(&mut self, node: &ExportStmtNode) -> T;
 }
@@ -1,4 +1,4 @@
-use crate::featurez::syntax::{SyntaxNode, SyntaxToken};
+use crate::featurez::syntax::{SyntaxNode, SyntaxToken, TextRange};
 use crate::featurez::TokenKind;
 use std::fmt::{Display, Error, Formatter};
 
@@ -43,6 +43,27 @@ impl SyntaxElement {
             _ => None,
         }
     }
+
+    pub fn range(&self) -> TextRange {
+        match self {
+            SyntaxElement::Node(n) => n.range(),
+            SyntaxElement::Token(t) => t.range(),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        match self {
+            SyntaxElement::Node(n) => n.text(),
+            SyntaxElement::Token(t) => t.lexeme().to_string(),
+        }
+    }
+
+    pub(crate) fn shifted(&self, delta: isize) -> SyntaxElement {
+        match self {
+            SyntaxElement::Node(n) => SyntaxElement::Node(n.shifted(delta)),
+            SyntaxElement::Token(t) => SyntaxElement::Token(t.shifted(delta)),
+        }
+    }
 }
 
 impl Display for SyntaxElement {
@@ -9,10 +9,25 @@ pub enum SyntaxKind {
     UnaryExpr,
     PrimitiveLiteralExpr,
     ObjectLiteralExpr,
+    ArrayLiteralExpr,
     ObjectPropertyExpr,
     ObjectPropertyRVal,
+    IndexExpr,
+    IndexRVal,
     VariableExpr,
     FunctionCallExpr,
+    StructLiteralExpr,
+    FunctionExpr,
+    InterpolatedStringExpr,
+    MatchExpr,
+    MatchArm,
+    WildcardPattern,
+    BindingPattern,
+    LiteralPattern,
+    ArrayPattern,
+    ObjectPattern,
+    StructPattern,
+    PatternField,
     VariableDeclarationStmt,
     AssignmentStmt,
     VariableRval,
@@ -20,8 +35,24 @@ pub enum SyntaxKind {
     ExprStmt,
     IfStmt,
     WhileStmt,
+    ForStmt,
     FunctionDeclarationStmt,
     ReturnStmt,
+    ThrowStmt,
+    TryStmt,
+    CatchClause,
+    BreakStmt,
+    ContinueStmt,
+    StructDeclarationStmt,
+    StructField,
+    ImplStmt,
+    InterfaceDeclarationStmt,
+    InterfaceMethod,
+    ImportStmt,
+    ExportStmt,
+    TypeRef,
+    TypeParameters,
+    TypeParameter,
 }
 
 impl Display for SyntaxKind {
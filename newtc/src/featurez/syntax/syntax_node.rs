@@ -1,4 +1,4 @@
-use crate::featurez::syntax::{SyntaxElement, SyntaxKind, SyntaxToken};
+use crate::featurez::syntax::{SyntaxElement, SyntaxKind, SyntaxToken, TextRange};
 use crate::featurez::TokenKind;
 use std::cell::RefCell;
 use std::fmt::{Error, Formatter};
@@ -8,14 +8,21 @@ use std::rc::Rc;
 #[derive(Debug, Clone)]
 pub struct SyntaxNode {
     kind: SyntaxKind,
+    offset: usize,
     length: usize,
     children: Rc<[SyntaxElement]>,
 }
 
 impl SyntaxNode {
-    pub fn new(kind: SyntaxKind, length: usize, children: Vec<SyntaxElement>) -> SyntaxNode {
+    pub fn new(
+        kind: SyntaxKind,
+        offset: usize,
+        length: usize,
+        children: Vec<SyntaxElement>,
+    ) -> SyntaxNode {
         SyntaxNode {
             kind,
+            offset,
             length,
             children: children.into(),
         }
@@ -53,10 +60,68 @@ impl SyntaxNode {
     pub fn kind(&self) -> SyntaxKind {
         self.kind
     }
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
     pub fn length(&self) -> usize {
         self.length
     }
+    pub fn range(&self) -> TextRange {
+        TextRange::at(self.offset, self.length)
+    }
     pub fn children(&self) -> &[SyntaxElement] {
         &*self.children
     }
+
+    pub fn text(&self) -> String {
+        let mut text = String::with_capacity(self.length);
+        self.write_text(&mut text);
+
+        text
+    }
+
+    pub(crate) fn write_text(&self, text: &mut String) {
+        for child in self.children.iter() {
+            match child {
+                SyntaxElement::Node(node) => node.write_text(text),
+                SyntaxElement::Token(token) => text.push_str(token.lexeme()),
+            }
+        }
+    }
+
+    /// A copy of this node with the child at `index` replaced by `child`, which is `delta`
+    /// bytes longer than the one it replaces.
+    pub(crate) fn with_child(&self, index: usize, child: SyntaxNode, delta: isize) -> SyntaxNode {
+        let mut children: Vec<SyntaxElement> = Vec::with_capacity(self.children.len());
+
+        children.extend_from_slice(&self.children[..index]);
+        children.push(SyntaxElement::Node(child));
+        children.extend(self.children[index + 1..].iter().map(|c| c.shifted(delta)));
+
+        SyntaxNode::new(
+            self.kind,
+            self.offset,
+            (self.length as isize + delta) as usize,
+            children,
+        )
+    }
+
+    pub(crate) fn shifted(&self, delta: isize) -> SyntaxNode {
+        if delta == 0 {
+            return self.clone();
+        }
+
+        let children = self
+            .children
+            .iter()
+            .map(|child| child.shifted(delta))
+            .collect();
+
+        SyntaxNode::new(
+            self.kind,
+            (self.offset as isize + delta) as usize,
+            self.length,
+            children,
+        )
+    }
 }
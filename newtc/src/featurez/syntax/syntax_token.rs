@@ -1,17 +1,20 @@
+use crate::featurez::syntax::TextRange;
 use crate::featurez::tokens::TokenKind;
 use std::fmt::{Display, Error, Formatter};
 
 #[derive(Debug, Clone)]
 pub struct SyntaxToken {
     token_kind: TokenKind,
+    offset: usize,
     length: usize,
     lexeme: String,
 }
 
 impl SyntaxToken {
-    pub fn new(token_kind: TokenKind, length: usize, lexeme: &str) -> SyntaxToken {
+    pub fn new(token_kind: TokenKind, offset: usize, length: usize, lexeme: &str) -> SyntaxToken {
         SyntaxToken {
             token_kind,
+            offset,
             length,
             lexeme: lexeme.to_string(),
         }
@@ -21,14 +24,29 @@ impl SyntaxToken {
         self.token_kind
     }
 
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
     pub fn length(&self) -> usize {
         self.length
     }
 
+    pub fn range(&self) -> TextRange {
+        TextRange::at(self.offset, self.length)
+    }
+
     pub fn lexeme(&self) -> &str {
         &self.lexeme
     }
 
+    pub(crate) fn shifted(&self, delta: isize) -> SyntaxToken {
+        SyntaxToken {
+            offset: (self.offset as isize + delta) as usize,
+            ..self.clone()
+        }
+    }
+
     fn escape_whitespace(text: &str) -> String {
         let escaped_text: String = text
             .chars()
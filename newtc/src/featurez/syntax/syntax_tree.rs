@@ -1,14 +1,18 @@
 use crate::featurez::parse::ParseEvent;
 use crate::featurez::parse::{CompletedParsing, Parser};
+use crate::featurez::syntax::text_range::{line_column, render_snippet};
 use crate::featurez::syntax::tree_sink::TreeSink;
 use crate::featurez::syntax::SyntaxToken;
 use crate::featurez::syntax::TextTreeSink;
-use crate::featurez::syntax::{AstNode, StmtNode, SyntaxElement, SyntaxKind, SyntaxNode};
+use crate::featurez::syntax::{
+    AstNode, StmtNode, SyntaxElement, SyntaxKind, SyntaxNode, TextEdit, TextRange,
+};
 use crate::featurez::tokenize;
+use crate::featurez::tokens::tomb_stone_message;
 
-use crate::featurez::driver::NewtError;
 use crate::featurez::grammar::{root_expr, root_stmt};
 use crate::featurez::{StrTokenSource, TokenKind};
+use ansi_term::Color::Red;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::fmt::Display;
@@ -20,17 +24,124 @@ pub struct SyntaxTree {
     errors: Vec<ErrorReport>,
 }
 
+#[derive(Clone, PartialEq)]
 pub struct ErrorReport {
     pub(crate) line: usize,
     pub(crate) message: String,
+    pub(crate) range: TextRange,
+    pub(crate) columns: (usize, usize),
+    pub(crate) expected: Vec<TokenKind>,
+    pub(crate) found: TokenKind,
+}
+
+impl ErrorReport {
+    fn new(
+        message: &str,
+        range: TextRange,
+        expected: Vec<TokenKind>,
+        found: TokenKind,
+        text: &str,
+    ) -> ErrorReport {
+        let (line, start_column) = line_column(text, range.start());
+        let line_end = text[range.start()..]
+            .find('\n')
+            .map_or(text.len(), |i| range.start() + i);
+        let end_column = start_column
+            + text[range.start()..range.end().min(line_end)]
+                .chars()
+                .count();
+
+        ErrorReport {
+            line,
+            message: message.to_string(),
+            range,
+            columns: (start_column, end_column),
+            expected,
+            found,
+        }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn range(&self) -> TextRange {
+        self.range
+    }
+
+    /// The 1-based columns of the offending text on its first line, end exclusive.
+    pub fn columns(&self) -> (usize, usize) {
+        self.columns
+    }
+
+    pub fn expected(&self) -> &[TokenKind] {
+        &self.expected
+    }
+
+    pub fn found(&self) -> TokenKind {
+        self.found
+    }
+
+    pub fn description(&self) -> String {
+        match self.expected.as_slice() {
+            // The tokenizer's errors are about the text itself, not what the parser wanted
+            [] if self.found == TokenKind::TombStone => self.message.clone(),
+            [] => format!("{}, found {}", self.message, self.found),
+            [expected] => format!(
+                "{} (expected {}, found {})",
+                self.message, expected, self.found
+            ),
+            expected => {
+                let expected: Vec<String> = expected.iter().map(|k| k.to_string()).collect();
+                format!(
+                    "{} (expected one of {}, found {})",
+                    self.message,
+                    expected.join(", "),
+                    self.found
+                )
+            }
+        }
+    }
+
+    pub fn render(&self, source: &str) -> String {
+        format!(
+            "{}\n{}",
+            Red.bold().paint(format!("error: {}", self)),
+            render_snippet(source, self.range)
+        )
+    }
 }
 
 impl Display for ErrorReport {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "{}: {}", self.line, self.message)
+        write!(
+            f,
+            "{}:{}: {}",
+            self.line,
+            self.columns.0,
+            self.description()
+        )
+    }
+}
+
+impl Debug for ErrorReport {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}", self)
     }
 }
 
+struct OpenNode {
+    kind: SyntaxKind,
+    event_index: usize,
+    offset: usize,
+    content_end: usize,
+    found: Option<TokenKind>,
+}
+
 impl SyntaxTree {
     fn new(root: SyntaxElement, errors: Vec<ErrorReport>) -> SyntaxTree {
         SyntaxTree { root, errors }
@@ -49,58 +160,117 @@ impl SyntaxTree {
 
         let mut sink = TextTreeSink::new();
         let mut offset = 0;
+        let mut token_end = 0;
         let mut error_reports: Vec<ErrorReport> = Vec::new();
-        let mut lines = 0;
+        let mut open_nodes: Vec<OpenNode> = Vec::new();
 
         for (index, event) in events.iter().enumerate() {
             match event {
                 ParseEvent::BeginNode {
-                    kind: k,
                     is_forward_parent: false,
-                    forward_parent_offset,
+                    ..
                 } => {
-                    match k {
-                        SyntaxKind::Error(message) => error_reports.push(ErrorReport {
-                            message: message.to_string(),
-                            line: lines + 1,
-                        }),
-                        _ => {}
-                    }
-                    Self::begin_forward_parents(&mut sink, &events, index);
+                    Self::begin_forward_parents(&mut sink, &mut open_nodes, &events, index, offset);
                 }
                 ParseEvent::BeginNode {
-                    kind,
                     is_forward_parent: true,
-                    forward_parent_offset: _,
-                } => match kind {
-                    SyntaxKind::Error(message) => error_reports.push(ErrorReport {
-                        message: message.to_string(),
-                        line: lines + 1,
-                    }),
-                    _ => {}
-                },
+                    ..
+                } => {}
                 ParseEvent::EndNode => {
-                    sink.end_node(0);
+                    sink.end_node(offset);
+
+                    let node = open_nodes.pop().expect("Unbalanced EndNode event");
+                    match node.kind {
+                        SyntaxKind::Error(_) if parser.silenced.contains(&node.event_index) => {}
+                        SyntaxKind::Error(message) => {
+                            let found = node
+                                .found
+                                .unwrap_or_else(|| Self::next_token_kind(&events[index..]));
+                            let expected = parser
+                                .expectations
+                                .get(&node.event_index)
+                                .cloned()
+                                .unwrap_or_default();
+                            // Something missing is reported just after what came before it,
+                            // rather than at whatever comes next, maybe lines later
+                            let range = match node.found {
+                                Some(_) => TextRange::new(node.offset, node.content_end),
+                                None => TextRange::new(token_end, token_end),
+                            };
+
+                            error_reports
+                                .push(ErrorReport::new(message, range, expected, found, text));
+                        }
+                        _ => {}
+                    }
                 }
                 ParseEvent::Token { kind: k, length: l } => {
-                    sink.attach_token(SyntaxToken::new(*k, *l, &text[offset..offset + l]));
+                    Self::record_found(&mut open_nodes, *k, offset + l);
+                    Self::report_tomb_stone(&mut error_reports, *k, offset, *l, text);
+                    if !k.is_trivia() {
+                        token_end = offset + l;
+                    }
+                    sink.attach_token(SyntaxToken::new(*k, offset, *l, &text[offset..offset + l]));
                     offset += l;
                 }
                 ParseEvent::Trivia { kind: k, length: l } => {
-                    let lexeme = &text[offset..offset + l];
-                    lines = lines + lexeme.chars().filter(|c| *c == '\n').count();
-
-                    sink.attach_token(SyntaxToken::new(*k, *l, lexeme));
+                    Self::record_found(&mut open_nodes, *k, offset + l);
+                    Self::report_tomb_stone(&mut error_reports, *k, offset, *l, text);
+                    sink.attach_token(SyntaxToken::new(*k, offset, *l, &text[offset..offset + l]));
                     offset += *l;
                 }
             }
         }
 
         let root = sink.end_tree();
+        error_reports.sort_by_key(|report| report.range.start());
 
         SyntaxTree::new(root, error_reports)
     }
 
+    fn record_found(open_nodes: &mut [OpenNode], kind: TokenKind, token_end: usize) {
+        if kind.is_trivia() {
+            return;
+        }
+
+        for node in open_nodes.iter_mut() {
+            node.found = node.found.or(Some(kind));
+            node.content_end = token_end;
+        }
+    }
+
+    fn report_tomb_stone(
+        error_reports: &mut Vec<ErrorReport>,
+        kind: TokenKind,
+        offset: usize,
+        length: usize,
+        text: &str,
+    ) {
+        if kind != TokenKind::TombStone || length == 0 {
+            return;
+        }
+
+        let message = tomb_stone_message(&text[offset..offset + length]);
+        let range = TextRange::new(offset, offset + length);
+
+        error_reports.push(ErrorReport::new(message, range, vec![], kind, text));
+    }
+
+    fn next_token_kind(events: &[ParseEvent]) -> TokenKind {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                ParseEvent::Token { kind, .. } | ParseEvent::Trivia { kind, .. }
+                    if !kind.is_trivia() =>
+                {
+                    Some(*kind)
+                }
+                _ => None,
+            })
+            .next()
+            .unwrap_or(TokenKind::EndOfFile)
+    }
+
     pub fn iter(&self) -> SyntaxTreeIterator {
         SyntaxTreeIterator {
             frontier: vec![self.root()],
@@ -167,19 +337,38 @@ impl SyntaxTree {
         Ok(())
     }
 
-    fn begin_forward_parents(sink: &mut TextTreeSink, events: &[ParseEvent], index: usize) {
+    fn begin_forward_parents(
+        sink: &mut TextTreeSink,
+        open_nodes: &mut Vec<OpenNode>,
+        events: &[ParseEvent],
+        index: usize,
+        offset: usize,
+    ) {
         let event = &events[index];
 
         match event {
             ParseEvent::BeginNode {
                 kind,
-                forward_parent_offset: offset,
+                forward_parent_offset: parent_offset,
                 is_forward_parent: _,
             } => {
-                if let Some(next_offset) = offset {
-                    Self::begin_forward_parents(sink, events, index + *next_offset);
+                if let Some(next_offset) = parent_offset {
+                    Self::begin_forward_parents(
+                        sink,
+                        open_nodes,
+                        events,
+                        index + *next_offset,
+                        offset,
+                    );
                 }
-                sink.begin_node(*kind, 0);
+                sink.begin_node(*kind, offset);
+                open_nodes.push(OpenNode {
+                    kind: *kind,
+                    event_index: index,
+                    offset,
+                    content_end: offset,
+                    found: None,
+                });
             }
             _ => {}
         }
@@ -218,14 +407,91 @@ impl<'a> Iterator for SyntaxTreeIterator<'a> {
     }
 }
 
+impl SyntaxTree {
+    pub fn from_stmts(source: &str) -> SyntaxTree {
+        let token_source = StrTokenSource::new(tokenize(source));
+        let parsing = root_stmt(Parser::new(token_source));
+
+        SyntaxTree::from_parser(&parsing, source)
+    }
+
+    pub fn text(&self) -> String {
+        self.root.text()
+    }
+
+    /// Only the smallest statement or block enclosing the edit is reparsed, falling back to
+    /// enclosing statements and then the whole text.
+    pub fn reparse(&self, edit: &TextEdit) -> SyntaxTree {
+        if self.errors.is_empty() {
+            let root = self
+                .root
+                .as_node()
+                .and_then(|root| SyntaxTree::reparse_within(root, edit));
+
+            if let Some(root) = root {
+                return SyntaxTree::new(SyntaxElement::Node(root), Vec::new());
+            }
+        }
+
+        let text = edit.apply(&self.text(), 0);
+
+        match self.root.as_node().map(SyntaxNode::kind) {
+            Some(SyntaxKind::StmtListStmt) => SyntaxTree::from_stmts(&text),
+            _ => text.as_str().into(),
+        }
+    }
+
+    fn reparse_within(node: &SyntaxNode, edit: &TextEdit) -> Option<SyntaxNode> {
+        let range = edit.range();
+        let index = node.children().iter().position(|child| {
+            child.range().start() < range.start() && range.end() < child.range().end()
+        })?;
+        let child = node.children()[index].as_node()?;
+
+        let replacement = SyntaxTree::reparse_within(child, edit).or_else(|| {
+            StmtNode::cast(child).and_then(|_| SyntaxTree::reparse_stmt(child, edit))
+        })?;
+
+        Some(node.with_child(index, replacement, edit.delta()))
+    }
+
+    fn reparse_stmt(stmt: &SyntaxNode, edit: &TextEdit) -> Option<SyntaxNode> {
+        let text = edit.apply(&stmt.text(), stmt.offset());
+        let parsing = root_stmt(Parser::new(StrTokenSource::new(tokenize(&text))));
+        let tree = SyntaxTree::from_parser(&parsing, &text);
+
+        if !tree.errors.is_empty() {
+            return None;
+        }
+
+        let reparsed = match tree.root.as_node().map(SyntaxNode::children) {
+            Some([SyntaxElement::Node(reparsed)]) if reparsed.kind() == stmt.kind() => reparsed,
+            _ => return None,
+        };
+
+        // Comments and unterminated literals at the end of the statement may run on past it
+        // in the whole text, unless the statement already ended in one.
+        if SyntaxTree::last_token_kind(reparsed) != SyntaxTree::last_token_kind(stmt) {
+            return None;
+        }
+
+        Some(reparsed.shifted(stmt.offset() as isize))
+    }
+
+    fn last_token_kind(node: &SyntaxNode) -> Option<TokenKind> {
+        match node.children().last()? {
+            SyntaxElement::Node(node) => SyntaxTree::last_token_kind(node),
+            SyntaxElement::Token(token) => Some(token.token_kind()),
+        }
+    }
+}
+
 impl From<&str> for SyntaxTree {
     fn from(source: &str) -> Self {
         let statement_token_kinds = [
             TokenKind::SemiColon,
             TokenKind::RightBrace,
             TokenKind::LeftBrace,
-            TokenKind::RightBracket,
-            TokenKind::LeftBracket,
         ];
         let tokens = tokenize(source);
         let statement_tokens = tokens
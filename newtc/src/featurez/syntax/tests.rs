@@ -8,6 +8,7 @@ use crate::featurez::parse::Parser;
 use crate::featurez::tokens::tokenize;
 use crate::featurez::tokens::StrTokenSource;
 use crate::featurez::tokens::TokenKind;
+use proptest::prelude::*;
 use std::collections::HashMap;
 
 #[test]
@@ -157,6 +158,162 @@ fn while_stmt_node_round_trips() {
     };
 }
 
+#[test]
+fn for_stmt_node_handles_identifier_and_iterable() {
+    let tree: SyntaxTree = "for x in range(3) {}".into();
+    let for_node: &ForStmtNode = expect_stmt_node(&tree);
+
+    assert_eq!("x", for_node.identifier().lexeme());
+    assert_eq!(
+        SyntaxKind::FunctionCallExpr,
+        for_node.iterable().syntax().kind()
+    );
+}
+
+#[test]
+fn for_stmt_node_handles_stmts() {
+    let tree: SyntaxTree = "for x in xs { let y = x; }".into();
+    let for_node: &ForStmtNode = expect_stmt_node(&tree);
+
+    assert_eq!(1, for_node.stmts().stmts().count());
+}
+
+#[test]
+fn try_stmt_node_handles_catch_and_finally() {
+    let tree: SyntaxTree = "try { f(); } catch (e) { g(e); h(); } finally {}".into();
+    let try_node: &TryStmtNode = expect_stmt_node(&tree);
+    let clause = try_node.catch_clause().expect("Expected a catch clause");
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!(1, try_node.stmts().stmts().count());
+    assert_eq!("e", clause.identifier().lexeme());
+    assert_eq!(2, clause.stmts().stmts().count());
+    assert_eq!(0, try_node.finally_stmts().map_or(1, |s| s.stmts().count()));
+}
+
+#[test]
+fn try_stmt_node_needs_catch_or_finally() {
+    let tree: SyntaxTree = "try { throw 1; } finally { g(); }".into();
+    let try_node: &TryStmtNode = expect_stmt_node(&tree);
+
+    assert!(try_node.catch_clause().is_none());
+    assert_eq!(1, try_node.finally_stmts().map_or(0, |s| s.stmts().count()));
+
+    let tree: SyntaxTree = "try { f(); }
+let x = 1;"
+        .into();
+    let messages: Vec<&str> = tree.errors().map(|e| e.message()).collect();
+
+    assert_eq!(vec!["Expected 'catch' or 'finally'"], messages);
+}
+
+#[test]
+fn match_expr_node_handles_patterns_and_guards() {
+    let tree: SyntaxTree =
+        "let m = match p { Point { x: 0, y } => y, [a, ..] if a > 1 => a, -1 => 0, _ => 1, };"
+            .into();
+    let declaration: &VariableDeclarationStmtNode = expect_stmt_node(&tree);
+    let node = match declaration.expr().kind() {
+        ExprKind::MatchExpr(node) => node,
+        _ => panic!("Expected a match expression"),
+    };
+    let arms: Vec<&MatchArmNode> = node.arms().collect();
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!(SyntaxKind::VariableExpr, node.scrutinee().syntax().kind());
+    assert_eq!(4, arms.len());
+
+    match arms[0].pattern().kind() {
+        PatternKind::StructPattern(pattern) => {
+            let fields: Vec<&PatternFieldNode> = pattern.fields().collect();
+
+            assert_eq!("Point", pattern.identifier().lexeme());
+            assert_eq!(2, fields.len());
+            assert!(fields[0].pattern().is_some());
+            assert_eq!("y", fields[1].identifier().lexeme());
+            assert!(fields[1].pattern().is_none());
+        }
+        _ => panic!("Expected a struct pattern"),
+    }
+
+    match arms[1].pattern().kind() {
+        PatternKind::ArrayPattern(pattern) => {
+            assert_eq!(1, pattern.elements().count());
+            assert!(pattern.has_rest());
+        }
+        _ => panic!("Expected an array pattern"),
+    }
+
+    assert_eq!(
+        Some(SyntaxKind::BinaryExpr),
+        arms[1].guard().map(|g| g.syntax().kind())
+    );
+    assert_eq!(SyntaxKind::VariableExpr, arms[1].expr().syntax().kind());
+    assert!(arms[0].guard().is_none());
+    assert_eq!(
+        SyntaxKind::LiteralPattern,
+        arms[2].pattern().syntax().kind()
+    );
+    assert_eq!(
+        SyntaxKind::WildcardPattern,
+        arms[3].pattern().syntax().kind()
+    );
+}
+
+#[test]
+fn match_arms_need_a_pattern_and_an_arrow() {
+    let tree: SyntaxTree = "match x { + => 1 };".into();
+    let messages: Vec<&str> = tree.errors().map(|e| e.message()).collect();
+
+    assert_eq!(vec!["Expected pattern"], messages);
+
+    let tree: SyntaxTree = "match x { 1 2 };".into();
+    let messages: Vec<&str> = tree.errors().map(|e| e.message()).collect();
+
+    assert_eq!(vec!["Expected '=>'"], messages);
+}
+
+#[test]
+fn for_stmt_node_round_trips() {
+    let tree: SyntaxTree = "for x in xs {}".into();
+    let for_node: &ForStmtNode = expect_stmt_node(&tree);
+
+    let stmt_node = StmtNode::cast(for_node.to_inner()).expect("Valid StmtNode");
+
+    match stmt_node.kind() {
+        StmtKind::ForStmt(_) => {}
+        _ => panic!("Could not round trip Stmt"),
+    };
+}
+
+#[test]
+fn loops_take_an_optional_label() {
+    let tree: SyntaxTree = "outer: for x in xs { break outer; }".into();
+    let for_node: &ForStmtNode = expect_stmt_node(&tree);
+    let break_node = BreakStmtNode::from_inner(for_node.stmts().stmts().next().unwrap().syntax());
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!(Some("outer"), for_node.label().map(|t| t.lexeme()));
+    assert_eq!("x", for_node.identifier().lexeme());
+    assert_eq!(Some("outer"), break_node.label().map(|t| t.lexeme()));
+
+    let tree: SyntaxTree = "while x { continue; }".into();
+    let while_node: &WhileStmtNode = expect_stmt_node(&tree);
+    let continue_node =
+        ContinueStmtNode::from_inner(while_node.stmts().stmts().next().unwrap().syntax());
+
+    assert!(while_node.label().is_none());
+    assert!(continue_node.label().is_none());
+}
+
+#[test]
+fn labels_must_name_a_loop() {
+    let tree: SyntaxTree = "outer: if x {}".into();
+    let messages: Vec<&str> = tree.errors().map(|e| e.message()).collect();
+
+    assert_eq!(vec!["Expected 'while' or 'for' after a label"], messages);
+}
+
 #[test]
 fn if_stmt_node_handles_conditional() {
     let tree: SyntaxTree = "if(true) {}".into();
@@ -243,6 +400,19 @@ fn variable_declaration_stmt_node_handles_expr() {
     );
 }
 
+#[test]
+fn variable_declaration_stmt_node_handles_type_annotation() {
+    let tree: SyntaxTree = "let x: Array[i64] = [42];".into();
+    let node: &VariableDeclarationStmtNode = expect_stmt_node(&tree);
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!(
+        Some("Array[i64]".to_string()),
+        node.type_ref().map(|t| t.to_string())
+    );
+    assert_eq!(SyntaxKind::ArrayLiteralExpr, node.expr().syntax().kind());
+}
+
 #[test]
 fn variable_declaration_stmt_node_round_trips() {
     let tree: SyntaxTree = "let x = 42;".into();
@@ -715,6 +885,99 @@ fn object_property_rval_handles_multiple_source_expr_and_identifier() {
     );
 }
 
+#[test]
+fn array_literal_expr_node_handles_zero_elements() {
+    let tree: SyntaxTree = "[]".into();
+    let node: &ArrayLiteralExprNode = expect_expr_node(&tree);
+
+    assert_eq!(0, node.elements().count());
+}
+
+#[test]
+fn array_literal_expr_node_handles_multiple_elements() {
+    let tree: SyntaxTree = "[1, 2+2, foo]".into();
+    let node: &ArrayLiteralExprNode = expect_expr_node(&tree);
+    let kinds: Vec<SyntaxKind> = node.elements().map(|e| e.syntax().kind()).collect();
+
+    assert_eq!(
+        vec![
+            SyntaxKind::PrimitiveLiteralExpr,
+            SyntaxKind::BinaryExpr,
+            SyntaxKind::VariableExpr
+        ],
+        kinds
+    );
+}
+
+#[test]
+fn array_literal_expr_node_round_trips() {
+    let tree: SyntaxTree = "[1]".into();
+    let node: &ArrayLiteralExprNode = expect_expr_node(&tree);
+
+    let expr = ExprNode::cast(node.to_inner()).unwrap();
+
+    match expr.kind() {
+        ExprKind::ArrayLiteralExpr(_) => {}
+        _ => panic!("Could not round trip Expr"),
+    };
+}
+
+#[test]
+fn index_expr_node_handles_source_expr_and_index() {
+    let tree: SyntaxTree = "foo[1+1]".into();
+    let node: &IndexExprNode = expect_expr_node(&tree);
+
+    assert_eq!(SyntaxKind::VariableExpr, node.source_expr().syntax().kind());
+    assert_eq!(SyntaxKind::BinaryExpr, node.index().syntax().kind());
+}
+
+#[test]
+fn index_expr_node_handles_nested_indexing() {
+    let tree: SyntaxTree = "foo[0][1]".into();
+    let outer: &IndexExprNode = expect_expr_node(&tree);
+    let inner = match outer.source_expr().kind() {
+        ExprKind::IndexExpr(inner) => inner,
+        _ => panic!("Expected an index expression at the source of the index"),
+    };
+
+    assert_eq!(
+        SyntaxKind::VariableExpr,
+        inner.source_expr().syntax().kind()
+    );
+}
+
+#[test]
+fn index_expr_node_round_trips() {
+    let tree: SyntaxTree = "foo[0]".into();
+    let node: &IndexExprNode = expect_expr_node(&tree);
+
+    let expr = ExprNode::cast(node.to_inner()).unwrap();
+
+    match expr.kind() {
+        ExprKind::IndexExpr(_) => {}
+        _ => panic!("Could not round trip Expr"),
+    };
+}
+
+#[test]
+fn index_rval_handles_source_expr_and_index() {
+    let tree: SyntaxTree = "foo.bar[2] = 42;".into();
+    let node: &AssignmentStmtNode = expect_stmt_node(&tree);
+    let element = match node.rval().kind() {
+        RValKind::IndexRVal(element) => element,
+        _ => panic!("Expected an index node"),
+    };
+
+    assert_eq!(
+        SyntaxKind::ObjectPropertyExpr,
+        element.source_expr().syntax().kind()
+    );
+    assert_eq!(
+        SyntaxKind::PrimitiveLiteralExpr,
+        element.index().syntax().kind()
+    );
+}
+
 #[test]
 fn binary_expr_node_does_not_swap_operands() {
     let tree: SyntaxTree = "2+foo()".into();
@@ -842,6 +1105,662 @@ fn variable_expr_node_round_trips() {
     };
 }
 
+#[test]
+fn syntax_nodes_and_tokens_track_source_ranges() {
+    let tree: SyntaxTree = "let x = 5;\nlet y = x;".into();
+    let root = tree.root().as_node().unwrap();
+    let stmts = StmtListStmtNode::from_inner(root);
+    let second = stmts.stmts().nth(1).unwrap();
+    let declaration = VariableDeclarationStmtNode::from_inner(second.to_inner());
+
+    assert_eq!(TextRange::new(0, 21), root.range());
+    assert_eq!(TextRange::new(11, 21), second.to_inner().range());
+    assert_eq!(TextRange::new(15, 16), declaration.identifier().range());
+}
+
+#[test]
+fn error_reports_point_at_the_offending_token() {
+    let tree: SyntaxTree = "let x = 1;\nlet  y 5;".into();
+    let error = tree.errors().next().expect("Expected an error report");
+
+    assert_eq!(2, error.line());
+    assert_eq!((8, 9), error.columns());
+    assert_eq!(TextRange::new(18, 19), error.range());
+    assert_eq!(&[TokenKind::Equals], error.expected());
+    assert_eq!(TokenKind::IntegerLiteral, error.found());
+}
+
+#[test]
+fn error_reports_at_end_of_file_find_end_of_file() {
+    let tree: SyntaxTree = "let x = 1;\nlet y = 2".into();
+    let error = tree.errors().next().expect("Expected an error report");
+
+    assert_eq!(&[TokenKind::SemiColon], error.expected());
+    assert_eq!(TokenKind::EndOfFile, error.found());
+    assert_eq!(2, error.line());
+}
+
+#[test]
+fn error_reports_display_their_location_and_expectation() {
+    let tree: SyntaxTree = "let x 5;".into();
+    let error = tree.errors().next().expect("Expected an error report");
+
+    assert_eq!(
+        "1:7: Expected equals (expected Equals, found IntegerLiteral)",
+        error.to_string()
+    );
+}
+
+#[test]
+fn error_reports_describe_tokenizer_errors() {
+    let tree: SyntaxTree = "let \u{e9} = '\u{e9}\u{e9}';\nlet s = \"caf\u{e9}".into();
+    let errors: Vec<String> = tree
+        .errors()
+        .filter(|error| error.found() == TokenKind::TombStone)
+        .map(ErrorReport::to_string)
+        .collect();
+
+    assert_eq!(
+        vec![
+            "1:9: Glyph literals must contain exactly one character",
+            "2:9: Unterminated string literal",
+        ],
+        errors
+    );
+}
+
+#[test]
+fn error_reports_render_the_source_line_with_carets() {
+    let source = "let x = 1;\nlet y 500;";
+    let tree: SyntaxTree = source.into();
+    let rendered = tree.errors().next().unwrap().render(source);
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert_eq!("2 | let y 500;", lines[2]);
+    assert!(lines[3].starts_with("  |       "));
+    assert!(lines[3].contains("^^^"));
+}
+
+#[test]
+fn error_reports_keep_the_tabs_of_the_source_line_under_it() {
+    let source = "fn f() {\n\t\tlet y 500;\n}";
+    let tree: SyntaxTree = source.into();
+    let rendered = tree.errors().next().unwrap().render(source);
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    assert_eq!("2 | \t\tlet y 500;", lines[2]);
+    assert!(lines[3].starts_with("  | \t\t      "));
+    assert!(lines[3].contains("^^^"));
+}
+
+#[test]
+fn struct_declaration_stmt_node_handles_empty_structs() {
+    let tree: SyntaxTree = "struct FizzBuzz;".into();
+    let node: &StructDeclarationStmtNode = expect_stmt_node(&tree);
+
+    assert_eq!("FizzBuzz", node.identifier().lexeme());
+    assert_eq!(0, node.fields().count());
+}
+
+#[test]
+fn struct_declaration_stmt_node_has_fields_with_modifiers() {
+    let tree: SyntaxTree =
+        "struct Foo { Bar: i32, imm Fizz: i32, get Buzz: i32, set Flop: i32, get set Zap: i32, }"
+            .into();
+    let node: &StructDeclarationStmtNode = expect_stmt_node(&tree);
+    let fields: Vec<(&str, &str, bool, bool, bool)> = node
+        .fields()
+        .map(|f| {
+            (
+                f.identifier().lexeme(),
+                f.type_ref().identifier().lexeme(),
+                f.is_immutable(),
+                f.is_readable(),
+                f.is_writable(),
+            )
+        })
+        .collect();
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!(
+        vec![
+            ("Bar", "i32", false, false, false),
+            ("Fizz", "i32", true, false, false),
+            ("Buzz", "i32", false, true, false),
+            ("Flop", "i32", false, false, true),
+            ("Zap", "i32", false, true, true),
+        ],
+        fields
+    );
+}
+
+#[test]
+fn struct_field_modifiers_can_name_fields_and_variables() {
+    let tree: SyntaxTree =
+        "struct Foo { get: i32, imm set: i32 } let get = 1; fn Get(self) { self.Set(get); }".into();
+    let node: &StructDeclarationStmtNode = expect_stmt_node(&tree);
+    let fields: Vec<(&str, bool, bool)> = node
+        .fields()
+        .map(|f| (f.identifier().lexeme(), f.is_immutable(), f.is_writable()))
+        .collect();
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!(vec![("get", false, false), ("set", true, false)], fields);
+}
+
+#[test]
+fn struct_field_modifiers_cannot_be_repeated() {
+    let tree: SyntaxTree = "struct Foo { get imm get Bar: i32 }".into();
+    let errors: Vec<String> = tree.errors().map(|e| e.message().to_string()).collect();
+    let node: &StructDeclarationStmtNode = expect_stmt_node(&tree);
+    let field = node.fields().next().expect("Expected a field");
+
+    assert_eq!(vec!["Field modifiers cannot be repeated"], errors);
+    assert_eq!("Bar", field.identifier().lexeme());
+}
+
+#[test]
+fn struct_literal_expr_node_has_fields_in_source_order() {
+    let tree: SyntaxTree = "let foo = Foo { b: 1, a: 2 + 2 };".into();
+    let stmt: &VariableDeclarationStmtNode = expect_stmt_node(&tree);
+    let node = match stmt.expr().kind() {
+        ExprKind::StructLiteralExpr(literal) => literal,
+        _ => panic!("Could not parse struct literal"),
+    };
+    let fields: Vec<(&str, SyntaxKind)> = node
+        .fields()
+        .map(|(name, value)| (name.lexeme(), value.syntax().kind()))
+        .collect();
+
+    assert_eq!("Foo", node.identifier().lexeme());
+    assert_eq!(
+        vec![
+            ("b", SyntaxKind::PrimitiveLiteralExpr),
+            ("a", SyntaxKind::BinaryExpr)
+        ],
+        fields
+    );
+}
+
+#[test]
+fn conditions_followed_by_blocks_are_not_struct_literals() {
+    let tree: SyntaxTree = "if done { x = 1; } while (Foo {}) { }".into();
+    let node: &IfStmtNode = expect_stmt_node(&tree);
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!(SyntaxKind::VariableExpr, node.condition().syntax().kind());
+}
+
+#[test]
+fn impl_stmt_node_has_type_and_methods() {
+    let tree: SyntaxTree = "impl Dog { fn bark(self) {} fn new() {} }".into();
+    let node: &ImplStmtNode = expect_stmt_node(&tree);
+    let methods: Vec<&str> = node.methods().map(|m| m.identifier().lexeme()).collect();
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!("Dog", node.type_identifier().lexeme());
+    assert!(node.interface_identifier().is_none());
+    assert_eq!(vec!["bark", "new"], methods);
+}
+
+#[test]
+fn impl_stmt_node_can_name_an_interface() {
+    let tree: SyntaxTree = "impl Animal for Dog { fn make_sound(self) {} }".into();
+    let node: &ImplStmtNode = expect_stmt_node(&tree);
+
+    assert_eq!("Dog", node.type_identifier().lexeme());
+    assert_eq!(
+        Some("Animal"),
+        node.interface_identifier().map(|i| i.lexeme())
+    );
+}
+
+#[test]
+fn impl_stmt_reports_items_that_are_not_methods() {
+    let tree: SyntaxTree = "impl Dog { let x = 1; }".into();
+
+    assert!(tree.errors().count() > 0);
+}
+
+#[test]
+fn interface_declaration_stmt_node_has_method_signatures() {
+    let tree: SyntaxTree = "interface Animal { fn make_sound(self); fn feed(self, food); }".into();
+    let node: &InterfaceDeclarationStmtNode = expect_stmt_node(&tree);
+    let methods: Vec<(&str, usize)> = node
+        .methods()
+        .map(|m| (m.identifier().lexeme(), m.arguments().count()))
+        .collect();
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!("Animal", node.identifier().lexeme());
+    assert_eq!(vec![("make_sound", 1), ("feed", 2)], methods);
+}
+
+#[test]
+fn struct_declaration_stmt_node_has_type_parameters_with_bounds() {
+    let tree: SyntaxTree = "struct Pair[T, U: Mammal + Pet] { First: T, Second: List[U] }".into();
+    let node: &StructDeclarationStmtNode = expect_stmt_node(&tree);
+    let parameters: Vec<(&str, Vec<&str>)> = node
+        .type_parameters()
+        .map(|p| {
+            (
+                p.identifier().lexeme(),
+                p.bounds().map(|b| b.lexeme()).collect(),
+            )
+        })
+        .collect();
+    let field_types: Vec<String> = node.fields().map(|f| f.type_ref().to_string()).collect();
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!(
+        vec![("T", vec![]), ("U", vec!["Mammal", "Pet"])],
+        parameters
+    );
+    assert_eq!(vec!["T", "List[U]"], field_types);
+}
+
+#[test]
+fn function_declaration_stmt_node_has_type_parameters_and_annotated_parameters() {
+    let tree: SyntaxTree = "fn feed[Z: Mammal](self, value: Z, count) {}".into();
+    let node: &FunctionDeclarationStmtNode = expect_stmt_node(&tree);
+    let parameters: Vec<(&str, Option<String>)> = node
+        .parameters()
+        .map(|(p, t)| (p.lexeme(), t.map(|t| t.to_string())))
+        .collect();
+    let arguments: Vec<&str> = node.arguments().map(|a| a.lexeme()).collect();
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!("feed", node.identifier().lexeme());
+    assert_eq!(1, node.type_parameters().count());
+    assert_eq!(
+        vec![
+            ("self", None),
+            ("value", Some("Z".to_string())),
+            ("count", None)
+        ],
+        parameters
+    );
+    assert_eq!(vec!["self", "value", "count"], arguments);
+}
+
+#[test]
+fn function_declaration_stmt_node_has_return_type() {
+    let tree: SyntaxTree = "fn half(value: f64) -> f64 { return value / 2.0; }".into();
+    let node: &FunctionDeclarationStmtNode = expect_stmt_node(&tree);
+    let parameters: Vec<(&str, Option<String>)> = node
+        .parameters()
+        .map(|(p, t)| (p.lexeme(), t.map(|t| t.to_string())))
+        .collect();
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!(vec![("value", Some("f64".to_string()))], parameters);
+    assert_eq!(
+        Some("f64".to_string()),
+        node.return_type().map(|t| t.to_string())
+    );
+    assert_eq!(1, node.stmts().stmts().count());
+}
+
+#[test]
+fn impl_stmt_node_has_type_parameters() {
+    let tree: SyntaxTree = "impl Animal for Cage[T: Mammal] { fn make_sound(self) {} }".into();
+    let node: &ImplStmtNode = expect_stmt_node(&tree);
+    let parameters: Vec<&str> = node
+        .type_parameters()
+        .map(|p| p.identifier().lexeme())
+        .collect();
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!("Cage", node.type_identifier().lexeme());
+    assert_eq!(
+        Some("Animal"),
+        node.interface_identifier().map(|i| i.lexeme())
+    );
+    assert_eq!(vec!["T"], parameters);
+}
+
+#[test]
+fn unterminated_type_parameters_are_reported() {
+    let tree: SyntaxTree = "struct Pair[T: Mammal + { }".into();
+
+    assert!(tree.errors().count() > 0);
+}
+
+#[test]
+fn import_stmt_node_has_dotted_module_path() {
+    let tree: SyntaxTree = "import Notepad.Store;".into();
+    let node: &ImportStmtNode = expect_stmt_node(&tree);
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!("Notepad.Store", node.module_name());
+    assert_eq!("Store", node.identifier().lexeme());
+}
+
+#[test]
+fn export_stmt_node_may_declare_its_value() {
+    let tree: SyntaxTree = "export Reducers = {};".into();
+    let node: &ExportStmtNode = expect_stmt_node(&tree);
+
+    assert_eq!(0, tree.errors().count());
+    assert_eq!("Reducers", node.identifier().lexeme());
+    assert!(node.expr().is_some());
+
+    let tree: SyntaxTree = "export CreateNotepadStore;".into();
+    let node: &ExportStmtNode = expect_stmt_node(&tree);
+
+    assert_eq!("CreateNotepadStore", node.identifier().lexeme());
+    assert!(node.expr().is_none());
+}
+
+#[test]
+fn syntax_tree_text_reproduces_source_byte_for_byte() {
+    let sources = [
+        "",
+        "   \n\t",
+        "// just a comment",
+        "  let x = 1; // trailing\n\n",
+        "fn add(a: i64, b: i64) -> i64 {\n\treturn a + b;\n}\r\n",
+        "let s = \"hello\"; /* block */ let c = 'c';",
+        "let x = ;\nlet y = 2",
+        "struct Pair[T: Mammal + { }",
+        "1 + (2 * 3",
+        include_str!("../../../../lang/Notepad/App.newt"),
+        include_str!("../../../../lang/Notepad/Store.newt"),
+        include_str!("../../../../lang/Notepad/CursorReducers.newt"),
+    ];
+
+    for source in sources.iter() {
+        let tree: SyntaxTree = (*source).into();
+
+        assert_eq!(*source, tree.text());
+        assert_eq!(*source, SyntaxTree::from_stmts(source).text());
+    }
+}
+
+#[test]
+fn reparse_matches_parsing_the_edited_text() {
+    let source = "fn f() {\n\tlet x = 1;\n\tif x { x = 2; }\n}\nlet y = f();\n";
+    let edits = [
+        TextEdit::new(TextRange::at(19, 1), "100"),
+        TextEdit::insert(37, " let z = x;"),
+        TextEdit::delete(TextRange::at(28, 7)),
+        TextEdit::insert(21, " // swallows"),
+        TextEdit::insert(21, "\""),
+        TextEdit::insert(21, "/* open"),
+        TextEdit::insert(37, "/* closed */"),
+        TextEdit::new(TextRange::at(11, 3), "return"),
+        TextEdit::delete(TextRange::at(39, 1)),
+        TextEdit::insert(source.len(), "let w = y;"),
+    ];
+
+    for edit in edits.iter() {
+        let expected = SyntaxTree::from_stmts(&edit.apply(source, 0));
+        let reparsed = SyntaxTree::from_stmts(source).reparse(edit);
+
+        assert_eq!(expected.text(), reparsed.text());
+        assert_eq!(
+            format!("{:?}", expected.root()),
+            format!("{:?}", reparsed.root()),
+            "{:?}",
+            edit
+        );
+        assert_eq!(expected.errors().count(), reparsed.errors().count());
+    }
+}
+
+#[test]
+fn reparse_reuses_statements_before_the_edit() {
+    let source = "let a = 1;\nfn f() { let b = 2; }\n";
+    let tree = SyntaxTree::from_stmts(source);
+    let reparsed = tree.reparse(&TextEdit::new(TextRange::at(28, 1), "3"));
+    let first_stmt = |tree: &SyntaxTree| tree.root().as_node().unwrap().nth_node(0).clone();
+
+    assert_eq!("let a = 1;\nfn f() { let b = 3; }\n", reparsed.text());
+    assert_eq!(0, reparsed.errors().count());
+    assert!(std::ptr::eq(
+        first_stmt(&tree).children().as_ptr(),
+        first_stmt(&reparsed).children().as_ptr()
+    ));
+}
+
+#[test]
+fn reparse_recovers_from_errors_in_the_original_text() {
+    let tree = SyntaxTree::from_stmts("let x = ;");
+    let reparsed = tree.reparse(&TextEdit::insert(8, "1"));
+
+    assert!(tree.errors().count() > 0);
+    assert_eq!(0, reparsed.errors().count());
+    assert_eq!("let x = 1;", reparsed.text());
+}
+
+#[test]
+fn parser_reports_each_typo_in_a_long_file_once() {
+    let mut lines: Vec<String> = (0..500)
+        .map(|i| format!("let x{} = {} + f(x, [1, 2], {{ a: {} }});", i, i, i))
+        .collect();
+    lines[99] = "let x99 = 99 + f(x [1, 2], { a: 99 });".to_string();
+    lines[249] = "let x249 = 249 + ;".to_string();
+    lines[449] = "let x449 = 449 + f(x, [1, 2], { a: 449 })".to_string();
+    let source = lines.join("\n");
+
+    let tree = SyntaxTree::from_stmts(&source);
+    let lines: Vec<usize> = tree.errors().map(|error| error.line()).collect();
+
+    assert_eq!(vec![100, 250, 450], lines);
+    assert_eq!(source, tree.text());
+}
+
+#[test]
+fn parser_reports_a_missing_token_after_the_token_before_it() {
+    let tree = SyntaxTree::from_stmts("let x = 1\nlet y = 2;");
+    let error = tree.errors().next().expect("Expected an error report");
+
+    assert_eq!(1, tree.errors().count());
+    assert_eq!(1, error.line());
+    assert_eq!(TextRange::new(9, 9), error.range());
+    assert_eq!(TokenKind::Let, error.found());
+}
+
+#[test]
+fn parser_wraps_exactly_the_unexpected_tokens_in_an_error_node() {
+    let tree = SyntaxTree::from_stmts("let x = f(1 2 3, 4);\nlet y = 1;");
+    let errors: Vec<&SyntaxNode> = tree
+        .iter()
+        .filter_map(|element| element.as_node())
+        .filter(|node| match node.kind() {
+            SyntaxKind::Error(_) => true,
+            _ => false,
+        })
+        .collect();
+
+    assert_eq!(1, errors.len());
+    assert_eq!("2 3", errors[0].text().trim_end());
+    assert_eq!(1, tree.errors().count());
+}
+
+#[test]
+fn parser_recovers_at_the_end_of_a_block() {
+    let tree = SyntaxTree::from_stmts("fn f() {\n\treturn 1\n}\nfn g() { return 2; }");
+    let stmts: Vec<SyntaxKind> = tree
+        .root()
+        .as_node()
+        .unwrap()
+        .nodes()
+        .map(|n| n.kind())
+        .collect();
+
+    assert_eq!(1, tree.errors().count());
+    assert_eq!(
+        vec![
+            SyntaxKind::FunctionDeclarationStmt,
+            SyntaxKind::FunctionDeclarationStmt
+        ],
+        stmts
+    );
+}
+
+#[test]
+fn parser_reports_stray_closing_brackets() {
+    let tree = SyntaxTree::from_stmts("let x = 1;\n}\n) let y = 2;\nlet z = a b;");
+    let lines: Vec<usize> = tree.errors().map(|error| error.line()).collect();
+
+    assert_eq!(vec![2, 3, 4], lines);
+}
+
+#[test]
+fn parser_recovers_within_an_interpolation() {
+    let tree = SyntaxTree::from_stmts("let s = \"a ${b c} d ${e}\";\nlet t = 1;");
+    let interpolation = tree
+        .iter()
+        .filter_map(|element| element.as_node())
+        .find(|node| node.kind() == SyntaxKind::InterpolatedStringExpr)
+        .expect("Expected an interpolated string");
+
+    assert_eq!(1, tree.errors().count());
+    assert_eq!("\"a ${b c} d ${e}\"", interpolation.text());
+}
+
+#[test]
+fn parser_does_not_panic_on_any_prefix_of_a_source() {
+    let sources = [
+        include_str!("../../../../lang/Notepad/App.newt"),
+        include_str!("../../../../lang/Notepad/CursorReducers.newt"),
+        "struct P[T: A + B] { imm x: T, y: List[T] }\nimpl P for Q { fn f(a: T) -> T { return P { x: a, y: [] }; } }\ninterface Q { fn f(a) -> T; }\nimport A.B;\nexport c = { d: -e.f[0](!g) };\nlet s = \"a\\\"${ {b: \"${c}\"}.b } d ${e}\";",
+    ];
+
+    for source in sources.iter() {
+        for end in 0..=source.len() {
+            let prefix = &source[..end];
+            let tree = SyntaxTree::from_stmts(prefix);
+
+            assert_eq!(prefix, tree.text());
+        }
+    }
+}
+
+fn int_or_overflow(result: Option<i64>) -> Result<NewtValue, RuntimeErrorKind> {
+    result.map(NewtValue::Int).ok_or(RuntimeErrorKind::Overflow)
+}
+
+fn kind_of(result: NewtResult) -> Result<NewtValue, RuntimeErrorKind> {
+    result.map_err(|e| e.kind().clone())
+}
+
+/// Floats are compared by their bits so that NaNs produced by both sides are equal.
+fn same_float(result: NewtResult, expected: f64) -> bool {
+    match result {
+        Ok(NewtValue::Float(actual)) => actual.to_bits() == expected.to_bits(),
+        _ => false,
+    }
+}
+
+proptest! {
+    #[test]
+    fn int_addition_is_checked(l: i64, r: i64) {
+        prop_assert_eq!(
+            kind_of(NewtValue::Int(l) + NewtValue::Int(r)),
+            int_or_overflow(l.checked_add(r))
+        );
+    }
+
+    #[test]
+    fn int_subtraction_is_checked(l: i64, r: i64) {
+        prop_assert_eq!(
+            kind_of(NewtValue::Int(l) - NewtValue::Int(r)),
+            int_or_overflow(l.checked_sub(r))
+        );
+    }
+
+    #[test]
+    fn int_multiplication_is_checked(l: i64, r: i64) {
+        prop_assert_eq!(
+            kind_of(NewtValue::Int(l) * NewtValue::Int(r)),
+            int_or_overflow(l.checked_mul(r))
+        );
+    }
+
+    #[test]
+    fn int_division_is_checked(l: i64, r: i64) {
+        let expected = if r == 0 {
+            Err(RuntimeErrorKind::DivisionByZero)
+        } else {
+            int_or_overflow(l.checked_div(r))
+        };
+
+        prop_assert_eq!(kind_of(NewtValue::Int(l) / NewtValue::Int(r)), expected);
+    }
+
+    #[test]
+    fn int_division_by_zero_is_an_error(l: i64) {
+        prop_assert_eq!(
+            kind_of(NewtValue::Int(l) / NewtValue::Int(0)),
+            Err(RuntimeErrorKind::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn float_arithmetic_follows_ieee(l: f64, r: f64) {
+        prop_assert!(same_float(NewtValue::Float(l) + NewtValue::Float(r), l + r));
+        prop_assert!(same_float(NewtValue::Float(l) - NewtValue::Float(r), l - r));
+        prop_assert!(same_float(NewtValue::Float(l) * NewtValue::Float(r), l * r));
+        prop_assert!(same_float(NewtValue::Float(l) / NewtValue::Float(r), l / r));
+    }
+
+    #[test]
+    fn ints_are_promoted_when_mixed_with_floats(l: i64, r: f64) {
+        let int = l as f64;
+
+        prop_assert!(same_float(NewtValue::Int(l) + NewtValue::Float(r), int + r));
+        prop_assert!(same_float(NewtValue::Float(r) + NewtValue::Int(l), r + int));
+        prop_assert!(same_float(NewtValue::Int(l) - NewtValue::Float(r), int - r));
+        prop_assert!(same_float(NewtValue::Float(r) - NewtValue::Int(l), r - int));
+        prop_assert!(same_float(NewtValue::Int(l) * NewtValue::Float(r), int * r));
+        prop_assert!(same_float(NewtValue::Float(r) * NewtValue::Int(l), r * int));
+        prop_assert!(same_float(NewtValue::Int(l) / NewtValue::Float(r), int / r));
+        prop_assert!(same_float(NewtValue::Float(r) / NewtValue::Int(l), r / int));
+    }
+
+    #[test]
+    fn addition_and_multiplication_commute(l: i64, r: i64) {
+        prop_assert_eq!(
+            kind_of(NewtValue::Int(l) + NewtValue::Int(r)),
+            kind_of(NewtValue::Int(r) + NewtValue::Int(l))
+        );
+        prop_assert_eq!(
+            kind_of(NewtValue::Int(l) * NewtValue::Int(r)),
+            kind_of(NewtValue::Int(r) * NewtValue::Int(l))
+        );
+    }
+
+    #[test]
+    fn subtraction_undoes_addition(l: i64, r: i64) {
+        if let Ok(sum) = NewtValue::Int(l) + NewtValue::Int(r) {
+            prop_assert_eq!(kind_of(sum - NewtValue::Int(r)), Ok(NewtValue::Int(l)));
+        }
+    }
+
+    #[test]
+    fn division_and_remainder_recombine(l: i64, r in any::<i64>().prop_filter("nonzero", |r| *r != 0)) {
+        if let (Ok(quotient), Ok(remainder)) = (
+            NewtValue::Int(l) / NewtValue::Int(r),
+            NewtValue::Int(l) % NewtValue::Int(r),
+        ) {
+            let recombined = (quotient * NewtValue::Int(r)).and_then(|q| q + remainder);
+            prop_assert_eq!(kind_of(recombined), Ok(NewtValue::Int(l)));
+        }
+    }
+
+    #[test]
+    fn arithmetic_on_non_numbers_is_a_type_error(l: i64) {
+        let string = || NewtValue::String(NewtString::new("newt"));
+
+        prop_assert_eq!(kind_of(NewtValue::Int(l) + string()), Err(RuntimeErrorKind::TypeError));
+        prop_assert_eq!(kind_of(string() - NewtValue::Int(l)), Err(RuntimeErrorKind::TypeError));
+        prop_assert_eq!(kind_of(NewtValue::Bool(true) * NewtValue::Int(l)), Err(RuntimeErrorKind::TypeError));
+        prop_assert_eq!(kind_of(NewtValue::Int(l) / NewtValue::Null), Err(RuntimeErrorKind::TypeError));
+    }
+}
+
 fn expect_stmt_node<N: TransparentNewType<Inner = SyntaxNode>>(tree: &SyntaxTree) -> &N {
     tree.root()
         .as_node()
@@ -0,0 +1,43 @@
+use crate::featurez::syntax::TextRange;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextEdit {
+    range: TextRange,
+    insert: String,
+}
+
+impl TextEdit {
+    pub fn new(range: TextRange, insert: &str) -> TextEdit {
+        TextEdit {
+            range,
+            insert: insert.to_string(),
+        }
+    }
+
+    pub fn insert(offset: usize, insert: &str) -> TextEdit {
+        TextEdit::new(TextRange::at(offset, 0), insert)
+    }
+
+    pub fn delete(range: TextRange) -> TextEdit {
+        TextEdit::new(range, "")
+    }
+
+    pub fn range(&self) -> TextRange {
+        self.range
+    }
+
+    pub fn inserted_text(&self) -> &str {
+        &self.insert
+    }
+
+    pub fn delta(&self) -> isize {
+        self.insert.len() as isize - self.range.len() as isize
+    }
+
+    pub fn apply(&self, text: &str, offset: usize) -> String {
+        let start = self.range.start() - offset;
+        let end = self.range.end() - offset;
+
+        format!("{}{}{}", &text[..start], self.insert, &text[end..])
+    }
+}
@@ -0,0 +1,93 @@
+use ansi_term::Color::Red;
+use std::fmt::{Debug, Display, Error, Formatter};
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TextRange {
+    start: usize,
+    end: usize,
+}
+
+impl TextRange {
+    pub fn new(start: usize, end: usize) -> TextRange {
+        assert!(start <= end, "A TextRange cannot end before it starts");
+
+        TextRange { start, end }
+    }
+
+    pub fn at(offset: usize, length: usize) -> TextRange {
+        TextRange::new(offset, offset + length)
+    }
+
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset < self.end
+    }
+
+    pub fn contains_range(&self, other: TextRange) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+}
+
+impl Display for TextRange {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+impl Debug for TextRange {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+pub(crate) fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+
+    (
+        text[..offset].matches('\n').count() + 1,
+        text[line_start..offset].chars().count() + 1,
+    )
+}
+
+pub(crate) fn render_snippet(text: &str, range: TextRange) -> String {
+    let (line, _) = line_column(text, range.start());
+    let source_line = text.lines().nth(line - 1).unwrap_or("");
+    let line_start = text[..range.start()].rfind('\n').map_or(0, |i| i + 1);
+    // Tabs are kept so that the carets line up however wide the terminal draws them
+    let padding: String = text[line_start..range.start()]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let gutter = " ".repeat(line.to_string().len());
+    let line_end = text[range.start()..]
+        .find('\n')
+        .map_or(text.len(), |i| range.start() + i);
+    let width = text[range.start()..range.end().min(line_end)]
+        .chars()
+        .count();
+
+    format!(
+        "{} |\n{} | {}\n{} | {}{}\n",
+        gutter,
+        line,
+        source_line,
+        gutter,
+        padding,
+        Red.paint("^".repeat(width.max(1)))
+    )
+}
@@ -38,7 +38,7 @@ impl TreeSink for TextTreeSink {
         }
         children.reverse();
 
-        let node = SyntaxNode::new(kind, offset - offset_start, children);
+        let node = SyntaxNode::new(kind, offset_start, offset - offset_start, children);
 
         self.working_set.push(SyntaxElement::Node(node));
     }
@@ -6,5 +6,5 @@ mod tokenkind;
 
 pub use self::token::*;
 pub use self::token_source::StrTokenSource;
-pub use self::tokenize::tokenize;
+pub use self::tokenize::{tokenize, tomb_stone_message};
 pub use self::tokenkind::*;
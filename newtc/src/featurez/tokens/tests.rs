@@ -5,6 +5,7 @@ use super::*;
 use std::string::ToString;
 
 use crate::featurez::syntax::TokenSource;
+use proptest::prelude::*;
 
 macro_rules! single_token_tests {
 	($($name:ident: $value:expr,)*) => {
@@ -38,31 +39,48 @@ single_token_tests! {
     minus_token: ("-", TokenKind::Minus),
     star_token: ("*", TokenKind::Star),
     slash_token: ("/", TokenKind::Slash),
+    percent_token: ("%", TokenKind::Percent),
 
     greater_token: (">", TokenKind::Greater),
     less_token: ("<", TokenKind::Less),
 
     ampersand_token: ("&", TokenKind::Ampersand),
     pipe_token: ("|", TokenKind::Pipe),
+    caret_token: ("^", TokenKind::Caret),
     bang_token: ("!", TokenKind::Bang),
 
     // double character tokens
     equals_equals_token: ("==", TokenKind::EqualsEquals),
+    bang_equals_token: ("!=", TokenKind::BangEquals),
     greater_equals_token: (">=", TokenKind::GreaterEquals),
     less_equals_token: ("<=", TokenKind::LessEquals),
     ampersand_ampersand_token: ("&&", TokenKind::AmpersandAmpersand),
     pipe_pipe_token: ("||", TokenKind::PipePipe),
+    arrow_token: ("->", TokenKind::Arrow),
+    fat_arrow_token: ("=>", TokenKind::FatArrow),
+    dot_dot_token: ("..", TokenKind::DotDot),
+    star_star_token: ("**", TokenKind::StarStar),
+    less_less_token: ("<<", TokenKind::LessLess),
+    greater_greater_token: (">>", TokenKind::GreaterGreater),
 
     // literals
     integer_literal_token: ("123", TokenKind::IntegerLiteral),
     float_literal_token: ("3.14", TokenKind::FloatLiteral),
     string_literal_token: ("\"Hello, world!\"", TokenKind::StringLiteral),
     glyph_literal_token: ("'c'", TokenKind::GlyphLiteral),
+    escaped_string_literal_token: (r#""say \"hi\" \${not} \\""#, TokenKind::StringLiteral),
+    escaped_glyph_literal_token: (r"'\''", TokenKind::GlyphLiteral),
+    unicode_glyph_literal_token: ("'\u{e9}'", TokenKind::GlyphLiteral),
+    unicode_escape_glyph_literal_token: (r"'\u{1F600}'", TokenKind::GlyphLiteral),
+    unicode_string_literal_token: ("\"h\u{e9}llo \u{1F98E}\"", TokenKind::StringLiteral),
 
     // identifiers
     identifier_all_characters: ("_abc123", TokenKind::Identifier),
     identifier_alphanumeric: ("abc123", TokenKind::Identifier),
     identifier_alpha: ("abc", TokenKind::Identifier),
+    identifier_accented: ("caf\u{e9}", TokenKind::Identifier),
+    identifier_non_latin: ("\u{540d}\u{524d}", TokenKind::Identifier),
+    identifier_combining_mark: ("e\u{301}", TokenKind::Identifier),
 
     // keywords
     fn_keyword: ("fn", TokenKind::Fn),
@@ -74,8 +92,23 @@ single_token_tests! {
     in_keyword: ("in", TokenKind::In),
     while_keyword: ("while", TokenKind::While),
     let_keyword: ("let", TokenKind::Let),
+    struct_keyword: ("struct", TokenKind::Struct),
+    impl_keyword: ("impl", TokenKind::Impl),
+    interface_keyword: ("interface", TokenKind::Interface),
+    import_keyword: ("import", TokenKind::Import),
+    export_keyword: ("export", TokenKind::Export),
+    imm_contextual_keyword: ("imm", TokenKind::Identifier),
+    get_contextual_keyword: ("get", TokenKind::Identifier),
+    set_contextual_keyword: ("set", TokenKind::Identifier),
     true_keyword: ("true", TokenKind::True),
     false_keyword: ("false", TokenKind::False),
+    throw_keyword: ("throw", TokenKind::Throw),
+    try_keyword: ("try", TokenKind::Try),
+    catch_keyword: ("catch", TokenKind::Catch),
+    finally_keyword: ("finally", TokenKind::Finally),
+    break_keyword: ("break", TokenKind::Break),
+    continue_keyword: ("continue", TokenKind::Continue),
+    match_keyword: ("match", TokenKind::Match),
 }
 /*
 
@@ -195,3 +228,160 @@ fn token_source_token_gets_end_of_file_when_out_of_bounds() {
     assert_eq!(token_source.token(6).token_kind(), TokenKind::EndOfFile);
     assert_eq!(token_source.token(10).token_kind(), TokenKind::EndOfFile);
 }
+
+#[test]
+fn interpolated_strings_are_split_around_their_expressions() {
+    use TokenKind::*;
+
+    assert_token_sequence(
+        r#""a ${b} c ${d + 1} e""#,
+        &[
+            StringHead,
+            Identifier,
+            StringMiddle,
+            Identifier,
+            WhiteSpace,
+            Plus,
+            WhiteSpace,
+            IntegerLiteral,
+            StringTail,
+            EndOfFile,
+        ],
+    );
+}
+
+#[test]
+fn interpolations_can_contain_braces_and_strings() {
+    use TokenKind::*;
+
+    assert_token_sequence(
+        r#""${ {a: "${b}"}.a }""#,
+        &[
+            StringHead, WhiteSpace, LeftBrace, Identifier, Colon, WhiteSpace, StringHead,
+            Identifier, StringTail, RightBrace, Dot, Identifier, WhiteSpace, StringTail, EndOfFile,
+        ],
+    );
+}
+
+#[test]
+fn unterminated_interpolated_strings_are_tombstones() {
+    use TokenKind::*;
+
+    assert_token_sequence(
+        r#""a ${b} c"#,
+        &[StringHead, Identifier, TombStone, EndOfFile],
+    );
+    assert_token_sequence(r#""a ${b"#, &[StringHead, Identifier, EndOfFile]);
+}
+
+#[test]
+fn token_lengths_count_bytes() {
+    let tokens = tokenize("'\u{e9}' \"\u{1F98E}\" caf\u{e9} // \u{e9}\n");
+    let lengths: Vec<usize> = tokens.iter().map(Token::lexeme_length).collect();
+
+    assert_eq!(vec![4, 1, 6, 1, 5, 1, 5, 1, 0], lengths);
+}
+
+#[test]
+fn identifiers_follow_unicode_xid() {
+    use TokenKind::*;
+
+    assert_token_sequence("x\u{660}", &[Identifier, EndOfFile]);
+    assert_token_sequence("\u{301}e", &[TombStone, Identifier, EndOfFile]);
+    assert_token_sequence("1\u{e9}", &[IntegerLiteral, Identifier, EndOfFile]);
+    assert_token_sequence(
+        "a\u{1F98E}b",
+        &[Identifier, TombStone, Identifier, EndOfFile],
+    );
+}
+
+#[test]
+fn glyphs_hold_exactly_one_character() {
+    use TokenKind::*;
+
+    assert_token_sequence("''", &[TombStone, EndOfFile]);
+    assert_token_sequence("'ab' c", &[TombStone, WhiteSpace, Identifier, EndOfFile]);
+    assert_token_sequence(r"'\u{1F600}x'", &[TombStone, EndOfFile]);
+}
+
+#[test]
+fn block_comments_run_to_the_first_closing_delimiter() {
+    use TokenKind::*;
+
+    assert_token_sequence("/* a\n* b */x", &[CommentBlock, Identifier, EndOfFile]);
+    assert_token_sequence(
+        "/**/ */",
+        &[CommentBlock, WhiteSpace, Star, Slash, EndOfFile],
+    );
+}
+
+#[test]
+fn block_comments_do_not_nest() {
+    use TokenKind::*;
+
+    assert_token_sequence(
+        "/* a /* b */ c */",
+        &[
+            CommentBlock,
+            WhiteSpace,
+            Identifier,
+            WhiteSpace,
+            Star,
+            Slash,
+            EndOfFile,
+        ],
+    );
+}
+
+#[test]
+fn unterminated_block_comments_are_tombstones() {
+    use TokenKind::*;
+
+    assert_token_sequence("x /* a", &[Identifier, WhiteSpace, TombStone, EndOfFile]);
+    assert_token_sequence("/* a /* b", &[TombStone, EndOfFile]);
+    assert_token_sequence("/*/", &[TombStone, EndOfFile]);
+    assert_eq!("Unterminated block comment", tomb_stone_message("/* a"));
+}
+
+#[test]
+fn unterminated_literals_are_tombstones() {
+    use TokenKind::*;
+
+    assert_token_sequence("'a", &[TombStone, EndOfFile]);
+    assert_token_sequence(
+        "'\u{e9}\nx",
+        &[TombStone, WhiteSpace, Identifier, EndOfFile],
+    );
+    assert_token_sequence(r"'\", &[TombStone, EndOfFile]);
+    assert_token_sequence("\"caf\u{e9}", &[TombStone, EndOfFile]);
+    assert_token_sequence("\"\u{e9}\\", &[TombStone, EndOfFile]);
+}
+
+proptest! {
+    #[test]
+    fn tokens_cover_any_text(text in any::<String>()) {
+        let tokens = tokenize(&text);
+        let length: usize = tokens.iter().map(Token::lexeme_length).sum();
+
+        let empty = tokens
+            .iter()
+            .filter(|token| token.token_kind() != TokenKind::EndOfFile)
+            .any(|token| token.lexeme_length() == 0);
+
+        prop_assert_eq!(text.len(), length);
+        prop_assert!(!empty);
+    }
+}
+
+#[test]
+fn tomb_stones_are_described() {
+    assert_eq!("Unterminated string literal", tomb_stone_message("\"abc"));
+    assert_eq!("Unterminated string literal", tomb_stone_message("} abc"));
+    assert_eq!("Unterminated glyph literal", tomb_stone_message("'a"));
+    assert_eq!("Unterminated glyph literal", tomb_stone_message("'"));
+    assert_eq!(
+        "Glyph literals must contain exactly one character",
+        tomb_stone_message("'ab'")
+    );
+    assert_eq!("Unexpected character", tomb_stone_message("\u{1F98E}"));
+}
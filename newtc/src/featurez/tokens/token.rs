@@ -5,20 +5,31 @@ use std::fmt::{Debug, Display, Error, Formatter};
 pub struct Token {
     token_kind: TokenKind,
     length: usize,
+    // The keyword an identifier stands for in the places it is one, like `get` before a field
+    contextual_kind: Option<TokenKind>,
 }
 
 impl Token {
     pub fn new(token_kind: TokenKind, length: usize) -> Token {
-        Token { token_kind, length }
+        Token {
+            token_kind,
+            length,
+            contextual_kind: None,
+        }
     }
 
-    fn merge_as(token_kind: TokenKind, left: &Token, right: &Token) -> Token {
+    pub fn contextual_keyword(keyword: TokenKind, length: usize) -> Token {
         Token {
-            token_kind,
-            length: left.length + right.length,
+            token_kind: TokenKind::Identifier,
+            length,
+            contextual_kind: Some(keyword),
         }
     }
 
+    fn merge_as(token_kind: TokenKind, left: &Token, right: &Token) -> Token {
+        Token::new(token_kind, left.length + right.length)
+    }
+
     pub fn tomb_stone() -> Token {
         Token::new(TokenKind::TombStone, 0)
     }
@@ -34,6 +45,10 @@ impl Token {
     pub fn lexeme_length(&self) -> usize {
         self.length
     }
+
+    pub fn contextual_kind(&self) -> Option<TokenKind> {
+        self.contextual_kind
+    }
 }
 
 impl Display for Token {
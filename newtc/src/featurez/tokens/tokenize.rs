@@ -1,13 +1,41 @@
 use crate::featurez::cursor::Cursor;
 use crate::featurez::syntax::TokenSource;
 use crate::featurez::tokens::{Token, TokenKind};
+use unicode_xid::UnicodeXID;
 
 pub fn tokenize(text: &str) -> Vec<Token> {
     let mut tokens: Vec<Token> = vec![];
     let mut source = text;
+    // The number of braces open within each interpolation in a string literal, innermost last.
+    // The string resumes at the closing brace that leaves none open.
+    let mut interpolations: Vec<usize> = vec![];
 
     while !source.is_empty() {
-        let mut token = next_token(source);
+        let token = match interpolations.last_mut() {
+            Some(0) if source.starts_with('}') => scan_string_continuation(source),
+            _ => next_token(source),
+        };
+
+        match token.token_kind() {
+            TokenKind::StringHead => interpolations.push(0),
+            TokenKind::StringTail => {
+                interpolations.pop();
+            }
+            TokenKind::LeftBrace => {
+                if let Some(open) = interpolations.last_mut() {
+                    *open += 1;
+                }
+            }
+            TokenKind::RightBrace => {
+                if let Some(open) = interpolations.last_mut() {
+                    *open -= 1;
+                }
+            }
+            TokenKind::TombStone if source.starts_with('}') => {
+                interpolations.pop();
+            }
+            _ => {}
+        }
 
         tokens.push(token);
         source = &source[token.lexeme_length()..];
@@ -29,8 +57,23 @@ fn next_token(text: &str) -> Token {
     } else if let Some(token) = lex_single_character_token(&mut cursor) {
         token
     } else {
-        cursor;
-        Token::new(TokenKind::TombStone, 1)
+        cursor.next();
+        Token::new(TokenKind::TombStone, cursor.len())
+    }
+}
+
+pub fn tomb_stone_message(lexeme: &str) -> &'static str {
+    // A tombstone starting with a brace is the rest of a string after an interpolation
+    if lexeme.starts_with('"') || lexeme.starts_with('}') {
+        "Unterminated string literal"
+    } else if lexeme.starts_with("/*") {
+        "Unterminated block comment"
+    } else if lexeme.len() > 1 && lexeme.starts_with('\'') && lexeme.ends_with('\'') {
+        "Glyph literals must contain exactly one character"
+    } else if lexeme.starts_with('\'') {
+        "Unterminated glyph literal"
+    } else {
+        "Unexpected character"
     }
 }
 
@@ -72,11 +115,13 @@ fn lex_single_character_token(cursor: &mut Cursor) -> Option<Token> {
         Some('-') => make_token(TokenKind::Minus),
         Some('*') => make_token(TokenKind::Star),
         Some('/') => make_token(TokenKind::Slash),
+        Some('%') => make_token(TokenKind::Percent),
         Some('>') => make_token(TokenKind::Greater),
         Some('<') => make_token(TokenKind::Less),
 
         Some('|') => make_token(TokenKind::Pipe),
         Some('&') => make_token(TokenKind::Ampersand),
+        Some('^') => make_token(TokenKind::Caret),
         Some('!') => make_token(TokenKind::Bang),
 
         _ => return None,
@@ -96,16 +141,32 @@ fn lex_two_character_token(cursor: &mut Cursor) -> Option<Token> {
     }
 
     fn make_token_consume_line(cursor: &mut Cursor, token_kind: TokenKind) -> Token {
-        let mut length = 2;
         cursor.next();
         cursor.next();
 
         while cursor.current().is_some() && cursor.match_char_predicate(&|c: char| c != '\n') {
-            length += 1;
             cursor.next();
         }
 
-        Token::new(token_kind, length)
+        Token::new(token_kind, cursor.len())
+    }
+
+    // An unterminated block comment runs to the end of the text as a tombstone
+    fn make_token_consume_block(cursor: &mut Cursor) -> Token {
+        cursor.next();
+        cursor.next();
+
+        while cursor.current().is_some() && !cursor.match_str("*/") {
+            cursor.next();
+        }
+
+        if cursor.match_str("*/") {
+            cursor.next();
+            cursor.next();
+            Token::new(TokenKind::CommentBlock, cursor.len())
+        } else {
+            Token::new(TokenKind::TombStone, cursor.len())
+        }
     }
 
     let current = cursor.current();
@@ -114,11 +175,19 @@ fn lex_two_character_token(cursor: &mut Cursor) -> Option<Token> {
     if let (Some(current), Some(next)) = (cursor.current(), cursor.peek(1)) {
         let token = match (current, next) {
             ('=', '=') => make_token(cursor, TokenKind::EqualsEquals),
+            ('!', '=') => make_token(cursor, TokenKind::BangEquals),
+            ('-', '>') => make_token(cursor, TokenKind::Arrow),
+            ('=', '>') => make_token(cursor, TokenKind::FatArrow),
+            ('.', '.') => make_token(cursor, TokenKind::DotDot),
             ('>', '=') => make_token(cursor, TokenKind::GreaterEquals),
             ('<', '=') => make_token(cursor, TokenKind::LessEquals),
+            ('>', '>') => make_token(cursor, TokenKind::GreaterGreater),
+            ('<', '<') => make_token(cursor, TokenKind::LessLess),
+            ('*', '*') => make_token(cursor, TokenKind::StarStar),
             ('|', '|') => make_token(cursor, TokenKind::PipePipe),
             ('&', '&') => make_token(cursor, TokenKind::AmpersandAmpersand),
             ('/', '/') => make_token_consume_line(cursor, TokenKind::CommentLine),
+            ('/', '*') => make_token_consume_block(cursor),
             _ => return None,
         };
 
@@ -143,8 +212,8 @@ fn lex_multi_character_token(cursor: &mut Cursor) -> Option<Token> {
 }
 
 fn scan_identifier(cursor: &mut Cursor) -> Option<Token> {
-    let starting_predicate = |c: char| c.is_alphabetic() || c == '_';
-    let suffix_predicate = |c: char| c.is_alphanumeric() || c == '_';
+    let starting_predicate = |c: char| UnicodeXID::is_xid_start(c) || c == '_';
+    let suffix_predicate = UnicodeXID::is_xid_continue;
 
     if !cursor.match_char_predicate(&starting_predicate) {
         return None;
@@ -159,6 +228,8 @@ fn scan_identifier(cursor: &mut Cursor) -> Option<Token> {
         Some(Token::new(TokenKind::UnderScore, cursor.len()))
     } else if let Some(keyword) = match_identifier_to_keyword(&lexeme) {
         Some(Token::new(keyword, cursor.len()))
+    } else if let Some(keyword) = match_identifier_to_contextual_keyword(&lexeme) {
+        Some(Token::contextual_keyword(keyword, cursor.len()))
     } else {
         Some(Token::new(TokenKind::Identifier, cursor.len()))
     }
@@ -171,16 +242,43 @@ fn scan_string_literal(cursor: &mut Cursor) -> Option<Token> {
 
     cursor.next();
 
-    while cursor.current().is_some() && cursor.match_char_predicate(&|c: char| c != '"') {
-        cursor.next();
-    }
+    Some(scan_string_fragment(
+        cursor,
+        TokenKind::StringLiteral,
+        TokenKind::StringHead,
+    ))
+}
 
-    if cursor.match_char('"') {
-        cursor.next();
-        Some(Token::new(TokenKind::StringLiteral, cursor.len()))
-    } else {
-        Some(Token::new(TokenKind::TombStone, cursor.len()))
+fn scan_string_continuation(text: &str) -> Token {
+    let mut cursor = Cursor::new(text);
+    cursor.next();
+
+    scan_string_fragment(&mut cursor, TokenKind::StringTail, TokenKind::StringMiddle)
+}
+
+fn scan_string_fragment(cursor: &mut Cursor, end: TokenKind, interpolation: TokenKind) -> Token {
+    while let Some(c) = cursor.current() {
+        match c {
+            '"' => {
+                cursor.next();
+                return Token::new(end, cursor.len());
+            }
+            '$' if cursor.peek(1) == Some('{') => {
+                cursor.next();
+                cursor.next();
+                return Token::new(interpolation, cursor.len());
+            }
+            '\\' => {
+                cursor.next();
+                cursor.next();
+            }
+            _ => {
+                cursor.next();
+            }
+        }
     }
+
+    Token::new(TokenKind::TombStone, cursor.len())
 }
 
 fn scan_glyph_literal(cursor: &mut Cursor) -> Option<Token> {
@@ -188,19 +286,46 @@ fn scan_glyph_literal(cursor: &mut Cursor) -> Option<Token> {
         return None;
     }
 
-    cursor.next();
     cursor.next();
 
-    while cursor.current().is_some() && cursor.match_char_predicate(&|c: char| c != '\'') {
-        cursor.next();
-    }
+    // A glyph runs to the next quote on the same line, and holds exactly one character or
+    // escape sequence
+    let mut characters = 0;
+    while let Some(c) = cursor.current() {
+        match c {
+            '\'' => {
+                cursor.next();
+                let kind = match characters {
+                    1 => TokenKind::GlyphLiteral,
+                    _ => TokenKind::TombStone,
+                };
+
+                return Some(Token::new(kind, cursor.len()));
+            }
+            '\n' => break,
+            '\\' => {
+                cursor.next();
+                if cursor.next() == Some('u') && cursor.match_char('{') {
+                    while cursor.current().is_some()
+                        && cursor.match_char_predicate(|c: char| c != '}' && c != '\'')
+                    {
+                        cursor.next();
+                    }
+
+                    if cursor.match_char('}') {
+                        cursor.next();
+                    }
+                }
+            }
+            _ => {
+                cursor.next();
+            }
+        }
 
-    if cursor.match_char('\'') {
-        cursor.next();
-        Some(Token::new(TokenKind::GlyphLiteral, cursor.len()))
-    } else {
-        Some(Token::new(TokenKind::TombStone, cursor.len()))
+        characters += 1;
     }
+
+    Some(Token::new(TokenKind::TombStone, cursor.len()))
 }
 
 fn scan_numeric_literal(cursor: &mut Cursor) -> Option<Token> {
@@ -235,8 +360,29 @@ fn match_identifier_to_keyword(lexeme: &str) -> Option<TokenKind> {
         "in" => Some(TokenKind::In),
         "while" => Some(TokenKind::While),
         "let" => Some(TokenKind::Let),
+        "struct" => Some(TokenKind::Struct),
+        "impl" => Some(TokenKind::Impl),
+        "interface" => Some(TokenKind::Interface),
+        "import" => Some(TokenKind::Import),
+        "export" => Some(TokenKind::Export),
         "true" => Some(TokenKind::True),
         "false" => Some(TokenKind::False),
+        "throw" => Some(TokenKind::Throw),
+        "try" => Some(TokenKind::Try),
+        "catch" => Some(TokenKind::Catch),
+        "finally" => Some(TokenKind::Finally),
+        "break" => Some(TokenKind::Break),
+        "continue" => Some(TokenKind::Continue),
+        "match" => Some(TokenKind::Match),
+        _ => None,
+    }
+}
+
+fn match_identifier_to_contextual_keyword(lexeme: &str) -> Option<TokenKind> {
+    match lexeme {
+        "imm" => Some(TokenKind::Imm),
+        "get" => Some(TokenKind::Get),
+        "set" => Some(TokenKind::Set),
         _ => None,
     }
 }
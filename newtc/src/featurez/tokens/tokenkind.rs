@@ -18,9 +18,12 @@ pub enum TokenKind {
 
     Comma,
     Dot,
+    DotDot,
     Colon,
     SemiColon,
     UnderScore,
+    Arrow,
+    FatArrow,
 
     // math, comparison, and logic operators
     Equals,
@@ -28,21 +31,31 @@ pub enum TokenKind {
     Plus,
     Minus,
     Star,
+    StarStar,
     Slash,
+    Percent,
     Greater,
     GreaterEquals,
+    GreaterGreater,
     Less,
     LessEquals,
+    LessLess,
     Ampersand,
     AmpersandAmpersand,
     Pipe,
     PipePipe,
+    Caret,
     Bang,
+    BangEquals,
 
     // literals
     IntegerLiteral,
     FloatLiteral,
     StringLiteral,
+    // the pieces of a string literal around its interpolations, as in `"a ${b} c ${d} e"`
+    StringHead,
+    StringMiddle,
+    StringTail,
     GlyphLiteral,
 
     Identifier,
@@ -56,8 +69,23 @@ pub enum TokenKind {
     In,
     While,
     Let,
+    Struct,
+    Impl,
+    Interface,
+    Import,
+    Export,
+    Imm,
+    Get,
+    Set,
     True,
     False,
+    Throw,
+    Try,
+    Catch,
+    Finally,
+    Break,
+    Continue,
+    Match,
 
     EndOfFile,
     TombStone,
@@ -68,7 +96,7 @@ impl TokenKind {
         match self {
             TokenKind::TombStone
             | TokenKind::CommentLine
-            | TokenKind::CommentLine
+            | TokenKind::CommentBlock
             | TokenKind::WhiteSpace => true,
             _ => false,
         }
@@ -79,12 +107,20 @@ impl TokenKind {
             TokenKind::Plus
             | TokenKind::Minus
             | TokenKind::Star
+            | TokenKind::StarStar
             | TokenKind::Slash
+            | TokenKind::Percent
             | TokenKind::Greater
             | TokenKind::GreaterEquals
+            | TokenKind::GreaterGreater
             | TokenKind::Less
             | TokenKind::LessEquals
+            | TokenKind::LessLess
             | TokenKind::EqualsEquals
+            | TokenKind::BangEquals
+            | TokenKind::Ampersand
+            | TokenKind::Pipe
+            | TokenKind::Caret
             | TokenKind::AmpersandAmpersand
             | TokenKind::PipePipe => true,
             _ => false,
@@ -0,0 +1,6 @@
+#![allow(unused)]
+
+#[macro_use]
+extern crate lazy_static;
+
+pub mod featurez;
@@ -1,16 +1,11 @@
 #![allow(unused)]
 
-#[macro_use]
-extern crate lazy_static;
-
-mod featurez;
-
-use crate::featurez::*;
+use newtc::featurez::*;
 
 use std::env::args;
 use std::io::{stdin, stdout};
-use std::io::{Error, Write};
-use std::path::PathBuf;
+use std::io::{Error, Read, Write};
+use std::path::{Path, PathBuf};
 use std::str::Chars;
 
 struct Config {
@@ -19,12 +14,17 @@ struct Config {
     display_evaluation: bool,
     display_help: bool,
     entry_file: Option<PathBuf>,
+    source_root: Option<PathBuf>,
 }
 
 fn main() {
     let arguments: Vec<String> = args().collect();
 
-    let borrowed_arguments = arguments.iter().map(|s| s.as_ref()).collect();
+    let borrowed_arguments: Vec<&str> = arguments.iter().map(|s| s.as_ref()).collect();
+
+    if borrowed_arguments.get(1) == Some(&"fmt") {
+        std::process::exit(format_command(&borrowed_arguments[2..]));
+    }
 
     let config = Config::parse(&borrowed_arguments);
 
@@ -34,6 +34,7 @@ fn main() {
     }
 
     let mut vm = VirtualMachine::new();
+    vm.set_source_root(config.module_root());
 
     if let Some(pathname) = &config.entry_file {
         let file_contents = std::fs::read_to_string(pathname).ok();
@@ -56,8 +57,21 @@ fn process_input(config: &Config, input: &str, vm: &mut VirtualMachine) {
         println!("{}\n", tree);
     }
 
+    if tree.errors().count() != 0 {
+        for error in tree.errors() {
+            eprintln!("{}", error.render(input));
+        }
+
+        return;
+    }
+
     let evaluation = vm.interpret(tree);
 
+    if let Err(error) = &evaluation {
+        eprintln!("{}", error.render(input));
+        return;
+    }
+
     if config.display_evaluation {
         println!("{:?}\n", evaluation);
     }
@@ -110,8 +124,91 @@ fn balanced_braces(input_buffer: &str) -> bool {
     return braces_counted == 0 && parenthesis_counted == 0;
 }
 
+/// Returns the exit code, which is 1 if any file was unformatted or unparsable.
+fn format_command(arguments: &[&str]) -> i32 {
+    let check = arguments.contains(&"--check");
+    let paths: Vec<&Path> = arguments
+        .iter()
+        .filter(|arg| **arg != "--check")
+        .map(Path::new)
+        .collect();
+
+    if paths.is_empty() {
+        let mut source = String::new();
+
+        if let Err(error) = stdin().read_to_string(&mut source) {
+            eprintln!("<stdin>: {}", error);
+            return 1;
+        }
+
+        return match format_input("<stdin>", &source) {
+            Some(formatted) if check => (formatted != source) as i32,
+            Some(formatted) => {
+                print!("{}", formatted);
+                0
+            }
+            None => 1,
+        };
+    }
+
+    let mut status = 0;
+
+    for path in paths {
+        if !format_file(path, check) {
+            status = 1;
+        }
+    }
+
+    status
+}
+
+fn format_file(path: &Path, check: bool) -> bool {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(error) => {
+            eprintln!("{}: {}", path.display(), error);
+            return false;
+        }
+    };
+
+    let formatted = match format_input(&path.display().to_string(), &source) {
+        Some(formatted) => formatted,
+        None => return false,
+    };
+
+    if formatted == source {
+        return true;
+    }
+
+    if check {
+        println!("{}", path.display());
+        return false;
+    }
+
+    if let Err(error) = std::fs::write(path, formatted) {
+        eprintln!("{}: {}", path.display(), error);
+        return false;
+    }
+
+    true
+}
+
+fn format_input(name: &str, source: &str) -> Option<String> {
+    match format_source(source) {
+        Ok(formatted) => Some(formatted),
+        Err(errors) => {
+            for error in errors {
+                eprintln!("{}: {}", name, error.render(source));
+            }
+
+            None
+        }
+    }
+}
+
 fn print_help() {
-    println!("usage: newtc [--entry-file (path)] [--tokens] [--parse] [--no-eval] [--help]");
+    println!("usage: newtc [--entry-file (path)] [--source-root (path)] [--tokens] [--parse] [--no-eval] [--help]");
+    println!("       newtc fmt [--check] [(path) ...]");
 }
 
 impl Config {
@@ -120,7 +217,8 @@ impl Config {
         let parse_flag = Config::parse_parse_flag(arguments);
         let no_eval_flag = Config::parse_no_eval_flag(arguments);
         let help_flag = Config::parse_help_flag(arguments);
-        let entry_file = Config::parse_entry_file(arguments);
+        let entry_file = Config::parse_path(arguments, "--entry-file");
+        let source_root = Config::parse_path(arguments, "--source-root");
 
         Config {
             display_tokenization: tokens_flag,
@@ -128,7 +226,22 @@ impl Config {
             display_evaluation: !no_eval_flag,
             display_help: help_flag,
             entry_file,
+            source_root,
+        }
+    }
+
+    /// The directory imports are found in: the `--source-root` if one was given, otherwise the
+    /// directory containing the entry file, otherwise the current directory.
+    pub fn module_root(&self) -> PathBuf {
+        if let Some(source_root) = &self.source_root {
+            return source_root.clone();
         }
+
+        self.entry_file
+            .as_ref()
+            .and_then(|entry_file| entry_file.parent())
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map_or_else(|| PathBuf::from("."), PathBuf::from)
     }
 
     fn parse_tokens_flag(arguments: &Vec<&str>) -> bool {
@@ -147,15 +260,15 @@ impl Config {
         arguments.contains(&"--help")
     }
 
-    fn parse_entry_file(arguments: &Vec<&str>) -> Option<PathBuf> {
-        let entry_file_flag_position = arguments.iter().position(|arg| *arg == "--entry-file");
+    fn parse_path(arguments: &Vec<&str>, flag: &str) -> Option<PathBuf> {
+        let flag_position = arguments.iter().position(|arg| *arg == flag);
 
-        match entry_file_flag_position {
+        match flag_position {
             Some(position) => {
-                let entry_file = arguments
+                let path = arguments
                     .get(position + 1)
                     .and_then(|s| Some(PathBuf::from(s)));
-                return entry_file;
+                return path;
             }
             None => None,
         }
@@ -259,3 +372,54 @@ fn config_parse_output_mode_finds_all_flags_and_entry_file() {
     assert_eq!(true, config.display_help);
     assert_eq!(Some("pathname"), config.entry_file.unwrap().to_str());
 }
+
+#[test]
+fn config_parse_source_root_defaults_to_entry_file_directory() {
+    let args = vec!["--entry-file", "lang/Notepad/App.newt"];
+
+    let config = Config::parse(&args);
+
+    assert_eq!(None, config.source_root);
+    assert_eq!(PathBuf::from("lang/Notepad"), config.module_root());
+    assert_eq!(PathBuf::from("."), Config::parse(&vec![]).module_root());
+}
+
+#[test]
+fn config_parse_source_root_finds_pathname() {
+    let args = vec![
+        "--entry-file",
+        "lang/Notepad/App.newt",
+        "--source-root",
+        "lang",
+    ];
+
+    let config = Config::parse(&args);
+
+    assert_eq!(PathBuf::from("lang"), config.module_root());
+}
+
+#[test]
+fn format_command_checks_and_rewrites_files() {
+    let path = std::env::temp_dir().join(format!("newtc-fmt-{}.newt", std::process::id()));
+    let path_name = path.to_str().unwrap();
+    std::fs::write(&path, "fn f(a,b){return a+b;}").unwrap();
+
+    assert_eq!(1, format_command(&["--check", path_name]));
+    assert_eq!(
+        "fn f(a,b){return a+b;}",
+        std::fs::read_to_string(&path).unwrap()
+    );
+
+    assert_eq!(0, format_command(&[path_name]));
+    assert_eq!(
+        "fn f(a, b) {\n\treturn a + b;\n}\n",
+        std::fs::read_to_string(&path).unwrap()
+    );
+    assert_eq!(0, format_command(&["--check", path_name]));
+
+    std::fs::write(&path, "let x = ;").unwrap();
+    assert_eq!(1, format_command(&[path_name]));
+    assert_eq!("let x = ;", std::fs::read_to_string(&path).unwrap());
+
+    std::fs::remove_file(&path).unwrap();
+}